@@ -3,7 +3,7 @@ use kick::driver::run_driver_tests;
 #[tokio::main]
 async fn main() {
     println!("Starting KICK Driver Tests...\n");
-    
+
     match run_driver_tests().await {
         Ok(()) => {
             println!("\n🎉 All driver tests completed successfully!");
@@ -14,4 +14,4 @@ async fn main() {
             std::process::exit(1);
         }
     }
-}
\ No newline at end of file
+}
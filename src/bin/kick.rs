@@ -1,7 +1,13 @@
 use clap::{Parser, Subcommand};
+use futures::StreamExt;
+use kick::error::{TimeoutDetail, TimeoutPhase};
 use kick::prelude::*;
 use kick::sec::{HeaderValidator, PathValidator, UrlValidator};
+use kick::storage::StorageManager;
+use kick::{ByteRange, ErrorKind, RequestOptions};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[derive(Parser)]
 #[command(name = "kick")]
@@ -22,6 +28,14 @@ struct Cli {
     #[arg(short = 'V', long = "version", action = clap::ArgAction::SetTrue)]
     version: bool,
 
+    /// Abort the whole command, including retries, after this many seconds
+    #[arg(long = "max-time", global = true, value_name = "SECONDS", value_parser = parse_seconds)]
+    max_time: Option<Duration>,
+
+    /// Read extra "Key: Value" headers from a file; -H wins on conflicts
+    #[arg(long = "header-file", global = true, value_name = "PATH")]
+    header_file: Option<std::path::PathBuf>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -44,6 +58,26 @@ enum Commands {
         /// Pretty print JSON responses
         #[arg(short = 'p', long = "pretty")]
         pretty: bool,
+        /// Include the status line and response headers in the output
+        #[arg(short = 'i', long = "include")]
+        include: bool,
+        /// Stream the body as NDJSON, printing one record per line as it arrives
+        #[arg(long = "ndjson", conflicts_with_all = ["save", "include"])]
+        ndjson: bool,
+        /// JSON pointer (e.g. /level) to print from each NDJSON record instead of the whole record
+        #[arg(
+            short = 'q',
+            long = "query",
+            value_name = "POINTER",
+            requires = "ndjson"
+        )]
+        query: Option<String>,
+        /// Serve a cached response stored less than SECONDS ago, caching fresh ones
+        #[arg(long = "cache", value_name = "SECONDS", value_parser = parse_seconds, conflicts_with = "ndjson")]
+        cache: Option<Duration>,
+        /// Bypass the response cache, neither reading nor writing it
+        #[arg(long = "no-cache")]
+        no_cache: bool,
         /// Verbose output with plugin logging
         #[arg(short = 'v', long = "verbose")]
         verbose: bool,
@@ -55,6 +89,17 @@ enum Commands {
         /// JSON data to post
         #[arg(short = 'd', long = "data")]
         data: String,
+        /// Send the data verbatim instead of as parsed JSON (`@FILE` reads it from a file)
+        #[arg(long = "raw")]
+        raw: bool,
+        /// Content type of a --raw body
+        #[arg(
+            long = "content-type",
+            value_name = "TYPE",
+            default_value = "application/json",
+            requires = "raw"
+        )]
+        content_type: String,
         /// Custom headers (format: "Key:Value")
         #[arg(short = 'H', long = "header", action = clap::ArgAction::Append)]
         headers: Vec<String>,
@@ -81,6 +126,12 @@ enum Commands {
         /// Download to local ./.downloads/ directory instead of XDG location
         #[arg(short = 'l', long = "local")]
         local: bool,
+        /// Download only this byte range (START-END, START- or -LEN)
+        #[arg(short = 'r', long = "range")]
+        range: Option<ByteRange>,
+        /// Abort (and delete the partial file) once the download passes this many bytes
+        #[arg(long = "max-size", value_name = "BYTES")]
+        max_size: Option<u64>,
         /// Custom headers (format: "Key:Value")
         #[arg(short = 'H', long = "header", action = clap::ArgAction::Append)]
         headers: Vec<String>,
@@ -157,16 +208,93 @@ enum Commands {
         #[arg(short = 'v', long = "verbose")]
         verbose: bool,
     },
+    /// Make a request with any method
+    Request {
+        /// HTTP method, e.g. OPTIONS or PURGE
+        method: String,
+        /// URL to request
+        url: String,
+        /// Body sent verbatim (`@FILE` reads it from a file)
+        #[arg(short = 'd', long = "data")]
+        data: Option<String>,
+        /// Content type of the body (default: sniffed from --data)
+        #[arg(long = "content-type", value_name = "TYPE", requires = "data")]
+        content_type: Option<String>,
+        /// Custom headers (format: "Key:Value")
+        #[arg(short = 'H', long = "header", action = clap::ArgAction::Append)]
+        headers: Vec<String>,
+        /// User agent string
+        #[arg(short = 'A', long = "user-agent")]
+        user_agent: Option<String>,
+        /// Pretty print JSON responses
+        #[arg(short = 'p', long = "pretty")]
+        pretty: bool,
+        /// Verbose output with plugin logging
+        #[arg(short = 'v', long = "verbose")]
+        verbose: bool,
+    },
+    /// Show client capabilities and check connectivity
+    Doctor {
+        /// URL used for the connectivity check
+        #[arg(default_value = "https://httpbin.org/get")]
+        url: String,
+        /// Verbose output with plugin logging
+        #[arg(short = 'v', long = "verbose")]
+        verbose: bool,
+    },
+    /// Check a URL against the SSRF policy without requesting it
+    CheckUrl {
+        /// URL to check
+        url: String,
+    },
+    /// Manage the configuration file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Manage cached `get --cache` responses
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
 }
 
-/// Load ASCII logo from logo.txt file
-fn load_logo() -> String {
-    match std::fs::read_to_string("logo.txt") {
-        Ok(logo) => logo,
-        Err(_) => "KICK".to_string(), // Fallback if logo.txt not found
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Write a config file to the default location
+    Init {
+        /// Write the fully-commented example instead of the bare defaults
+        #[arg(long = "example")]
+        example: bool,
+        /// Overwrite an existing config file
+        #[arg(short = 'f', long = "force")]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Remove every cached response
+    Clear,
+}
+
+/// Copies embedded at build time, so an installed binary shows them from any directory
+const EMBEDDED_LOGO: &str = include_str!("../../logo.txt");
+const EMBEDDED_LICENSE: &str = include_str!("../../LICENSE");
+
+/// `path` in the current directory if it can be read, else the embedded copy
+fn load_asset(path: &str, embedded: &'static str) -> std::borrow::Cow<'static, str> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => contents.into(),
+        Err(_) => embedded.into(),
     }
 }
 
+/// ASCII logo; a logo.txt in the current directory overrides the built-in one
+fn load_logo() -> std::borrow::Cow<'static, str> {
+    load_asset("logo.txt", EMBEDDED_LOGO)
+}
+
 /// Show comprehensive help or help for specific command
 fn show_help(command: Option<String>) {
     match command.as_deref() {
@@ -181,6 +309,13 @@ fn show_help(command: Option<String>) {
             println!("  -A, --user-agent <AGENT>  User agent string");
             println!("  -s, --save <FILE>         Save response to file");
             println!("  -p, --pretty              Pretty print JSON responses");
+            println!("  -i, --include             Include status line and response headers");
+            println!(
+                "      --ndjson              Stream NDJSON, printing each record as it arrives"
+            );
+            println!("  -q, --query <POINTER>     With --ndjson, print only this JSON pointer per record");
+            println!("      --cache <SECONDS>     Serve a response cached less than SECONDS ago");
+            println!("      --no-cache            Bypass the response cache");
             println!("  -v, --verbose             Verbose output with plugin logging\n");
             println!("Examples:");
             println!("  kick get https://api.example.com/users");
@@ -188,6 +323,8 @@ fn show_help(command: Option<String>) {
                 "  kick get -H \"Authorization: Bearer token\" https://api.example.com/protected"
             );
             println!("  kick get -p -s response.json https://api.example.com/data");
+            println!("  kick get --ndjson --query /level https://logs.example.com/tail");
+            println!("  kick get --cache 60 https://api.example.com/slow-report");
         }
         Some("post") => {
             println!("KICK POST Command Help\n");
@@ -197,6 +334,10 @@ fn show_help(command: Option<String>) {
             println!("  <URL>  URL to request\n");
             println!("Options:");
             println!("  -d, --data <DATA>         JSON data to post");
+            println!(
+                "      --raw                 Send the data verbatim; @FILE reads it from a file"
+            );
+            println!("      --content-type <TYPE> Content type of a --raw body (default: application/json)");
             println!("  -H, --header <HEADER>     Custom headers (format: \"Key:Value\")");
             println!("  -A, --user-agent <AGENT>  User agent string");
             println!("  -s, --save <FILE>         Save response to file");
@@ -205,6 +346,30 @@ fn show_help(command: Option<String>) {
             println!("Examples:");
             println!("  kick post -d '{{\"name\": \"John\"}}' https://api.example.com/users");
             println!("  kick post -H \"Content-Type: application/json\" -d '{{\"data\": \"value\"}}' https://api.example.com/create");
+            println!("  kick post --raw --content-type application/xml -d @order.xml https://api.example.com/orders");
+        }
+        Some("request") => {
+            println!("KICK REQUEST Command Help\n");
+            println!("Make a request with any method\n");
+            println!("Usage: kick request [OPTIONS] <METHOD> <URL>\n");
+            println!("Arguments:");
+            println!("  <METHOD>  HTTP method, e.g. OPTIONS or PURGE");
+            println!("  <URL>     URL to request\n");
+            println!("Options:");
+            println!("  -d, --data <DATA>         Body sent verbatim; @FILE reads it from a file");
+            println!("      --content-type <TYPE> Content type of the body (default: sniffed)");
+            println!("  -H, --header <HEADER>     Custom headers (format: \"Key:Value\")");
+            println!("  -A, --user-agent <AGENT>  User agent string");
+            println!("  -p, --pretty              Pretty print JSON responses");
+            println!("  -v, --verbose             Verbose output with plugin logging\n");
+            println!("Without --content-type, data that parses as JSON is sent as");
+            println!("application/json and anything else as text/plain.\n");
+            println!("Examples:");
+            println!("  kick request PURGE https://cdn.example.com/assets/app.js");
+            println!(
+                "  kick request PUT -d '{{\"enabled\": true}}' https://api.example.com/flags/beta"
+            );
+            println!("  kick request POST --content-type application/xml -d @order.xml https://api.example.com/orders");
         }
         Some("put") => {
             println!("KICK PUT Command Help\n");
@@ -233,12 +398,56 @@ fn show_help(command: Option<String>) {
             println!("Options:");
             println!("  -o, --output <FILE>       Output filename");
             println!("  -l, --local               Download to ./.downloads/ directory");
+            println!("  -r, --range <RANGE>       Download only a byte range (e.g. 0-1023)");
+            println!("      --max-size <BYTES>    Abort downloads larger than this");
             println!("  -H, --header <HEADER>     Custom headers");
             println!("  -A, --user-agent <AGENT>  User agent string");
             println!("  -v, --verbose             Verbose output\n");
             println!("Examples:");
             println!("  kick download -o file.zip https://example.com/file.zip");
             println!("  kick download -l -o local-file.txt https://example.com/data.txt");
+            println!("  kick download -r 0-1023 -o head.bin https://example.com/large.bin");
+            println!(
+                "  kick download --max-size 10485760 -o file.zip https://example.com/file.zip"
+            );
+        }
+        Some("doctor") => {
+            println!("KICK DOCTOR Command Help\n");
+            println!("Show client capabilities and check connectivity\n");
+            println!("Usage: kick doctor [OPTIONS] [URL]\n");
+            println!("Arguments:");
+            println!(
+                "  [URL]  URL used for the connectivity check [default: https://httpbin.org/get]\n"
+            );
+            println!("Options:");
+            println!("  -v, --verbose  Verbose output with plugin logging");
+        }
+        Some("check-url") => {
+            println!("KICK CHECK-URL Command Help\n");
+            println!("Check a URL against the SSRF policy without requesting it\n");
+            println!("Usage: kick check-url <URL>\n");
+            println!("Prints ALLOW or DENY with the reason and the active policy.");
+            println!("Exits 0 when allowed and 2 when denied.\n");
+            println!("Examples:");
+            println!("  kick check-url https://api.example.com/users");
+            println!("  kick check-url http://169.254.169.254/latest/meta-data");
+        }
+        Some("config") => {
+            println!("KICK CONFIG Command Help\n");
+            println!("Manage the configuration file\n");
+            println!("Usage: kick config init [OPTIONS]\n");
+            println!("Options:");
+            println!("      --example  Write the fully-commented example config");
+            println!("  -f, --force    Overwrite an existing config file\n");
+            println!("Examples:");
+            println!("  kick config init --example");
+        }
+        Some("cache") => {
+            println!("KICK CACHE Command Help\n");
+            println!("Manage responses cached by 'kick get --cache'\n");
+            println!("Usage: kick cache clear\n");
+            println!("Entries live under the storage temp directory and are keyed by");
+            println!("method, URL and request headers.");
         }
         Some("help") => {
             println!("KICK HELP Command Help\n");
@@ -262,12 +471,28 @@ fn show_help(command: Option<String>) {
             println!("  put       Make a PUT request with JSON data");
             println!("  patch     Make a PATCH request with JSON data");
             println!("  delete    Make a DELETE request");
+            println!("  request   Make a request with any method");
             println!("  download  Download file from URL");
+            println!("  doctor    Show client capabilities and check connectivity");
+            println!("  check-url Check a URL against the SSRF policy");
+            println!("  config    Manage the configuration file");
+            println!("  cache     Manage cached responses");
             println!("  help      Show help information [aliases: -h, --help]");
             println!("  version   Show version and license information [aliases: -v, --version]\n");
             println!("Options:");
             println!("  -h, --help     Print help");
-            println!("  -V, --version  Print version\n");
+            println!("  -V, --version  Print version");
+            println!("      --max-time <SECONDS>  Abort the whole command after SECONDS");
+            println!(
+                "      --header-file <PATH>  Read \"Key: Value\" headers from a file (-H wins)\n"
+            );
+            println!("Exit codes:");
+            println!("  0  Success");
+            println!("  1  Other failure (I/O, storage, malformed response)");
+            println!("  2  Invalid arguments or input");
+            println!("  3  Network error or timeout");
+            println!("  4  HTTP 4xx response");
+            println!("  5  HTTP 5xx response\n");
             println!("Use 'kick help <command>' for detailed help on specific commands.");
             println!("\nExample:");
             println!("  kick get https://httpbin.org/get");
@@ -284,21 +509,13 @@ fn show_version() {
     println!("Built with the REBEL philosophy of developer ergonomics\n");
     println!("LICENSE INFORMATION:");
     println!("{}", "-".repeat(50));
-    match std::fs::read_to_string("LICENSE") {
-        Ok(license) => {
-            // Show first few lines of license
-            let lines: Vec<&str> = license.lines().take(10).collect();
-            for line in &lines {
-                println!("{}", line);
-            }
-            if license.lines().count() > 10 {
-                println!("\n... (see LICENSE file for complete terms)");
-            }
-        }
-        Err(_) => {
-            println!("Licensed under the Apache License, Version 2.0");
-            println!("See LICENSE file for complete terms");
-        }
+    // Show first few lines of license
+    let license = load_asset("LICENSE", EMBEDDED_LICENSE);
+    for line in license.lines().take(10) {
+        println!("{}", line);
+    }
+    if license.lines().count() > 10 {
+        println!("\n... (see LICENSE file for complete terms)");
     }
     println!("\nRepository: {}", env!("CARGO_PKG_REPOSITORY"));
 }
@@ -306,19 +523,94 @@ fn show_version() {
 /// Sanitize save filename to prevent path traversal attacks
 fn sanitize_save_filename(filename: &str) -> Result<std::path::PathBuf> {
     PathValidator::safe_current_dir_path(filename)
-        .map_err(|e| ApiError::other(format!("Save path validation failed: {}", e)).into())
+        .map_err(|e| ApiError::validation(format!("Save path validation failed: {}", e)))
+}
+
+/// Process exit code for a failed command, by error category.
+///
+/// 1 = other failure, 2 = usage/validation, 3 = network or timeout,
+/// 4 = HTTP 4xx, 5 = HTTP 5xx. Keep in sync with the README.
+fn exit_code(error: &ApiError) -> i32 {
+    match error.kind() {
+        ErrorKind::Validation => 2,
+        ErrorKind::Network | ErrorKind::Timeout => 3,
+        ErrorKind::ClientStatus => 4,
+        ErrorKind::ServerStatus => 5,
+        ErrorKind::Other => 1,
+    }
+}
+
+/// Category of a status class, printed next to the numeric code
+fn status_group(status: hyper::StatusCode) -> &'static str {
+    match status.as_u16() {
+        100..=199 => "1xx Informational",
+        200..=299 => "2xx Success",
+        300..=399 => "3xx Redirect",
+        400..=499 => "4xx Client Error",
+        _ => "5xx Server Error",
+    }
+}
+
+/// Status with its group, e.g. `404 Not Found (4xx Client Error)`
+fn status_label(status: hyper::StatusCode) -> String {
+    format!("{} ({})", status, status_group(status))
+}
+
+/// Error text for the terminal; HTTP status errors carry their status group
+fn describe_error(error: &ApiError) -> String {
+    match error {
+        ApiError::HttpStatus { status, .. } => format!("HTTP {}", status_label(*status)),
+        error => error.to_string(),
+    }
+}
+
+/// Parse a positive, possibly fractional, number of seconds
+fn parse_seconds(value: &str) -> std::result::Result<Duration, String> {
+    let seconds: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid number of seconds: {}", value))?;
+    if seconds <= 0.0 {
+        return Err("must be greater than zero".to_string());
+    }
+    Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     let cli = Cli::parse();
+    let result = match cli.max_time {
+        Some(limit) => {
+            let started = Instant::now();
+            tokio::time::timeout(limit, run(cli))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(ApiError::Timeout(TimeoutDetail::new(
+                        TimeoutPhase::Total,
+                        started.elapsed(),
+                        limit,
+                    )))
+                })
+        }
+        None => run(cli).await,
+    };
+    if let Err(e) = result {
+        eprintln!("❌ {}", describe_error(&e));
+        std::process::exit(exit_code(&e));
+    }
+}
 
+async fn run(cli: Cli) -> Result<()> {
     // Handle version flag
     if cli.version {
         show_version();
         return Ok(());
     }
 
+    let file_headers = match &cli.header_file {
+        Some(path) => read_header_file(path)?,
+        None => Vec::new(),
+    };
+
     // Handle case where no command is provided
     let command = match cli.command {
         Some(cmd) => cmd,
@@ -328,7 +620,13 @@ async fn main() -> Result<()> {
         }
     };
 
-    let config = Config::default();
+    // `config init` must work even when the current config file is broken,
+    // and `check-url` depends only on the compiled-in policy
+    let config = if matches!(command, Commands::Config { .. } | Commands::CheckUrl { .. }) {
+        Config::default()
+    } else {
+        Config::load()?
+    };
 
     match command {
         Commands::Get {
@@ -337,25 +635,40 @@ async fn main() -> Result<()> {
             user_agent,
             save,
             pretty,
+            include,
+            ndjson,
+            query,
+            cache,
+            no_cache,
             verbose,
         } => {
             // Validate URL for SSRF protection
             let _validated_url = UrlValidator::validate(&url)
-                .map_err(|e| ApiError::other(format!("URL validation failed: {}", e)))?;
+                .map_err(|e| ApiError::validation(format!("URL validation failed: {}", e)))?;
+
+            let storage = StorageManager::new(config.clone());
+            let client = build_client(config, file_headers, headers, user_agent, verbose).await?;
 
-            let client = build_client(config, headers, user_agent, verbose).await?;
+            if ndjson {
+                // Records only on stdout, so the output can be piped
+                return print_ndjson(&client, &url, query.as_deref(), pretty).await;
+            }
 
             println!("🌐 GET {}", url);
 
-            match client.get(&url).await {
-                Ok(response) => {
-                    println!("✅ Success ({} chars)", response.len());
+            let cache = cache.filter(|_| !no_cache).map(|ttl| (&storage, ttl));
+            match cached_get(&client, &url, cache).await {
+                Ok((status, response_headers, response)) => {
+                    println!("✅ {} ({} chars)", status_label(status), response.len());
 
-                    let output = if pretty {
+                    let mut output = if pretty {
                         format_json(&response).unwrap_or(response)
                     } else {
                         response
                     };
+                    if include {
+                        output = format!("{}{}", format_head(status, &response_headers), output);
+                    }
 
                     if let Some(filename) = save {
                         let safe_filename = sanitize_save_filename(&filename)?;
@@ -366,8 +679,8 @@ async fn main() -> Result<()> {
                     }
                 }
                 Err(e) => {
-                    eprintln!("❌ Request failed: {}", e);
-                    std::process::exit(1);
+                    eprintln!("❌ Request failed: {}", describe_error(&e));
+                    std::process::exit(exit_code(&e));
                 }
             }
         }
@@ -375,6 +688,8 @@ async fn main() -> Result<()> {
         Commands::Post {
             url,
             data,
+            raw,
+            content_type,
             headers,
             user_agent,
             save,
@@ -383,16 +698,23 @@ async fn main() -> Result<()> {
         } => {
             // Validate URL for SSRF protection
             let _validated_url = UrlValidator::validate(&url)
-                .map_err(|e| ApiError::other(format!("URL validation failed: {}", e)))?;
+                .map_err(|e| ApiError::validation(format!("URL validation failed: {}", e)))?;
 
-            let client = build_client(config, headers, user_agent, verbose).await?;
+            let client = build_client(config, file_headers, headers, user_agent, verbose).await?;
 
             println!("📤 POST {}", url);
 
-            let json_data: serde_json::Value = serde_json::from_str(&data)
-                .map_err(|e| ApiError::other(format!("Invalid JSON data: {}", e)))?;
+            let result = if raw {
+                client
+                    .post_bytes(&url, raw_data(&data)?, &content_type)
+                    .await
+            } else {
+                let json_data: serde_json::Value = serde_json::from_str(&data)
+                    .map_err(|e| ApiError::validation(format!("Invalid JSON data: {}", e)))?;
+                client.post_json(&url, &json_data).await
+            };
 
-            match client.post_json(&url, &json_data).await {
+            match result {
                 Ok(response) => {
                     println!("✅ Success ({} chars)", response.len());
 
@@ -411,8 +733,8 @@ async fn main() -> Result<()> {
                     }
                 }
                 Err(e) => {
-                    eprintln!("❌ POST failed: {}", e);
-                    std::process::exit(1);
+                    eprintln!("❌ POST failed: {}", describe_error(&e));
+                    std::process::exit(exit_code(&e));
                 }
             }
         }
@@ -421,28 +743,39 @@ async fn main() -> Result<()> {
             url,
             output,
             local,
+            range,
+            max_size,
             headers,
             user_agent,
             verbose,
         } => {
             // Validate URL for SSRF protection
             let _validated_url = UrlValidator::validate(&url)
-                .map_err(|e| ApiError::other(format!("URL validation failed: {}", e)))?;
+                .map_err(|e| ApiError::validation(format!("URL validation failed: {}", e)))?;
 
-            let client = build_client(config, headers, user_agent, verbose).await?;
+            let client = build_client(config, file_headers, headers, user_agent, verbose).await?;
 
             println!("📥 Downloading {}", url);
 
+            let mut options = RequestOptions::new();
+            if let Some(range) = range {
+                println!("📐 Range: bytes={}", range);
+                options = options.range(range);
+            }
+            if let Some(max_size) = max_size {
+                options = options.max_size(max_size);
+            }
+
             match client
-                .download_file_with_options(&url, &output, local)
+                .download_file_with(&url, &output, local, &options)
                 .await
             {
                 Ok(path) => {
                     println!("✅ Downloaded to: {:?}", path);
                 }
                 Err(e) => {
-                    eprintln!("❌ Download failed: {}", e);
-                    std::process::exit(1);
+                    eprintln!("❌ Download failed: {}", describe_error(&e));
+                    std::process::exit(exit_code(&e));
                 }
             }
         }
@@ -458,14 +791,14 @@ async fn main() -> Result<()> {
         } => {
             // Validate URL for SSRF protection
             let _validated_url = UrlValidator::validate(&url)
-                .map_err(|e| ApiError::other(format!("URL validation failed: {}", e)))?;
+                .map_err(|e| ApiError::validation(format!("URL validation failed: {}", e)))?;
 
-            let client = build_client(config, headers, user_agent, verbose).await?;
+            let client = build_client(config, file_headers, headers, user_agent, verbose).await?;
 
             println!("🔄 PUT {}", url);
 
             let json_data: serde_json::Value = serde_json::from_str(&data)
-                .map_err(|e| ApiError::other(format!("Invalid JSON: {}", e)))?;
+                .map_err(|e| ApiError::validation(format!("Invalid JSON: {}", e)))?;
 
             match client.put_json(&url, &json_data).await {
                 Ok(response) => {
@@ -489,8 +822,8 @@ async fn main() -> Result<()> {
                     }
                 }
                 Err(e) => {
-                    eprintln!("❌ PUT failed: {}", e);
-                    std::process::exit(1);
+                    eprintln!("❌ PUT failed: {}", describe_error(&e));
+                    std::process::exit(exit_code(&e));
                 }
             }
         }
@@ -505,9 +838,9 @@ async fn main() -> Result<()> {
         } => {
             // Validate URL for SSRF protection
             let _validated_url = UrlValidator::validate(&url)
-                .map_err(|e| ApiError::other(format!("URL validation failed: {}", e)))?;
+                .map_err(|e| ApiError::validation(format!("URL validation failed: {}", e)))?;
 
-            let client = build_client(config, headers, user_agent, verbose).await?;
+            let client = build_client(config, file_headers, headers, user_agent, verbose).await?;
 
             println!("🗑️ DELETE {}", url);
 
@@ -533,8 +866,60 @@ async fn main() -> Result<()> {
                     }
                 }
                 Err(e) => {
-                    eprintln!("❌ DELETE failed: {}", e);
-                    std::process::exit(1);
+                    eprintln!("❌ DELETE failed: {}", describe_error(&e));
+                    std::process::exit(exit_code(&e));
+                }
+            }
+        }
+
+        Commands::Request {
+            method,
+            url,
+            data,
+            content_type,
+            headers,
+            user_agent,
+            pretty,
+            verbose,
+        } => {
+            // Validate URL for SSRF protection
+            let _validated_url = UrlValidator::validate(&url)
+                .map_err(|e| ApiError::validation(format!("URL validation failed: {}", e)))?;
+            let method = hyper::Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+                .map_err(|_| ApiError::validation(format!("Invalid method: {}", method)))?;
+
+            let client = build_client(config, file_headers, headers, user_agent, verbose).await?;
+
+            println!("📨 {} {}", method, url);
+
+            let result = match data {
+                Some(data) => {
+                    let body = raw_data(&data)?;
+                    let content_type =
+                        content_type.unwrap_or_else(|| sniff_content_type(&body).to_string());
+                    client
+                        .request_bytes(method.clone(), &url, body, &content_type)
+                        .await
+                }
+                None => client
+                    .request(method.clone(), &url, None, &RequestOptions::default())
+                    .await
+                    .map(|content| String::from_utf8_lossy(&content.into_bytes()).into_owned()),
+            };
+
+            match result {
+                Ok(response) => {
+                    println!("✅ Success ({} chars)", response.len());
+                    let output = if pretty {
+                        format_json(&response).unwrap_or(response)
+                    } else {
+                        response
+                    };
+                    println!("{}", output);
+                }
+                Err(e) => {
+                    eprintln!("❌ {} failed: {}", method, describe_error(&e));
+                    std::process::exit(exit_code(&e));
                 }
             }
         }
@@ -550,14 +935,14 @@ async fn main() -> Result<()> {
         } => {
             // Validate URL for SSRF protection
             let _validated_url = UrlValidator::validate(&url)
-                .map_err(|e| ApiError::other(format!("URL validation failed: {}", e)))?;
+                .map_err(|e| ApiError::validation(format!("URL validation failed: {}", e)))?;
 
-            let client = build_client(config, headers, user_agent, verbose).await?;
+            let client = build_client(config, file_headers, headers, user_agent, verbose).await?;
 
             println!("🔧 PATCH {}", url);
 
             let json_data: serde_json::Value = serde_json::from_str(&data)
-                .map_err(|e| ApiError::other(format!("Invalid JSON: {}", e)))?;
+                .map_err(|e| ApiError::validation(format!("Invalid JSON: {}", e)))?;
 
             match client.patch_json(&url, &json_data).await {
                 Ok(response) => {
@@ -581,18 +966,103 @@ async fn main() -> Result<()> {
                     }
                 }
                 Err(e) => {
-                    eprintln!("❌ PATCH failed: {}", e);
-                    std::process::exit(1);
+                    eprintln!("❌ PATCH failed: {}", describe_error(&e));
+                    std::process::exit(exit_code(&e));
+                }
+            }
+        }
+
+        Commands::Doctor { url, verbose } => {
+            UrlValidator::validate(&url)
+                .map_err(|e| ApiError::validation(format!("URL validation failed: {}", e)))?;
+
+            let client = build_client(config, file_headers, Vec::new(), None, verbose).await?;
+            let capabilities = serde_json::to_string_pretty(&client.capabilities())?;
+            println!("🩺 Client capabilities\n{}\n", capabilities);
+
+            println!("🌐 Checking connectivity: GET {}", url);
+            let started = Instant::now();
+            match client.get(&url).await {
+                Ok(_) => println!("✅ Reachable in {}ms", started.elapsed().as_millis()),
+                Err(e) => {
+                    eprintln!("❌ Connectivity check failed: {}", describe_error(&e));
+                    std::process::exit(exit_code(&e));
+                }
+            }
+        }
+
+        Commands::CheckUrl { url } => {
+            let policy = UrlValidator::policy();
+            match UrlValidator::validate(&url) {
+                Ok(_) => println!("ALLOW {} (policy: {})", url, policy),
+                Err(e) => {
+                    let reason = match e {
+                        ApiError::Other(reason) => reason,
+                        e => e.to_string(),
+                    };
+                    println!("DENY {}: {} (policy: {})", url, reason, policy);
+                    std::process::exit(exit_code(&ApiError::validation(reason)));
+                }
+            }
+        }
+
+        Commands::Config {
+            action: ConfigAction::Init { example, force },
+        } => {
+            let path = Config::config_path();
+            if path.exists() && !force {
+                return Err(ApiError::validation(format!(
+                    "Config already exists: {} (use --force to overwrite)",
+                    path.display()
+                )));
+            }
+            if example {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
                 }
+                std::fs::write(&path, Config::example_toml())?;
+            } else {
+                config.save()?;
             }
+            println!("📝 Wrote config to: {}", path.display());
+        }
+
+        Commands::Cache {
+            action: CacheAction::Clear,
+        } => {
+            let removed = StorageManager::new(config).clear_cache().await?;
+            println!("🧹 Removed {} cached response(s)", removed);
         }
     }
 
     Ok(())
 }
 
+/// Headers from a `--header-file`: one "Key: Value" per line, skipping blank lines and `#` comments
+fn read_header_file(path: &std::path::Path) -> Result<Vec<(String, String)>> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        ApiError::validation(format!("Cannot read header file {}: {}", path.display(), e))
+    })?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| {
+            HeaderValidator::parse_and_validate(line).map_err(|e| {
+                ApiError::validation(format!(
+                    "Header validation failed in {} line {}: {}",
+                    path.display(),
+                    index + 1,
+                    e
+                ))
+            })
+        })
+        .collect()
+}
+
 async fn build_client(
     config: Config,
+    file_headers: Vec<(String, String)>,
     headers: Vec<String>,
     user_agent: Option<String>,
     verbose: bool,
@@ -610,9 +1080,22 @@ async fn build_client(
     }
 
     // Parse and validate headers
+    let mut inline = Vec::with_capacity(headers.len());
     for header in headers {
         let (key, value) = HeaderValidator::parse_and_validate(&header)
-            .map_err(|e| ApiError::other(format!("Header validation failed: {}", e)))?;
+            .map_err(|e| ApiError::validation(format!("Header validation failed: {}", e)))?;
+        inline.push((key, value));
+    }
+    // Header file entries only fill in names not given with -H
+    for (key, value) in file_headers {
+        if !inline
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case(&key))
+        {
+            builder = builder.with_header(key, value)?;
+        }
+    }
+    for (key, value) in inline {
         builder = builder.with_header(key, value)?;
     }
 
@@ -623,8 +1106,179 @@ async fn build_client(
     builder.build().await
 }
 
+/// Response stored by `get --cache`
+#[derive(Serialize, Deserialize)]
+struct CachedResponse {
+    status: u16,
+    headers: HashMap<String, String>,
+    body: String,
+}
+
+/// Cache key for a GET of `url`: a digest of the method, URL and every header the client sends
+fn response_cache_key(client: &ApiClient, url: &str) -> String {
+    let mut headers: Vec<_> = client
+        .effective_headers(&hyper::Method::GET)
+        .into_iter()
+        .collect();
+    headers.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(format!("GET {}\n", url));
+    for (name, value) in headers {
+        hasher.update(format!("{}: {}\n", name, value));
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// `get_with_headers`, answered from the response cache when `cache` has a fresh entry.
+///
+/// Only successful responses are cached. An unreadable cache entry counts as a
+/// miss and a failure to store one is reported without failing the request.
+async fn cached_get(
+    client: &ApiClient,
+    url: &str,
+    cache: Option<(&StorageManager, Duration)>,
+) -> Result<(hyper::StatusCode, HashMap<String, String>, String)> {
+    let Some((storage, ttl)) = cache else {
+        return client.get_with_headers(url).await;
+    };
+
+    let key = response_cache_key(client, url);
+    let hit = storage
+        .load_cached(&key, ttl)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|data| serde_json::from_slice::<CachedResponse>(&data).ok());
+    if let Some(cached) = hit {
+        if let Ok(status) = hyper::StatusCode::from_u16(cached.status) {
+            println!("📦 Served from cache");
+            return Ok((status, cached.headers, cached.body));
+        }
+    }
+
+    let (status, headers, body) = client.get_with_headers(url).await?;
+    let entry = CachedResponse {
+        status: status.as_u16(),
+        headers,
+        body,
+    };
+    if let Err(e) = storage
+        .save_cached(&key, &serde_json::to_vec(&entry)?)
+        .await
+    {
+        eprintln!("⚠️  Failed to cache response: {}", e);
+    }
+    Ok((status, entry.headers, entry.body))
+}
+
 fn format_json(text: &str) -> Option<String> {
     serde_json::from_str::<serde_json::Value>(text)
         .ok()
         .and_then(|json| serde_json::to_string_pretty(&json).ok())
 }
+
+/// Stream `url` as NDJSON and print each record, or the value at `query` in it.
+///
+/// Strings are printed without quotes. Malformed lines and records without the
+/// queried value are reported on stderr and skipped.
+async fn print_ndjson(
+    client: &ApiClient,
+    url: &str,
+    query: Option<&str>,
+    pretty: bool,
+) -> Result<()> {
+    if let Some(pointer) = query {
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return Err(ApiError::validation(format!(
+                "Invalid JSON pointer '{}': must be empty or start with '/'",
+                pointer
+            )));
+        }
+    }
+
+    let mut lines = match client.get_lines(url).await {
+        Ok(lines) => lines,
+        Err(e) => {
+            eprintln!("❌ Request failed: {}", describe_error(&e));
+            std::process::exit(exit_code(&e));
+        }
+    };
+
+    let mut line_number = 0;
+    while let Some(line) = lines.next().await {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("❌ Stream failed: {}", describe_error(&e));
+                std::process::exit(exit_code(&e));
+            }
+        };
+        line_number += 1;
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+
+        let record: serde_json::Value = match serde_json::from_slice(&line) {
+            Ok(record) => record,
+            Err(e) => {
+                eprintln!("⚠️  line {}: invalid JSON: {}", line_number, e);
+                continue;
+            }
+        };
+        let value = match query {
+            Some(pointer) => match record.pointer(pointer) {
+                Some(value) => value,
+                None => {
+                    eprintln!("⚠️  line {}: no value at {}", line_number, pointer);
+                    continue;
+                }
+            },
+            None => &record,
+        };
+
+        match value {
+            serde_json::Value::String(text) => println!("{}", text),
+            value if pretty => println!("{}", serde_json::to_string_pretty(value)?),
+            value => println!("{}", value),
+        }
+    }
+    Ok(())
+}
+
+/// Body for `--raw`: the `-d` value itself, or the contents of the file named by `@FILE`
+fn raw_data(data: &str) -> Result<Vec<u8>> {
+    match data.strip_prefix('@') {
+        Some(path) => std::fs::read(path)
+            .map_err(|e| ApiError::validation(format!("Failed to read {}: {}", path, e))),
+        None => Ok(data.as_bytes().to_vec()),
+    }
+}
+
+/// Content type for `request --data` without `--content-type`: JSON when the data parses as JSON
+fn sniff_content_type(data: &[u8]) -> &'static str {
+    if serde_json::from_slice::<serde_json::Value>(data).is_ok() {
+        "application/json"
+    } else {
+        "text/plain"
+    }
+}
+
+/// Status line and headers, sorted by name, as printed by `--include`
+fn format_head(
+    status: hyper::StatusCode,
+    headers: &std::collections::HashMap<String, String>,
+) -> String {
+    let mut names: Vec<&String> = headers.keys().collect();
+    names.sort();
+    let mut head = format!("HTTP/1.1 {}\n", status);
+    for name in names {
+        head.push_str(&format!("{}: {}\n", name, headers[name]));
+    }
+    head.push('\n');
+    head
+}
@@ -1,3 +1,4 @@
+use kick::models::{DogApiResponse, IpifyResponse, Joke};
 use kick::prelude::*;
 use std::sync::Arc;
 
@@ -24,23 +25,27 @@ async fn main() -> Result<()> {
 
     // Test 1: IP Address API
     println!("\n🌐 Test 1: IP Address API");
-    match client.get("https://api.ipify.org/?format=json").await {
+    match client
+        .download_json::<IpifyResponse>("https://api.ipify.org/?format=json")
+        .await
+    {
         Ok(response) => {
-            println!("✅ IP API Response: {}", response);
-            let json: serde_json::Value = serde_json::from_str(&response)?;
-            println!("📍 Your IP: {}", json["ip"]);
+            println!("✅ IP API Response: {:?}", response);
+            println!("📍 Your IP: {}", response.ip);
         }
         Err(e) => println!("❌ IP API failed: {}", e),
     }
 
     // Test 2: Dog Image API
     println!("\n🐕 Test 2: Random Dog Image API");
-    match client.get("https://dog.ceo/api/breeds/image/random").await {
+    match client
+        .download_json::<DogApiResponse>("https://dog.ceo/api/breeds/image/random")
+        .await
+    {
         Ok(response) => {
-            println!("✅ Dog API Response: {}", response);
-            let json: serde_json::Value = serde_json::from_str(&response)?;
-            if json["status"] == "success" {
-                println!("🖼️ Dog Image URL: {}", json["message"]);
+            println!("✅ Dog API Response: {:?}", response);
+            if response.is_success() {
+                println!("🖼️ Dog Image URL: {}", response.message);
             }
         }
         Err(e) => println!("❌ Dog API failed: {}", e),
@@ -49,16 +54,14 @@ async fn main() -> Result<()> {
     // Test 3: Jokes API
     println!("\n😄 Test 3: Random Jokes API");
     match client
-        .get("https://official-joke-api.appspot.com/jokes/ten")
+        .download_json::<Vec<Joke>>("https://official-joke-api.appspot.com/jokes/ten")
         .await
     {
-        Ok(response) => {
-            println!("✅ Jokes API Response received ({} chars)", response.len());
-            let jokes: Vec<serde_json::Value> = serde_json::from_str(&response)?;
+        Ok(jokes) => {
             println!("📝 Retrieved {} jokes", jokes.len());
             if let Some(first_joke) = jokes.first() {
-                println!("🎭 First joke - Setup: {}", first_joke["setup"]);
-                println!("🎪 Punchline: {}", first_joke["punchline"]);
+                println!("🎭 First joke - Setup: {}", first_joke.setup);
+                println!("🎪 Punchline: {}", first_joke.punchline);
             }
         }
         Err(e) => println!("❌ Jokes API failed: {}", e),
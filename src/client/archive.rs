@@ -0,0 +1,136 @@
+//! Streaming archive extraction for `download_and_extract` (`archive` feature)
+//!
+//! The response body is handed chunk by chunk to a blocking task that runs the
+//! decompressor and archive reader, so nothing is spooled to disk or memory.
+//! Zip archives are read entry by entry from their local headers.
+
+use crate::error::{ApiError, Result};
+use crate::sec::PathValidator;
+use bytes::{Buf, Bytes};
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+
+/// Layout of a downloaded archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// Uncompressed `.tar`
+    Tar,
+    /// Gzip-compressed tar (`.tar.gz`, `.tgz`)
+    TarGz,
+    /// `.zip`, stored or deflated entries
+    Zip,
+}
+
+/// Blocking reader over body chunks sent from the async side
+pub(crate) struct ChunkReader {
+    chunks: mpsc::Receiver<Bytes>,
+    current: Bytes,
+}
+
+impl ChunkReader {
+    pub(crate) fn new(chunks: mpsc::Receiver<Bytes>) -> Self {
+        Self {
+            chunks,
+            current: Bytes::new(),
+        }
+    }
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.current.is_empty() {
+            match self.chunks.blocking_recv() {
+                Some(chunk) => self.current = chunk,
+                None => return Ok(0),
+            }
+        }
+        let len = buf.len().min(self.current.len());
+        buf[..len].copy_from_slice(&self.current[..len]);
+        self.current.advance(len);
+        Ok(len)
+    }
+}
+
+/// Extract every entry read from `reader` under `dest_dir`, returning the files written.
+///
+/// Entry names must pass `PathValidator::sanitize_filename`; the first one that
+/// does not aborts extraction. Links and special files are skipped.
+pub(crate) fn extract(
+    reader: impl Read,
+    format: ArchiveFormat,
+    dest_dir: &Path,
+) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dest_dir)?;
+    match format {
+        ArchiveFormat::Tar => extract_tar(reader, dest_dir),
+        ArchiveFormat::TarGz => extract_tar(GzDecoder::new(reader), dest_dir),
+        ArchiveFormat::Zip => extract_zip(reader, dest_dir),
+    }
+}
+
+fn extract_tar(reader: impl Read, dest_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut written = Vec::new();
+    for entry in archive.entries().map_err(archive_error)? {
+        let mut entry = entry.map_err(archive_error)?;
+        let name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+        let path = entry_path(dest_dir, &name)?;
+        let kind = entry.header().entry_type();
+        if kind.is_dir() {
+            fs::create_dir_all(&path)?;
+        } else if kind.is_file() {
+            write_entry(&mut entry, &path)?;
+            written.push(path);
+        } else {
+            tracing::warn!("Skipping archive entry {} of type {:?}", name, kind);
+        }
+    }
+    Ok(written)
+}
+
+fn extract_zip(mut reader: impl Read, dest_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    while let Some(mut file) =
+        zip::read::read_zipfile_from_stream(&mut reader).map_err(archive_error)?
+    {
+        let name = file.name().to_string();
+        let path = entry_path(dest_dir, &name)?;
+        if file.is_dir() {
+            fs::create_dir_all(&path)?;
+        } else if file.is_file() {
+            write_entry(&mut file, &path)?;
+            written.push(path);
+        } else {
+            tracing::warn!("Skipping archive entry {}", name);
+        }
+    }
+    Ok(written)
+}
+
+/// Where entry `name` goes under `dest_dir`, rejecting names that could escape it
+fn entry_path(dest_dir: &Path, name: &str) -> Result<PathBuf> {
+    let name = PathValidator::sanitize_filename(name).map_err(|e| {
+        let reason = match e {
+            ApiError::Other(reason) => reason,
+            e => e.to_string(),
+        };
+        ApiError::validation(format!("Rejected archive entry {:?}: {}", name, reason))
+    })?;
+    Ok(dest_dir.join(name))
+}
+
+fn write_entry(entry: &mut impl Read, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::File::create(path)?;
+    io::copy(entry, &mut file)?;
+    Ok(())
+}
+
+fn archive_error(e: impl std::fmt::Display) -> ApiError {
+    ApiError::other(format!("Failed to read archive: {}", e))
+}
@@ -0,0 +1,77 @@
+//! Per-item outcomes of a batch of requests
+
+use crate::error::{ApiError, Result};
+
+/// Outcome of each item of a batch, keyed by the URL (or other key) it was run for,
+/// in the order the items were given
+#[derive(Debug)]
+pub struct BatchResult<T> {
+    items: Vec<(String, Result<T>)>,
+}
+
+impl<T> BatchResult<T> {
+    pub(crate) fn new(items: Vec<(String, Result<T>)>) -> Self {
+        Self { items }
+    }
+
+    /// Number of items in the batch
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Whether every item succeeded (true for an empty batch)
+    pub fn all_succeeded(&self) -> bool {
+        self.items.iter().all(|(_, result)| result.is_ok())
+    }
+
+    /// Items that succeeded, with their keys
+    pub fn successes(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.items
+            .iter()
+            .filter_map(|(key, result)| result.as_ref().ok().map(|value| (key.as_str(), value)))
+    }
+
+    /// Items that failed, with their keys
+    pub fn failures(&self) -> impl Iterator<Item = (&str, &ApiError)> {
+        self.items
+            .iter()
+            .filter_map(|(key, result)| result.as_ref().err().map(|error| (key.as_str(), error)))
+    }
+
+    /// Outcome for `key`, or `None` if it wasn't part of the batch
+    pub fn get(&self, key: &str) -> Option<&Result<T>> {
+        self.items
+            .iter()
+            .find(|(item_key, _)| item_key == key)
+            .map(|(_, result)| result)
+    }
+
+    /// Every item in batch order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Result<T>)> {
+        self.items
+            .iter()
+            .map(|(key, result)| (key.as_str(), result))
+    }
+}
+
+impl<T> IntoIterator for BatchResult<T> {
+    type Item = (String, Result<T>);
+    type IntoIter = std::vec::IntoIter<(String, Result<T>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BatchResult<T> {
+    type Item = &'a (String, Result<T>);
+    type IntoIter = std::slice::Iter<'a, (String, Result<T>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
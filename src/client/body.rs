@@ -0,0 +1,237 @@
+//! Streaming request bodies
+
+use bytes::{Bytes, BytesMut};
+use hyper::body::{Body, Frame, SizeHint};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::mpsc;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Bytes read from the reader per body frame
+const READ_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Request body that streams from an `AsyncRead`.
+///
+/// With a known length the size hint is exact, so hyper sends `Content-Length`;
+/// otherwise the body goes out with chunked transfer encoding. Read errors end
+/// the body with an error, which aborts the request.
+pub(crate) struct ReaderBody<R> {
+    reader: Pin<Box<R>>,
+    buf: Box<[u8]>,
+    length: Option<u64>,
+    done: bool,
+}
+
+impl<R> ReaderBody<R> {
+    pub(crate) fn new(reader: R, length: Option<u64>) -> Self {
+        Self {
+            reader: Box::pin(reader),
+            buf: vec![0; READ_CHUNK_SIZE].into_boxed_slice(),
+            length,
+            done: false,
+        }
+    }
+}
+
+impl<R: AsyncRead> Body for ReaderBody<R> {
+    type Data = Bytes;
+    type Error = std::io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Self::Error>>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        let mut read_buf = ReadBuf::new(&mut this.buf);
+        match this.reader.as_mut().poll_read(cx, &mut read_buf) {
+            Poll::Ready(Ok(())) if read_buf.filled().is_empty() => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Ready(Ok(())) => {
+                let chunk = Bytes::copy_from_slice(read_buf.filled());
+                Poll::Ready(Some(Ok(Frame::data(chunk))))
+            }
+            Poll::Ready(Err(e)) => {
+                this.done = true;
+                Poll::Ready(Some(Err(e)))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match self.length {
+            Some(length) => SizeHint::with_exact(length),
+            None => SizeHint::default(),
+        }
+    }
+}
+
+/// Item accepted by `ApiClient::post_channel`.
+///
+/// Plain `Bytes` chunks can only end the body by closing the channel. Send
+/// `Result<Bytes, E>` items instead to be able to abort: an `Err` fails the
+/// request with that error rather than sending a truncated body.
+pub trait BodyChunk: Send + 'static {
+    fn into_chunk(self) -> Result<Bytes, BoxError>;
+}
+
+impl BodyChunk for Bytes {
+    fn into_chunk(self) -> Result<Bytes, BoxError> {
+        Ok(self)
+    }
+}
+
+impl<E> BodyChunk for Result<Bytes, E>
+where
+    E: Into<BoxError> + Send + 'static,
+{
+    fn into_chunk(self) -> Result<Bytes, BoxError> {
+        self.map_err(Into::into)
+    }
+}
+
+/// Request body fed by a channel, sent chunked; it ends when every sender is dropped
+pub(crate) struct ChannelBody<T> {
+    rx: mpsc::Receiver<T>,
+    done: bool,
+}
+
+impl<T> ChannelBody<T> {
+    pub(crate) fn new(rx: mpsc::Receiver<T>) -> Self {
+        Self { rx, done: false }
+    }
+}
+
+impl<T: BodyChunk> Body for ChannelBody<T> {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Self::Error>>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        match this.rx.poll_recv(cx) {
+            Poll::Ready(Some(item)) => match item.into_chunk() {
+                Ok(chunk) => Poll::Ready(Some(Ok(Frame::data(chunk)))),
+                Err(e) => {
+                    this.done = true;
+                    Poll::Ready(Some(Err(
+                        format!("Request body channel aborted: {}", e).into()
+                    )))
+                }
+            },
+            Poll::Ready(None) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done
+    }
+}
+
+/// What a `RecordedBody` sent so far
+#[derive(Default)]
+struct Recording {
+    data: BytesMut,
+    complete: bool,
+    /// The body outgrew the cap and the copy was dropped
+    overflowed: bool,
+}
+
+/// Request body that keeps a copy of what it sends, up to a cap, so a
+/// redirect can repeat the request with the same body
+pub(crate) struct RecordedBody<B> {
+    inner: B,
+    recording: Arc<Mutex<Recording>>,
+    limit: usize,
+}
+
+/// Handle to the copy kept by a `RecordedBody`
+pub(crate) struct BodyRecording(Arc<Mutex<Recording>>);
+
+impl<B> RecordedBody<B> {
+    /// Record at most `limit` bytes; larger bodies are sent but not kept
+    pub(crate) fn new(inner: B, limit: usize) -> (Self, BodyRecording) {
+        let recording = Arc::new(Mutex::new(Recording::default()));
+        let body = Self {
+            inner,
+            recording: recording.clone(),
+            limit,
+        };
+        (body, BodyRecording(recording))
+    }
+}
+
+impl BodyRecording {
+    /// The whole body, or `None` if it was not sent to the end or was over the cap
+    pub(crate) fn into_bytes(self) -> Option<Bytes> {
+        let mut recording = self.0.lock().unwrap();
+        (recording.complete && !recording.overflowed)
+            .then(|| std::mem::take(&mut recording.data).freeze())
+    }
+}
+
+impl<B> Body for RecordedBody<B>
+where
+    B: Body<Data = Bytes> + Unpin,
+{
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Self::Error>>> {
+        let this = self.get_mut();
+        let frame = ready!(Pin::new(&mut this.inner).poll_frame(cx));
+        let mut recording = this.recording.lock().unwrap();
+        match &frame {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    if recording.data.len() + data.len() > this.limit {
+                        recording.overflowed = true;
+                        recording.data = BytesMut::new();
+                    }
+                    if !recording.overflowed {
+                        recording.data.extend_from_slice(data);
+                    }
+                }
+                // hyper stops polling once the body says it has ended
+                recording.complete = this.inner.is_end_stream();
+            }
+            Some(Err(_)) => {}
+            None => recording.complete = true,
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
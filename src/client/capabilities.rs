@@ -0,0 +1,35 @@
+//! Diagnostic snapshot of what a built client supports
+
+use serde::Serialize;
+
+/// Features and settings in effect for an `ApiClient`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Capabilities {
+    /// Compiled-in TLS backend (`native-tls` or `rustls`)
+    pub tls_backend: String,
+    /// HTTP versions the connector negotiates
+    pub http_versions: Vec<String>,
+    /// Response content encodings decoded transparently
+    pub compression: Vec<String>,
+    /// Whether 3xx responses are followed automatically
+    pub follows_redirects: bool,
+    /// Whether cookies are stored and replayed between requests
+    pub cookies: bool,
+    pub timeout_ms: u64,
+    pub max_retries: usize,
+    pub retry_delay_ms: u64,
+    pub max_concurrent_requests: usize,
+    pub max_request_size: u64,
+    pub max_response_size: Option<u64>,
+    pub keepalive_interval_secs: Option<u64>,
+    /// Capacity of the `download_json` cache, when enabled
+    pub json_cache_capacity: Option<usize>,
+    /// `Host` header override, when set
+    pub host_header: Option<String>,
+    /// Whether the config file is watched for live changes
+    pub config_reload: bool,
+    /// Body formats available to the typed methods (`get_typed`, `post_typed`, ...)
+    pub content_types: Vec<String>,
+    /// Names of registered plugins, in execution order
+    pub plugins: Vec<String>,
+}
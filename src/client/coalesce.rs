@@ -0,0 +1,98 @@
+//! Coalescing of concurrent downloads to the same destination
+//!
+//! The first caller for a destination path becomes the leader and performs the
+//! download; later callers wait for its outcome instead of writing the same file.
+
+use crate::error::{ApiError, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tokio::sync::watch;
+
+/// Outcome shared with followers (`ApiError` is not `Clone`, so errors travel as text)
+type Outcome = Option<std::result::Result<PathBuf, String>>;
+
+struct InFlight {
+    url: String,
+    outcome: watch::Receiver<Outcome>,
+}
+
+/// Downloads currently being written, keyed by canonical destination path
+#[derive(Default)]
+pub(crate) struct InFlightDownloads {
+    inner: Mutex<HashMap<PathBuf, InFlight>>,
+}
+
+pub(crate) enum Claim<'a> {
+    /// No download in progress: perform it and report through the guard
+    Leader(LeaderGuard<'a>),
+    /// Another download to this path is running
+    Follower(Follower),
+}
+
+impl InFlightDownloads {
+    pub(crate) fn claim(&self, key: PathBuf, url: &str) -> Claim<'_> {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(in_flight) = inner.get(&key) {
+            return Claim::Follower(Follower {
+                same_url: in_flight.url == url,
+                outcome: in_flight.outcome.clone(),
+            });
+        }
+
+        let (tx, rx) = watch::channel(None);
+        inner.insert(
+            key.clone(),
+            InFlight {
+                url: url.to_string(),
+                outcome: rx,
+            },
+        );
+        Claim::Leader(LeaderGuard {
+            registry: self,
+            key,
+            tx,
+        })
+    }
+}
+
+/// Held by the caller performing the download; releases the path when dropped
+pub(crate) struct LeaderGuard<'a> {
+    registry: &'a InFlightDownloads,
+    key: PathBuf,
+    tx: watch::Sender<Outcome>,
+}
+
+impl LeaderGuard<'_> {
+    /// Publish the result to followers
+    pub(crate) fn finish(self, result: &Result<PathBuf>) {
+        let outcome = match result {
+            Ok(path) => Ok(path.clone()),
+            Err(e) => Err(e.to_string()),
+        };
+        self.tx.send_replace(Some(outcome));
+    }
+}
+
+impl Drop for LeaderGuard<'_> {
+    fn drop(&mut self) {
+        self.registry.inner.lock().unwrap().remove(&self.key);
+    }
+}
+
+pub(crate) struct Follower {
+    same_url: bool,
+    outcome: watch::Receiver<Outcome>,
+}
+
+impl Follower {
+    /// Wait for the leader. Returns its result when it fetched the same URL, or
+    /// `None` once the path is free again (different URL, or leader cancelled).
+    pub(crate) async fn wait(mut self) -> Option<Result<PathBuf>> {
+        let outcome = self.outcome.wait_for(Option::is_some).await.ok()?.clone()?;
+        if !self.same_url {
+            return None;
+        }
+        Some(outcome.map_err(|e| ApiError::other(format!("Coalesced download failed: {}", e))))
+    }
+}
@@ -0,0 +1,22 @@
+//! Whole-body transform applied at the client boundary
+//!
+//! A `BodyCodec` set with `ApiClientBuilder::with_body_codec` sees every
+//! request body just before it is sent and every response body read in full
+//! just after it arrives, e.g. to encrypt and decrypt payloads. Plugins and
+//! callers only ever see the decoded form.
+
+use crate::error::Result;
+use bytes::Bytes;
+
+/// Encodes outgoing and decodes incoming bodies.
+///
+/// Empty bodies pass through untouched in both directions. Streaming request bodies
+/// are read in full (up to `max_request_size`) so they can be encoded. Responses
+/// streamed to files or readers are not decoded.
+pub trait BodyCodec: Send + Sync {
+    /// Transform a request body before it is sent
+    fn encode(&self, body: Bytes) -> Result<Bytes>;
+
+    /// Transform a response body after it was received
+    fn decode(&self, body: Bytes) -> Result<Bytes>;
+}
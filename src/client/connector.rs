@@ -0,0 +1,335 @@
+//! HTTPS connector selection
+//!
+//! The TLS backend is picked at compile time so the public API stays identical:
+//! - `native-tls` (default): hyper-tls backed by the platform TLS library
+//! - `rustls`: hyper-rustls with the bundled webpki root store, for static/musl builds
+//!
+//! Exactly one backend must be enabled; enabling both is a compile error, so
+//! `rustls` builds need `default-features = false`.
+//! Custom trust roots and client certificates (`ClientConfig.tls`) need the rustls backend.
+
+use super::encoding::BodyError;
+use super::events::EventBus;
+use super::instrument::{Instrumented, TimedTcp};
+use super::proxy::ProxyConnector;
+use super::resolver::{ConnectorResolver, PinnedConnector, ResolvePins, Resolver, SystemResolver};
+use crate::config::TlsConfig;
+use crate::error::{BuildError, Result};
+use bytes::Bytes;
+use http_body_util::combinators::BoxBody;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::client::proxy::matcher::Matcher;
+use hyper_util::rt::TokioExecutor;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+compile_error!("kick requires a TLS backend: enable either the `native-tls` or `rustls` feature");
+
+#[cfg(all(feature = "native-tls", feature = "rustls"))]
+compile_error!(
+    "the `native-tls` and `rustls` features conflict: enable only one TLS backend \
+     (use `default-features = false` with `rustls`)"
+);
+
+/// Request body type shared by every client in the crate
+pub(crate) type RequestBody = BoxBody<Bytes, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Response body after the client's accounting and decoding wrappers
+pub(crate) type ResponseBody = BoxBody<Bytes, BodyError>;
+
+/// TCP connector resolving names through the configured `Resolver`, via the
+/// matching proxy if any
+pub(crate) type TcpConnector =
+    ProxyConnector<TimedTcp<PinnedConnector<HttpConnector<ConnectorResolver>>>>;
+
+/// HTTPS connector for the active TLS backend, reporting connection timing
+#[cfg(feature = "rustls")]
+pub(crate) type HttpsConnector = Instrumented<hyper_rustls::HttpsConnector<TcpConnector>>;
+
+/// HTTPS connector for the active TLS backend, reporting connection timing
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+pub(crate) type HttpsConnector = Instrumented<hyper_tls::HttpsConnector<TcpConnector>>;
+
+/// Transport settings shared by both TLS backends
+#[derive(Clone)]
+pub(crate) struct ConnectOptions {
+    /// TCP keepalive probe interval for idle connections
+    pub(crate) keepalive: Option<Duration>,
+    /// Name resolution for hostnames in URLs
+    pub(crate) resolver: Arc<dyn Resolver>,
+    /// Receives `ClientEvent::Connection` timings
+    pub(crate) events: Arc<EventBus>,
+    /// Proxy routing rules (`None` connects directly)
+    pub(crate) proxy: Option<Arc<Matcher>>,
+    /// `host:port` pairs dialed at a fixed address instead of resolving
+    pub(crate) pins: Arc<ResolvePins>,
+}
+
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        Self {
+            keepalive: None,
+            resolver: Arc::new(SystemResolver),
+            events: Arc::default(),
+            proxy: None,
+            pins: Arc::default(),
+        }
+    }
+}
+
+/// Pooled hyper client over the active HTTPS connector
+pub(crate) type HttpClient = Client<HttpsConnector, RequestBody>;
+
+/// Name of the compiled-in TLS backend
+pub fn tls_backend() -> &'static str {
+    if cfg!(feature = "rustls") {
+        "rustls"
+    } else {
+        "native-tls"
+    }
+}
+
+/// Build an HTTPS connector for the active TLS backend
+#[cfg(feature = "rustls")]
+pub(crate) fn https_connector(tls: &TlsConfig, options: &ConnectOptions) -> Result<HttpsConnector> {
+    let mut roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    if let Some(path) = &tls.ca_bundle {
+        for cert in load_certs(path, "CA bundle")? {
+            roots
+                .add(cert)
+                .map_err(|e| BuildError::InvalidCertificate {
+                    what: "CA bundle",
+                    path: path.clone(),
+                    reason: e.to_string(),
+                })?;
+        }
+    }
+
+    let builder = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| BuildError::Tls(format!("Unsupported TLS protocol versions: {}", e)))?
+    .with_root_certificates(roots);
+
+    let tls_config = match (&tls.client_cert, &tls.client_key) {
+        (Some(cert_path), Some(key_path)) => {
+            let chain = load_certs(cert_path, "client certificate")?;
+            let key = load_private_key(key_path)?;
+            builder.with_client_auth_cert(chain, key).map_err(|e| {
+                BuildError::InvalidCertificate {
+                    what: "client certificate",
+                    path: cert_path.clone(),
+                    reason: e.to_string(),
+                }
+            })?
+        }
+        (None, None) => builder.with_no_client_auth(),
+        _ => return Err(BuildError::IncompleteClientIdentity.into()),
+    };
+
+    let https = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_tls_config(tls_config)
+        .https_or_http()
+        .enable_http1()
+        .wrap_connector(tcp_connector(options));
+    Ok(Instrumented::new(https, options.events.clone()))
+}
+
+/// Build an HTTPS connector for the active TLS backend
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+pub(crate) fn https_connector(tls: &TlsConfig, options: &ConnectOptions) -> Result<HttpsConnector> {
+    if tls.is_custom() {
+        let setting = if tls.ca_bundle.is_some() {
+            "TLS ca_bundle"
+        } else {
+            "TLS client_cert/client_key"
+        };
+        return Err(BuildError::UnsupportedTlsBackend {
+            setting,
+            backend: tls_backend(),
+        }
+        .into());
+    }
+    let tls_connector =
+        hyper_tls::native_tls::TlsConnector::new().map_err(|e| BuildError::Tls(e.to_string()))?;
+    let https = hyper_tls::HttpsConnector::from((tcp_connector(options), tls_connector.into()));
+    Ok(Instrumented::new(https, options.events.clone()))
+}
+
+/// Plain TCP connector using the configured resolver, pins, keepalive and proxy
+fn tcp_connector(options: &ConnectOptions) -> TcpConnector {
+    let mut http =
+        HttpConnector::new_with_resolver(ConnectorResolver::new(options.resolver.clone()));
+    http.enforce_http(false);
+    http.set_keepalive(options.keepalive);
+    let pinned = PinnedConnector::new(http, options.pins.clone());
+    ProxyConnector::new(TimedTcp::new(pinned), options.proxy.clone())
+}
+
+/// Read a PEM file, reporting missing or unreadable files as build errors
+#[cfg(feature = "rustls")]
+fn read_pem(path: &std::path::Path, what: &'static str) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|e| {
+        BuildError::MissingCertFile {
+            what,
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Load every certificate from a PEM file
+#[cfg(feature = "rustls")]
+fn load_certs(
+    path: &std::path::Path,
+    what: &'static str,
+) -> Result<Vec<rustls::pki_types::CertificateDer<'static>>> {
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::CertificateDer;
+
+    let pem = read_pem(path, what)?;
+    let certs = CertificateDer::pem_slice_iter(&pem)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| BuildError::InvalidCertificate {
+            what,
+            path: path.to_path_buf(),
+            reason: format!("invalid PEM: {}", e),
+        })?;
+
+    if certs.is_empty() {
+        return Err(BuildError::InvalidCertificate {
+            what,
+            path: path.to_path_buf(),
+            reason: "no certificates found".to_string(),
+        }
+        .into());
+    }
+    Ok(certs)
+}
+
+/// Load the first private key from a PEM file
+#[cfg(feature = "rustls")]
+fn load_private_key(path: &std::path::Path) -> Result<rustls::pki_types::PrivateKeyDer<'static>> {
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::PrivateKeyDer;
+
+    let pem = read_pem(path, "client key")?;
+    PrivateKeyDer::from_pem_slice(&pem).map_err(|e| {
+        BuildError::InvalidCertificate {
+            what: "client key",
+            path: path.to_path_buf(),
+            reason: format!("no usable private key: {}", e),
+        }
+        .into()
+    })
+}
+
+/// Build a fresh connection pool over an existing connector
+pub(crate) fn pooled_client(connector: HttpsConnector) -> HttpClient {
+    Client::builder(TokioExecutor::new()).build(connector)
+}
+
+/// Build a pooled hyper client over the active HTTPS connector
+pub(crate) fn build_http_client(tls: &TlsConfig) -> Result<HttpClient> {
+    Ok(pooled_client(https_connector(
+        tls,
+        &ConnectOptions::default(),
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+
+    #[test]
+    fn test_tls_backend_matches_features() {
+        if cfg!(feature = "rustls") {
+            assert_eq!(tls_backend(), "rustls");
+        } else {
+            assert_eq!(tls_backend(), "native-tls");
+        }
+    }
+
+    #[test]
+    fn test_default_tls_config_builds() {
+        assert!(https_connector(&TlsConfig::default(), &ConnectOptions::default()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_keepalive_is_set_on_new_sockets() {
+        use tower_service::Service;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri: hyper::Uri = format!("http://{}/", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        for keepalive in [Some(Duration::from_secs(7)), None] {
+            let options = ConnectOptions {
+                keepalive,
+                ..ConnectOptions::default()
+            };
+            let stream = tcp_connector(&options).call(uri.clone()).await.unwrap();
+            let socket = socket2::SockRef::from(stream.get_ref().inner());
+            assert_eq!(socket.keepalive().unwrap(), keepalive.is_some());
+            if let Some(idle) = keepalive {
+                assert_eq!(socket.tcp_keepalive_time().unwrap(), idle);
+            }
+        }
+    }
+
+    #[test]
+    fn test_missing_ca_bundle_is_build_error() {
+        let tls = TlsConfig {
+            ca_bundle: Some("/nonexistent/kick-ca.pem".into()),
+            ..TlsConfig::default()
+        };
+        let result = https_connector(&tls, &ConnectOptions::default());
+        if cfg!(feature = "rustls") {
+            assert!(matches!(
+                result,
+                Err(ApiError::Build(BuildError::MissingCertFile { .. }))
+            ));
+        } else {
+            assert!(matches!(
+                result,
+                Err(ApiError::Build(BuildError::UnsupportedTlsBackend { .. }))
+            ));
+        }
+    }
+
+    #[cfg(feature = "rustls")]
+    #[test]
+    fn test_invalid_ca_bundle_is_build_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("ca.pem");
+        std::fs::write(&path, "not a certificate").unwrap();
+        let tls = TlsConfig {
+            ca_bundle: Some(path),
+            ..TlsConfig::default()
+        };
+        assert!(matches!(
+            https_connector(&tls, &ConnectOptions::default()),
+            Err(ApiError::Build(BuildError::InvalidCertificate { .. }))
+        ));
+    }
+
+    #[cfg(feature = "rustls")]
+    #[test]
+    fn test_client_cert_without_key_is_build_error() {
+        let tls = TlsConfig {
+            client_cert: Some("cert.pem".into()),
+            ..TlsConfig::default()
+        };
+        assert!(matches!(
+            https_connector(&tls, &ConnectOptions::default()),
+            Err(ApiError::Build(BuildError::IncompleteClientIdentity))
+        ));
+    }
+}
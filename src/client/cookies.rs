@@ -0,0 +1,407 @@
+//! Cookie storage for `ApiClientBuilder::with_cookie_jar`
+//!
+//! Follows the RFC 6265 matching rules: host-only cookies go back to the exact
+//! host, `Domain` cookies to the domain and its subdomains, and `Path` limits
+//! which request paths see a cookie. `Secure` cookies are only sent over https.
+
+use hyper::header::{HeaderMap, SET_COOKIE};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use url::Url;
+
+struct Cookie {
+    name: String,
+    value: String,
+    /// Lowercased domain without a leading dot
+    domain: String,
+    /// Whether only `domain` itself matches, not its subdomains
+    host_only: bool,
+    path: String,
+    secure: bool,
+    expires: Option<SystemTime>,
+}
+
+impl Cookie {
+    /// Parse one `Set-Cookie` value received from `url`.
+    ///
+    /// `None` for malformed values and for a `Domain` the host may not set.
+    fn parse(header: &str, url: &Url, now: SystemTime) -> Option<Self> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        let mut parts = header.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(url),
+            secure: false,
+            expires: None,
+        };
+        let mut max_age = None;
+        for attribute in parts {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                    if !domain_matches(&host, &domain) {
+                        return None;
+                    }
+                    // A public suffix may only name the host itself, as a host-only cookie
+                    if is_public_suffix(&domain) {
+                        if domain != host {
+                            return None;
+                        }
+                        continue;
+                    }
+                    cookie.domain = domain;
+                    cookie.host_only = false;
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "secure" => cookie.secure = true,
+                "expires" => {
+                    if let Some(expires) = parse_expires(value) {
+                        cookie.expires = Some(expires);
+                    }
+                }
+                "max-age" => {
+                    if let Ok(seconds) = value.parse::<i64>() {
+                        max_age = Some(seconds);
+                    }
+                }
+                _ => {}
+            }
+        }
+        // Max-Age wins over Expires; zero or less expires the cookie now
+        if let Some(seconds) = max_age {
+            cookie.expires = Some(match u64::try_from(seconds) {
+                Ok(seconds) if seconds > 0 => now + Duration::from_secs(seconds),
+                _ => SystemTime::UNIX_EPOCH,
+            });
+        }
+        Some(cookie)
+    }
+
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn matches(&self, url: &Url, host: &str) -> bool {
+        let domain_ok = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(host, &self.domain)
+        };
+        domain_ok
+            && path_matches(url.path(), &self.path)
+            && (!self.secure || url.scheme() == "https")
+    }
+
+    fn same_slot(&self, other: &Cookie) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || (host.ends_with(domain)
+            && host.as_bytes()[host.len() - domain.len() - 1] == b'.'
+            && host.parse::<std::net::IpAddr>().is_err())
+}
+
+/// Whether `domain` is a single label or a public suffix (`com`, `co.uk`,
+/// `github.io`) that no single site controls
+fn is_public_suffix(domain: &str) -> bool {
+    !domain.contains('.') || psl::suffix_str(domain) == Some(domain)
+}
+
+/// Whether a cookie scoped to `cookie_path` applies to `request_path`
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+/// Directory of the request path, used when `Set-Cookie` has no `Path`
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(end) => url.path()[..end].to_string(),
+    }
+}
+
+/// `Expires` dates, in the RFC 1123 form or the older dashed form
+fn parse_expires(value: &str) -> Option<SystemTime> {
+    if let Ok(date) = chrono::DateTime::parse_from_rfc2822(value) {
+        return Some(date.into());
+    }
+    chrono::NaiveDateTime::parse_from_str(value, "%a, %d-%b-%Y %H:%M:%S GMT")
+        .ok()
+        .map(|date| date.and_utc().into())
+}
+
+/// Cookies received by one client, replayed on matching requests.
+///
+/// Shared by every request of the client, so all access goes through a lock.
+/// Cookies are kept oldest first, which is the order they are evicted in.
+pub struct CookieJar {
+    cookies: Mutex<Vec<Cookie>>,
+    max_per_domain: usize,
+    max_size: usize,
+}
+
+impl Default for CookieJar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CookieJar {
+    /// Jar with the RFC 6265 minimums: 50 cookies per domain of up to 4096 bytes each
+    pub fn new() -> Self {
+        Self::with_limits(50, 4096)
+    }
+
+    /// Jar keeping at most `max_per_domain` cookies per domain, evicting the
+    /// oldest beyond that, and ignoring cookies whose name and value together
+    /// exceed `max_size` bytes
+    pub fn with_limits(max_per_domain: usize, max_size: usize) -> Self {
+        Self {
+            cookies: Mutex::new(Vec::new()),
+            max_per_domain,
+            max_size,
+        }
+    }
+
+    /// Number of unexpired cookies held
+    pub fn len(&self) -> usize {
+        let now = SystemTime::now();
+        let mut cookies = self.cookies.lock().unwrap();
+        cookies.retain(|cookie| !cookie.is_expired(now));
+        cookies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget every cookie
+    pub fn clear(&self) {
+        self.cookies.lock().unwrap().clear();
+    }
+
+    /// `Cookie` header value for a request to `url`, if any cookie matches.
+    ///
+    /// Longer paths come first, as RFC 6265 recommends.
+    pub fn cookie_header(&self, url: &str) -> Option<String> {
+        let url = Url::parse(url).ok()?;
+        let host = url.host_str()?.to_ascii_lowercase();
+        let now = SystemTime::now();
+        let mut cookies = self.cookies.lock().unwrap();
+        cookies.retain(|cookie| !cookie.is_expired(now));
+
+        let mut matching: Vec<&Cookie> = cookies
+            .iter()
+            .filter(|cookie| cookie.matches(&url, &host))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        matching.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+        let pairs: Vec<String> = matching
+            .iter()
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect();
+        Some(pairs.join("; "))
+    }
+
+    /// Store the `Set-Cookie` headers of a response to `url`, in header order.
+    ///
+    /// A cookie replaces an earlier one with the same name, domain and path,
+    /// keeping its place in the eviction order; an already expired one deletes
+    /// it. Oversized cookies are dropped.
+    pub(crate) fn store(&self, url: &str, headers: &HeaderMap) {
+        let Ok(url) = Url::parse(url) else {
+            return;
+        };
+        let now = SystemTime::now();
+        let received: Vec<Cookie> = headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| Cookie::parse(value, &url, now))
+            .filter(|cookie| cookie.name.len() + cookie.value.len() <= self.max_size)
+            .collect();
+        if received.is_empty() {
+            return;
+        }
+
+        let mut cookies = self.cookies.lock().unwrap();
+        for cookie in received {
+            match cookies.iter().position(|stored| stored.same_slot(&cookie)) {
+                Some(pos) => cookies[pos] = cookie,
+                None => cookies.push(cookie),
+            }
+        }
+        cookies.retain(|cookie| !cookie.is_expired(now));
+        self.evict(&mut cookies);
+    }
+
+    /// Drop the oldest cookies of every domain holding more than `max_per_domain`
+    fn evict(&self, cookies: &mut Vec<Cookie>) {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for cookie in cookies.iter() {
+            *counts.entry(cookie.domain.clone()).or_default() += 1;
+        }
+        cookies.retain(|cookie| {
+            let count = counts
+                .get_mut(&cookie.domain)
+                .expect("every domain is counted");
+            if *count > self.max_per_domain {
+                *count -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::header::HeaderValue;
+
+    fn set_cookies(values: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(SET_COOKIE, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_domain_and_path_scoping() {
+        let jar = CookieJar::new();
+        jar.store(
+            "https://www.example.com/account/login",
+            &set_cookies(&[
+                "host=1",
+                "wide=2; Domain=.example.com; Path=/",
+                "rooted=3; Path=/account/settings",
+                "foreign=4; Domain=other.com",
+            ]),
+        );
+
+        assert_eq!(
+            jar.cookie_header("https://www.example.com/account/settings/email")
+                .as_deref(),
+            Some("rooted=3; host=1; wide=2")
+        );
+        assert_eq!(
+            jar.cookie_header("https://www.example.com/accounts")
+                .as_deref(),
+            Some("wide=2")
+        );
+        assert_eq!(
+            jar.cookie_header("https://api.example.com/").as_deref(),
+            Some("wide=2")
+        );
+        assert_eq!(jar.cookie_header("https://other.com/"), None);
+        assert_eq!(jar.len(), 3);
+    }
+
+    #[test]
+    fn test_public_suffix_domains_are_rejected() {
+        let jar = CookieJar::new();
+        jar.store(
+            "https://shop.example.co.uk/",
+            &set_cookies(&[
+                "tld=1; Domain=uk",
+                "suffix=2; Domain=.co.uk",
+                "site=3; Domain=example.co.uk",
+            ]),
+        );
+        jar.store(
+            "https://evil.github.io/",
+            &set_cookies(&["hosted=4; Domain=github.io"]),
+        );
+        jar.store(
+            "http://localhost/",
+            &set_cookies(&["local=5; Domain=localhost"]),
+        );
+
+        assert_eq!(
+            jar.cookie_header("https://other.co.uk/"),
+            None,
+            "cookie leaked across a public suffix"
+        );
+        assert_eq!(jar.cookie_header("https://victim.github.io/"), None);
+        assert_eq!(
+            jar.cookie_header("https://www.example.co.uk/").as_deref(),
+            Some("site=3")
+        );
+        // A single-label host may still name itself, but only as a host-only cookie
+        assert_eq!(
+            jar.cookie_header("http://localhost/").as_deref(),
+            Some("local=5")
+        );
+        assert_eq!(jar.len(), 2);
+    }
+
+    #[test]
+    fn test_secure_and_expiry_attributes() {
+        let jar = CookieJar::new();
+        let url = "https://example.com/";
+        jar.store(
+            url,
+            &set_cookies(&[
+                "token=abc; Secure",
+                "old=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT",
+                "legacy=1; Expires=Wednesday, 21-Oct-2099 07:28:00 GMT",
+                "short=1; Max-Age=3600; Expires=Wed, 21 Oct 2015 07:28:00 GMT",
+            ]),
+        );
+        assert_eq!(
+            jar.cookie_header(url).as_deref(),
+            Some("token=abc; legacy=1; short=1")
+        );
+        assert_eq!(
+            jar.cookie_header("http://example.com/").as_deref(),
+            Some("legacy=1; short=1")
+        );
+
+        // Max-Age=0 deletes the stored cookie
+        jar.store(url, &set_cookies(&["token=; Max-Age=0"]));
+        assert_eq!(jar.cookie_header(url).as_deref(), Some("legacy=1; short=1"));
+    }
+
+    #[test]
+    fn test_oldest_cookies_of_a_full_domain_are_evicted() {
+        let jar = CookieJar::with_limits(2, 4096);
+        jar.store("https://a.example/", &set_cookies(&["one=1", "two=2"]));
+        jar.store("https://b.example/", &set_cookies(&["other=1"]));
+        // Replacing keeps the original slot, so "one" is still the oldest
+        jar.store(
+            "https://a.example/",
+            &set_cookies(&["one=updated", "three=3"]),
+        );
+
+        assert_eq!(
+            jar.cookie_header("https://a.example/").as_deref(),
+            Some("two=2; three=3")
+        );
+        assert_eq!(
+            jar.cookie_header("https://b.example/").as_deref(),
+            Some("other=1")
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! `Accept-Encoding` negotiation and transparent response decoding
+//!
+//! The client advertises the codings from `ClientConfig.accept_encodings` and
+//! decodes a response body only when its `Content-Encoding` is one of them.
+//! Any other coding is passed through untouched, headers included.
+
+use super::connector::ResponseBody;
+use crate::error::ApiError;
+use bytes::Bytes;
+use flate2::write::{GzDecoder, ZlibDecoder};
+use http_body_util::BodyExt;
+use hyper::body::{Body, Frame};
+use hyper::header::{CONTENT_ENCODING, CONTENT_LENGTH};
+use hyper::Response;
+use pin_project::pin_project;
+use std::io::Write;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+/// Content codings the client can decode
+pub(crate) const SUPPORTED_ENCODINGS: &[&str] = &["gzip", "deflate"];
+
+/// Failure while reading a response body
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum BodyError {
+    #[error(transparent)]
+    Http(#[from] hyper::Error),
+    #[error("Failed to decode {encoding} response body: {source}")]
+    Decode {
+        encoding: &'static str,
+        source: std::io::Error,
+    },
+}
+
+impl BodyError {
+    pub(crate) fn into_api_error(self) -> ApiError {
+        match self {
+            Self::Http(e) => ApiError::Http(e),
+            e @ Self::Decode { .. } => ApiError::stream(e.to_string()),
+        }
+    }
+}
+
+/// Canonical name of a supported coding, matched case-insensitively
+pub(crate) fn supported_encoding(name: &str) -> Option<&'static str> {
+    SUPPORTED_ENCODINGS
+        .iter()
+        .find(|supported| supported.eq_ignore_ascii_case(name.trim()))
+        .copied()
+}
+
+/// Decode `response` if it uses a single coding from `accepted`.
+///
+/// The decoded response loses `Content-Encoding` and `Content-Length`, which
+/// describe the encoded bytes.
+pub(crate) fn decode_response(
+    response: Response<ResponseBody>,
+    accepted: &[&'static str],
+) -> Response<ResponseBody> {
+    let encoding = response
+        .headers()
+        .get(CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .and_then(supported_encoding)
+        .filter(|encoding| accepted.contains(encoding));
+    let Some(encoding) = encoding else {
+        return response;
+    };
+
+    let (mut parts, body) = response.into_parts();
+    parts.headers.remove(CONTENT_ENCODING);
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Decoded::new(body, encoding).boxed())
+}
+
+/// Streaming decoder for one coding, fed whole chunks as they arrive
+enum Decoder {
+    Gzip(GzDecoder<Vec<u8>>),
+    Deflate(ZlibDecoder<Vec<u8>>),
+}
+
+impl Decoder {
+    fn new(encoding: &str) -> Self {
+        match encoding {
+            "gzip" => Self::Gzip(GzDecoder::new(Vec::new())),
+            _ => Self::Deflate(ZlibDecoder::new(Vec::new())),
+        }
+    }
+
+    /// Decode `chunk`, returning whatever output it completed
+    fn write(&mut self, chunk: &[u8]) -> std::io::Result<Bytes> {
+        match self {
+            Self::Gzip(decoder) => decoder.write_all(chunk)?,
+            Self::Deflate(decoder) => decoder.write_all(chunk)?,
+        }
+        Ok(self.take_output())
+    }
+
+    /// Flush the remaining output once the encoded body has ended
+    fn finish(&mut self) -> std::io::Result<Bytes> {
+        match self {
+            Self::Gzip(decoder) => decoder.try_finish()?,
+            Self::Deflate(decoder) => decoder.try_finish()?,
+        }
+        Ok(self.take_output())
+    }
+
+    fn take_output(&mut self) -> Bytes {
+        let output = match self {
+            Self::Gzip(decoder) => decoder.get_mut(),
+            Self::Deflate(decoder) => decoder.get_mut(),
+        };
+        Bytes::from(std::mem::take(output))
+    }
+}
+
+/// Body wrapper decoding data frames on the fly
+#[pin_project]
+struct Decoded {
+    #[pin]
+    inner: ResponseBody,
+    /// Taken once the encoded body has ended
+    decoder: Option<Decoder>,
+    encoding: &'static str,
+}
+
+impl Decoded {
+    fn new(inner: ResponseBody, encoding: &'static str) -> Self {
+        Self {
+            inner,
+            decoder: Some(Decoder::new(encoding)),
+            encoding,
+        }
+    }
+}
+
+impl Body for Decoded {
+    type Data = Bytes;
+    type Error = BodyError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<std::result::Result<Frame<Bytes>, BodyError>>> {
+        let mut this = self.project();
+        let encoding = *this.encoding;
+        let decode_error = |source| BodyError::Decode { encoding, source };
+        loop {
+            let Some(decoder) = this.decoder.as_mut() else {
+                return Poll::Ready(None);
+            };
+            let decoded = match ready!(this.inner.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => decoder.write(&data),
+                    Err(trailers) => return Poll::Ready(Some(Ok(trailers))),
+                },
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => {
+                    let finished = decoder.finish();
+                    *this.decoder = None;
+                    finished
+                }
+            };
+            match decoded {
+                Ok(data) if data.is_empty() => continue,
+                Ok(data) => return Poll::Ready(Some(Ok(Frame::data(data)))),
+                Err(e) => {
+                    *this.decoder = None;
+                    return Poll::Ready(Some(Err(decode_error(e))));
+                }
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.decoder.is_none()
+    }
+}
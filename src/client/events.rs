@@ -0,0 +1,129 @@
+//! Lightweight request/response event subscription
+//!
+//! Subscribers receive `ClientEvent`s over bounded channels. Delivery never blocks
+//! a request: when a subscriber's channel is full the event is dropped for it.
+
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Default capacity of a subscriber channel
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Events broadcast by `ApiClient` during the request lifecycle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientEvent {
+    /// A request is about to be sent
+    RequestStarted { method: String, url: String },
+    /// Response headers arrived (any status)
+    ResponseReceived { status: u16 },
+    /// The request ended in an error; `error_code` comes from `ApiError::code()`
+    RequestFailed { error_code: &'static str },
+    /// A retry attempt starts after `delay`; `reason` describes the failure being retried
+    RetryAttempted {
+        attempt: u32,
+        delay: Duration,
+        reason: String,
+    },
+    /// A phase of opening a new connection finished; reused pooled connections
+    /// emit nothing. Only delivered to `ApiClient::subscribe_with_connections`.
+    Connection(ConnectionEvent),
+}
+
+/// Timing of one phase of opening a connection, emitted as the phase completes.
+///
+/// Phases arrive in this order; a connection that fails part way still reports
+/// the phases it finished, e.g. `Connected` without `TlsEstablished`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The host name was resolved (IP literals skip this)
+    DnsResolved { host: String, elapsed: Duration },
+    /// The TCP connection was established; `elapsed` excludes resolution
+    Connected { host: String, elapsed: Duration },
+    /// The TLS handshake completed (`https` only)
+    TlsEstablished { host: String, elapsed: Duration },
+}
+
+struct Subscriber {
+    tx: mpsc::Sender<ClientEvent>,
+    /// Also receives `ClientEvent::Connection`
+    connections: bool,
+}
+
+/// Fan-out of events to all live subscribers
+#[derive(Default)]
+pub(crate) struct EventBus {
+    subscribers: Mutex<Vec<Subscriber>>,
+}
+
+impl EventBus {
+    /// Request lifecycle events only
+    pub(crate) fn subscribe(&self, capacity: usize) -> mpsc::Receiver<ClientEvent> {
+        self.add_subscriber(capacity, false)
+    }
+
+    /// Request lifecycle events interleaved with connection timing
+    pub(crate) fn subscribe_with_connections(
+        &self,
+        capacity: usize,
+    ) -> mpsc::Receiver<ClientEvent> {
+        self.add_subscriber(capacity, true)
+    }
+
+    fn add_subscriber(&self, capacity: usize, connections: bool) -> mpsc::Receiver<ClientEvent> {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        self.subscribers
+            .lock()
+            .unwrap()
+            .push(Subscriber { tx, connections });
+        rx
+    }
+
+    /// Deliver an event without blocking; closed subscribers are pruned
+    pub(crate) fn emit(&self, event: ClientEvent) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        if subscribers.is_empty() {
+            return;
+        }
+        let connection = matches!(event, ClientEvent::Connection(_));
+        subscribers.retain(|subscriber| {
+            if connection && !subscriber.connections {
+                return !subscriber.tx.is_closed();
+            }
+            match subscriber.tx.try_send(event.clone()) {
+                Ok(()) | Err(mpsc::error::TrySendError::Full(_)) => true,
+                Err(mpsc::error::TrySendError::Closed(_)) => false,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_channel_drops_events() {
+        let bus = EventBus::default();
+        let mut rx = bus.subscribe(1);
+
+        let retry = |attempt| ClientEvent::RetryAttempted {
+            attempt,
+            delay: Duration::from_millis(100),
+            reason: "HTTP 503".to_string(),
+        };
+        bus.emit(retry(1));
+        bus.emit(retry(2));
+
+        assert_eq!(rx.try_recv().unwrap(), retry(1));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_closed_subscribers_are_pruned() {
+        let bus = EventBus::default();
+        drop(bus.subscribe(4));
+        bus.emit(ClientEvent::ResponseReceived { status: 200 });
+        assert!(bus.subscribers.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,74 @@
+//! Canonical casing for header names exposed to callers
+//!
+//! hyper lowercases header names and does not keep the casing a server sent,
+//! so names are reconstructed: well-known headers with irregular casing come
+//! from a table, everything else is Train-Cased (`x-request-id` → `X-Request-Id`).
+
+use hyper::header::HeaderMap;
+use std::collections::HashMap;
+
+/// Headers whose canonical form is not plain Train-Case
+const IRREGULAR: &[&str] = &[
+    "Content-MD5",
+    "DNT",
+    "ETag",
+    "TE",
+    "WWW-Authenticate",
+    "X-DNS-Prefetch-Control",
+    "X-UA-Compatible",
+    "X-XSS-Protection",
+];
+
+/// Canonical casing for a header name, e.g. `content-type` → `Content-Type`, `etag` → `ETag`
+pub fn canonical_header_name(name: &str) -> String {
+    if let Some(known) = IRREGULAR
+        .iter()
+        .find(|known| known.eq_ignore_ascii_case(name))
+    {
+        return known.to_string();
+    }
+
+    let mut canonical = String::with_capacity(name.len());
+    let mut word_start = true;
+    for c in name.chars() {
+        if word_start {
+            canonical.push(c.to_ascii_uppercase());
+        } else {
+            canonical.push(c.to_ascii_lowercase());
+        }
+        word_start = c == '-';
+    }
+    canonical
+}
+
+/// Response headers keyed by canonical name; repeated headers are joined with `, `
+pub(crate) fn canonical_header_map(headers: &HeaderMap) -> HashMap<String, String> {
+    let mut map: HashMap<String, String> = HashMap::new();
+    for (name, value) in headers {
+        let value = String::from_utf8_lossy(value.as_bytes());
+        map.entry(canonical_header_name(name.as_str()))
+            .and_modify(|joined| {
+                joined.push_str(", ");
+                joined.push_str(&value);
+            })
+            .or_insert_with(|| value.into_owned());
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_header_name() {
+        assert_eq!(canonical_header_name("content-type"), "Content-Type");
+        assert_eq!(canonical_header_name("x-request-id"), "X-Request-Id");
+        assert_eq!(canonical_header_name("etag"), "ETag");
+        assert_eq!(
+            canonical_header_name("www-authenticate"),
+            "WWW-Authenticate"
+        );
+        assert_eq!(canonical_header_name("server"), "Server");
+    }
+}
@@ -0,0 +1,146 @@
+//! Connection setup timing for `ClientEvent::Connection`
+//!
+//! `Instrumented` wraps the whole HTTPS connector and `TimedTcp` the TCP
+//! connector inside it. While a connection is being opened, the resolver and
+//! `TimedTcp` note their durations in a task-local `ConnectTiming`; once the
+//! connection is ready `Instrumented` emits one event per phase, in order.
+
+use super::events::{ClientEvent, ConnectionEvent, EventBus};
+use hyper::Uri;
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower_service::Service;
+
+tokio::task_local! {
+    static CONNECT_TIMING: ConnectTiming;
+}
+
+/// The connection being opened and the phases it has finished so far
+struct ConnectTiming {
+    host: String,
+    events: Arc<EventBus>,
+    dns: Cell<Option<Duration>>,
+    /// When the TCP connection was established
+    connected_at: Cell<Option<Instant>>,
+}
+
+impl ConnectTiming {
+    fn emit(&self, event: ConnectionEvent) {
+        self.events.emit(ClientEvent::Connection(event));
+    }
+}
+
+/// Report that name resolution for the connection being opened finished
+pub(crate) fn dns_resolved(elapsed: Duration) {
+    let _ = CONNECT_TIMING.try_with(|timing| {
+        timing.dns.set(Some(elapsed));
+        timing.emit(ConnectionEvent::DnsResolved {
+            host: timing.host.clone(),
+            elapsed,
+        });
+    });
+}
+
+type BoxFuture<T, E> = Pin<Box<dyn Future<Output = std::result::Result<T, E>> + Send>>;
+
+/// TCP connector that notes when the socket is connected
+#[derive(Clone)]
+pub(crate) struct TimedTcp<C> {
+    inner: C,
+}
+
+impl<C> TimedTcp<C> {
+    pub(crate) fn new(inner: C) -> Self {
+        Self { inner }
+    }
+}
+
+impl<C> Service<Uri> for TimedTcp<C>
+where
+    C: Service<Uri>,
+    C::Future: Send + 'static,
+{
+    type Response = C::Response;
+    type Error = C::Error;
+    type Future = BoxFuture<C::Response, C::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let connecting = self.inner.call(uri);
+        Box::pin(async move {
+            let start = Instant::now();
+            let result = connecting.await;
+            if result.is_ok() {
+                let _ = CONNECT_TIMING.try_with(|timing| {
+                    // The inner connector resolves the name first
+                    let elapsed = start.elapsed();
+                    timing.connected_at.set(Some(Instant::now()));
+                    timing.emit(ConnectionEvent::Connected {
+                        host: timing.host.clone(),
+                        elapsed: elapsed.saturating_sub(timing.dns.get().unwrap_or_default()),
+                    });
+                });
+            }
+            result
+        })
+    }
+}
+
+/// Connector emitting `ClientEvent::Connection` events for each new connection
+#[derive(Clone)]
+pub(crate) struct Instrumented<C> {
+    inner: C,
+    events: Arc<EventBus>,
+}
+
+impl<C> Instrumented<C> {
+    pub(crate) fn new(inner: C, events: Arc<EventBus>) -> Self {
+        Self { inner, events }
+    }
+}
+
+impl<C> Service<Uri> for Instrumented<C>
+where
+    C: Service<Uri>,
+    C::Future: Send + 'static,
+{
+    type Response = C::Response;
+    type Error = C::Error;
+    type Future = BoxFuture<C::Response, C::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let timing = ConnectTiming {
+            host: uri.host().unwrap_or_default().to_string(),
+            events: self.events.clone(),
+            dns: Cell::default(),
+            connected_at: Cell::default(),
+        };
+        let tls = uri.scheme_str() == Some("https");
+        let connecting = self.inner.call(uri);
+        Box::pin(CONNECT_TIMING.scope(timing, async move {
+            let result = connecting.await;
+            if result.is_ok() && tls {
+                CONNECT_TIMING.with(|timing| {
+                    if let Some(connected_at) = timing.connected_at.get() {
+                        timing.emit(ConnectionEvent::TlsEstablished {
+                            host: timing.host.clone(),
+                            elapsed: connected_at.elapsed(),
+                        });
+                    }
+                });
+            }
+            result
+        }))
+    }
+}
@@ -0,0 +1,107 @@
+//! Small in-memory LRU for `download_json` results
+//!
+//! Entries hold the raw JSON text keyed by URL, so one cache serves every
+//! target type: each hit is deserialized again for the caller.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+struct Entry {
+    body: String,
+    stored_at: Instant,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<String, Entry>,
+    /// Keys from least to most recently used
+    recency: VecDeque<String>,
+}
+
+impl Inner {
+    fn touch(&mut self, url: &str) {
+        if let Some(pos) = self.recency.iter().position(|key| key == url) {
+            let key = self.recency.remove(pos).expect("position is in range");
+            self.recency.push_back(key);
+        }
+    }
+
+    fn remove(&mut self, url: &str) {
+        self.entries.remove(url);
+        self.recency.retain(|key| key != url);
+    }
+}
+
+/// Bounded, TTL-limited cache of JSON response bodies
+pub(crate) struct JsonCache {
+    capacity: usize,
+    ttl: Duration,
+    inner: Mutex<Inner>,
+}
+
+impl JsonCache {
+    pub(crate) fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Cached body for `url` if it is still fresh
+    pub(crate) fn get(&self, url: &str) -> Option<String> {
+        let mut inner = self.inner.lock().unwrap();
+        let fresh = inner.entries.get(url)?.stored_at.elapsed() < self.ttl;
+        if !fresh {
+            inner.remove(url);
+            return None;
+        }
+        inner.touch(url);
+        inner.entries.get(url).map(|entry| entry.body.clone())
+    }
+
+    /// Store `body` for `url`, evicting the least recently used entry when full
+    pub(crate) fn insert(&self, url: &str, body: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        let entry = Entry {
+            body,
+            stored_at: Instant::now(),
+        };
+        if inner.entries.insert(url.to_string(), entry).is_some() {
+            inner.touch(url);
+            return;
+        }
+        inner.recency.push_back(url.to_string());
+        while inner.recency.len() > self.capacity {
+            if let Some(oldest) = inner.recency.pop_front() {
+                inner.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recently_read_entry_survives_eviction() {
+        let cache = JsonCache::new(2, Duration::from_secs(60));
+        cache.insert("a", "1".to_string());
+        cache.insert("b", "2".to_string());
+        assert_eq!(cache.get("a").as_deref(), Some("1"));
+
+        cache.insert("c", "3".to_string());
+        assert_eq!(cache.get("a").as_deref(), Some("1"));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("c").as_deref(), Some("3"));
+    }
+}
@@ -0,0 +1,105 @@
+//! JSON-RPC 2.0 batches for `jsonrpc_batch`
+//!
+//! Calls get ids 1, 2, ... in order. Replies may come back in any order and are
+//! matched to their call by id; a call without a reply fails the whole batch.
+
+use crate::error::{ApiError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Error object a JSON-RPC server returned for one call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[error("JSON-RPC error {code}: {message}")]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+/// Outcome of one call in a batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonRpcResponse {
+    pub id: u64,
+    pub method: String,
+    /// The call's `result`, or the `error` the server answered it with
+    pub result: std::result::Result<Value, JsonRpcError>,
+}
+
+impl JsonRpcResponse {
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+#[derive(Deserialize)]
+struct Reply {
+    #[serde(default)]
+    id: Value,
+    result: Option<Value>,
+    error: Option<JsonRpcError>,
+}
+
+/// Request array for `calls`, with ids starting at 1
+pub(crate) fn batch_body(calls: &[(&str, Value)]) -> Value {
+    calls
+        .iter()
+        .zip(1u64..)
+        .map(|((method, params), id)| {
+            json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params})
+        })
+        .collect()
+}
+
+/// Match the replies in `body` to `calls`, returning them in call order
+pub(crate) fn correlate(calls: &[(&str, Value)], body: &str) -> Result<Vec<JsonRpcResponse>> {
+    let replies = match serde_json::from_str::<Value>(body)? {
+        Value::Array(replies) => replies,
+        // A server that cannot read the batch at all answers with one error object
+        reply => {
+            let error = serde_json::from_value::<Reply>(reply)
+                .ok()
+                .and_then(|reply| reply.error)
+                .map_or_else(
+                    || "expected an array of replies".to_string(),
+                    |e| e.to_string(),
+                );
+            return Err(ApiError::other(format!(
+                "JSON-RPC batch rejected: {}",
+                error
+            )));
+        }
+    };
+
+    let mut by_id = HashMap::new();
+    for reply in replies {
+        let reply: Reply = serde_json::from_value(reply)?;
+        // Replies with a null id (invalid requests) cannot be matched to a call
+        if let Some(id) = reply.id.as_u64() {
+            by_id.insert(id, reply);
+        }
+    }
+
+    calls
+        .iter()
+        .zip(1u64..)
+        .map(|((method, _), id)| {
+            let reply = by_id.remove(&id).ok_or_else(|| {
+                ApiError::other(format!(
+                    "JSON-RPC batch has no reply for call {} ({})",
+                    id, method
+                ))
+            })?;
+            let result = match reply.error {
+                Some(error) => Err(error),
+                None => Ok(reply.result.unwrap_or(Value::Null)),
+            };
+            Ok(JsonRpcResponse {
+                id,
+                method: method.to_string(),
+                result,
+            })
+        })
+        .collect()
+}
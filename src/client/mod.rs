@@ -1,34 +1,537 @@
+#[cfg(feature = "archive")]
+mod archive;
+mod batch;
+mod body;
+mod capabilities;
+mod coalesce;
+mod codec;
+pub(crate) mod connector;
+mod cookies;
+mod encoding;
+mod events;
+mod form;
+mod header_case;
+mod instrument;
+mod json_cache;
+mod jsonrpc;
+mod options;
+mod priority;
+mod proxy;
+#[cfg(feature = "config-watch")]
+mod reload;
+mod resolver;
+mod serializer;
+mod traffic;
+
+#[cfg(feature = "archive")]
+pub use archive::ArchiveFormat;
+pub use batch::BatchResult;
+pub use body::BodyChunk;
+pub use capabilities::Capabilities;
+pub use codec::BodyCodec;
+pub use connector::tls_backend;
+pub use cookies::CookieJar;
+pub use events::{ClientEvent, ConnectionEvent, EVENT_CHANNEL_CAPACITY};
+pub use form::MultipartForm;
+pub use header_case::canonical_header_name;
+pub use jsonrpc::{JsonRpcError, JsonRpcResponse};
+pub use options::{BodyExpectation, ByteRange, RequestOptions, ResponseContent};
+pub use priority::Priority;
+pub use resolver::{Resolver, StaticResolver, SystemResolver};
+pub use serializer::{SerializerRegistry, DEFAULT_CONTENT_TYPE};
+pub use traffic::TrafficStats;
+
 use crate::config::Config;
-use crate::error::{ApiError, Result};
-use crate::plugin::PluginManager;
+use crate::error::{ApiError, AuthDetail, BuildError, Result, TimeoutDetail, TimeoutPhase};
+use crate::plugin::{PluginContext, PluginHook, PluginManager, PLUGIN_HEADERS_KEY};
 use crate::sec::{HeaderValidator, UrlValidator};
-use std::sync::Arc;
+use crate::streaming::{
+    boundary_from_content_type, with_read_timeout, LineStream, MultipartStream, NdjsonStream, Part,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
+use base64::prelude::{Engine, BASE64_STANDARD};
+use body::{BodyRecording, ChannelBody, ReaderBody, RecordedBody};
 use bytes::Bytes;
-use http_body_util::{BodyExt, Empty, Full};
-use hyper::{Method, Request};
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::rt::TokioExecutor;
+use coalesce::{Claim, InFlightDownloads};
+use connector::{
+    https_connector, pooled_client, ConnectOptions, HttpClient, HttpsConnector, RequestBody,
+    ResponseBody,
+};
+use encoding::{decode_response, supported_encoding, BodyError};
+use events::EventBus;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use header_case::canonical_header_map;
+use http_body_util::{BodyExt, Empty, Full, Limited};
+use hyper::body::Body;
+use hyper::{Method, Request, StatusCode};
+use hyper_util::client::proxy::matcher::Matcher;
+use json_cache::JsonCache;
+use priority::{DispatchPermit, DispatchQueue};
+use proxy::{proxy_authorization, proxy_matcher};
+use resolver::ResolvePins;
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 use serde_json;
 use std::collections::HashMap;
+use std::future::Future;
+use std::net::IpAddr;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::{mpsc, watch};
 use tokio::time::timeout;
+use traffic::TrafficCounters;
+
+/// Characters left unescaped in a path segment (RFC 3986 unreserved set)
+const PATH_SEGMENT: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Substitute `{name}` placeholders with percent-encoded parameter values
+fn expand_path_template(template: &str, params: &[(&str, &str)]) -> Result<String> {
+    let mut expanded = String::with_capacity(template.len());
+    let mut used = vec![false; params.len()];
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        expanded.push_str(&rest[..open]);
+        let close = rest[open..].find('}').map(|i| open + i).ok_or_else(|| {
+            ApiError::other(format!("Unclosed placeholder in path: {}", template))
+        })?;
+        let name = &rest[open + 1..close];
+
+        let index = params
+            .iter()
+            .position(|(key, _)| *key == name)
+            .ok_or_else(|| ApiError::other(format!("Missing path parameter: {}", name)))?;
+        used[index] = true;
+        expanded.extend(percent_encoding::utf8_percent_encode(
+            params[index].1,
+            PATH_SEGMENT,
+        ));
+
+        rest = &rest[close + 1..];
+    }
+    expanded.push_str(rest);
+
+    if let Some(index) = used.iter().position(|u| !u) {
+        return Err(ApiError::other(format!(
+            "Unused path parameter: {}",
+            params[index].0
+        )));
+    }
+    Ok(expanded)
+}
+
+/// Append percent-encoded `key=value` pairs to the query of `url`, after any it already has.
+///
+/// Repeated keys are kept in order; an empty value is sent as `key=`.
+fn append_query(url: &str, pairs: &[(&str, &str)]) -> Result<String> {
+    let mut parsed = url::Url::parse(url)
+        .map_err(|e| ApiError::validation(format!("Invalid URL {}: {}", url, e)))?;
+    if pairs.is_empty() {
+        return Ok(parsed.into());
+    }
+
+    let mut query = parsed.query().unwrap_or_default().to_string();
+    for (key, value) in pairs {
+        if !query.is_empty() {
+            query.push('&');
+        }
+        query.extend(percent_encoding::utf8_percent_encode(key, PATH_SEGMENT));
+        query.push('=');
+        query.extend(percent_encoding::utf8_percent_encode(value, PATH_SEGMENT));
+    }
+    parsed.set_query(Some(&query));
+    Ok(parsed.into())
+}
+
+/// Empty request body
+fn empty_body() -> RequestBody {
+    Empty::<Bytes>::new()
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+        .boxed()
+}
+
+/// In-memory request body
+fn full_body(data: impl Into<Bytes>) -> RequestBody {
+    Full::new(data.into())
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+        .boxed()
+}
+
+/// Read a whole response body
+async fn read_body(response: hyper::Response<ResponseBody>, limit: Option<u64>) -> Result<Bytes> {
+    let body = response.into_body();
+    let collected = match limit {
+        Some(limit) => Limited::new(body, limit as usize)
+            .collect()
+            .await
+            .map_err(|e| {
+                if e.is::<http_body_util::LengthLimitError>() {
+                    response_too_large(limit)
+                } else {
+                    ApiError::other(format!("Failed to read response body: {}", e))
+                }
+            })?,
+        None => body
+            .collect()
+            .await
+            .map_err(|e| ApiError::other(format!("Failed to read response body: {}", e)))?,
+    };
+    Ok(collected.to_bytes())
+}
+
+/// Context for one hook of a request, carrying its `RequestOptions::metadata`
+fn plugin_context(
+    hook: PluginHook,
+    metadata: &HashMap<String, serde_json::Value>,
+) -> PluginContext {
+    PluginContext::new(hook).with_metadata_map(metadata)
+}
+
+/// Why streaming a body to disk stopped early
+enum WriteError {
+    /// The connection failed mid-body; everything counted in `written` is on disk
+    Interrupted(ApiError),
+    /// A failure another attempt would not fix (disk, cancellation)
+    Fatal(ApiError),
+    /// The body would grow the file past this many bytes
+    TooLarge(u64),
+}
+
+/// Append a response body to `file`, counting the bytes in `written`.
+///
+/// `limit` caps the file as a whole, including bytes written by earlier attempts.
+async fn write_body(
+    response: hyper::Response<ResponseBody>,
+    file: &mut fs::File,
+    written: &mut u64,
+    limit: Option<u64>,
+) -> std::result::Result<(), WriteError> {
+    let mut body = response.into_body().into_data_stream();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|e| {
+            WriteError::Interrupted(ApiError::other(format!(
+                "Failed to read response body: {}",
+                e
+            )))
+        })?;
+        if let Some(limit) = limit.filter(|limit| *written + chunk.len() as u64 > *limit) {
+            return Err(WriteError::TooLarge(limit));
+        }
+        file.write_all(&chunk).await.map_err(|e| {
+            WriteError::Fatal(ApiError::other(format!("Failed to write file: {}", e)))
+        })?;
+        *written += chunk.len() as u64;
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| WriteError::Fatal(ApiError::other(format!("Failed to flush file: {}", e))))
+}
+
+/// Hidden sibling of `path` that a download is written to before it is renamed over `path`
+fn temp_sibling(path: &std::path::Path) -> std::path::PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.part", name, uuid::Uuid::new_v4().simple()))
+}
+
+/// Run `write` against a temp sibling of `path`, then rename it over `path`.
+///
+/// On failure only the temp file is removed, so an existing `path` is kept.
+async fn write_via_temp<F, Fut>(path: &std::path::Path, write: F) -> Result<()>
+where
+    F: FnOnce(std::path::PathBuf) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let temp = temp_sibling(path);
+    let result = match write(temp.clone()).await {
+        Ok(()) => fs::rename(&temp, path)
+            .await
+            .map_err(|e| ApiError::other(format!("Failed to move download into place: {}", e))),
+        Err(e) => Err(e),
+    };
+    if result.is_err() {
+        let _ = fs::remove_file(&temp).await;
+    }
+    result
+}
+
+async fn create_file(path: &std::path::Path) -> Result<fs::File> {
+    fs::File::create(path)
+        .await
+        .map_err(|e| ApiError::other(format!("Failed to create file: {}", e)))
+}
+
+/// Whether the reply to `Range: bytes={offset}-` continues exactly at `offset`
+fn resumes_at(response: &hyper::Response<ResponseBody>, offset: u64) -> bool {
+    if response.status() != StatusCode::PARTIAL_CONTENT {
+        return false;
+    }
+    let start = response
+        .headers()
+        .get(hyper::header::CONTENT_RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("bytes "))
+        .and_then(|value| value.split_once('-'))
+        .and_then(|(start, _)| start.trim().parse::<u64>().ok());
+    start == Some(offset)
+}
+
+fn response_too_large(limit: u64) -> ApiError {
+    ApiError::other(format!("Response body exceeds max_response_size {}", limit))
+}
+
+fn download_too_large(limit: u64) -> ApiError {
+    ApiError::other(format!(
+        "Download exceeds the maximum size of {} bytes",
+        limit
+    ))
+}
+
+/// `Location` of a response the client follows as a redirect (301, 302, 303, 307 or 308)
+fn redirect_location(
+    status: StatusCode,
+    headers: &hyper::HeaderMap,
+) -> Option<&hyper::header::HeaderValue> {
+    if !matches!(status.as_u16(), 301 | 302 | 303 | 307 | 308) {
+        return None;
+    }
+    headers.get(hyper::header::LOCATION)
+}
+
+fn too_many_redirects(url: &str, limit: usize) -> ApiError {
+    ApiError::other(format!(
+        "Too many redirects for {}: max_redirects is {}",
+        url, limit
+    ))
+}
+
+/// Redirects and retries spent on one call, shared by every attempt it makes
+struct HopBudget<'a> {
+    url: &'a str,
+    limit: Option<usize>,
+    used: AtomicUsize,
+}
+
+impl<'a> HopBudget<'a> {
+    fn new(url: &'a str, limit: Option<usize>) -> Self {
+        Self {
+            url,
+            limit,
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    /// Claim one more hop, or fail with `ApiError::TooManyHops` once `max_total_hops` is spent
+    fn take(&self) -> Result<()> {
+        let used = self.used.fetch_add(1, Ordering::Relaxed);
+        match self.limit {
+            Some(limit) if used >= limit => Err(ApiError::TooManyHops {
+                url: self.url.to_string(),
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Chunks buffered ahead of a request body fed from a stream
+const STREAMED_BODY_CAPACITY: usize = 16;
+
+/// Largest request body kept in memory to repeat on a 307/308 redirect
+const MAX_REPLAY_BODY: usize = 64 * 1024;
+
+/// Request body fed from `stream` by a background task.
+///
+/// An `Err` item aborts the body; the task stops early once the request gives
+/// up on the body and drops the receiver.
+fn streamed_body<S>(stream: S) -> RequestBody
+where
+    S: futures::Stream<Item = Result<Bytes>> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel::<Result<Bytes>>(STREAMED_BODY_CAPACITY);
+    tokio::spawn(async move {
+        let mut stream = std::pin::pin!(stream);
+        while let Some(chunk) = stream.next().await {
+            let failed = chunk.is_err();
+            if tx.send(chunk).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+    ChannelBody::new(rx).boxed()
+}
+
+/// A request on its way through the shared send path
+struct Outgoing<'a> {
+    method: Method,
+    url: &'a str,
+    content_type: Option<&'a str>,
+    body: RequestBody,
+    priority: Priority,
+    options: RequestOptions,
+    /// Turn non-2xx responses into errors instead of returning their body
+    error_for_status: bool,
+    /// Return 3xx responses that carry a `Location` instead of an error
+    pass_redirects: bool,
+    /// The body is a one-shot stream, never copied to repeat on a 307/308
+    streamed: bool,
+    /// Charged for every redirect followed, alongside `max_redirects`
+    hops: Option<&'a HopBudget<'a>>,
+}
+
+impl<'a> Outgoing<'a> {
+    fn new(method: Method, url: &'a str, content_type: Option<&'a str>, body: RequestBody) -> Self {
+        Self {
+            method,
+            url,
+            content_type,
+            body,
+            priority: Priority::Normal,
+            options: RequestOptions::default(),
+            error_for_status: true,
+            pass_redirects: false,
+            streamed: false,
+            hops: None,
+        }
+    }
+
+    fn priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    fn options(mut self, options: &RequestOptions) -> Self {
+        self.options = options.clone();
+        self
+    }
+
+    /// Return non-2xx responses with their body instead of `ApiError::HttpStatus`
+    fn any_status(mut self) -> Self {
+        self.error_for_status = false;
+        self
+    }
+
+    /// Return redirects with a `Location` for the caller to follow
+    fn pass_redirects(mut self) -> Self {
+        self.pass_redirects = true;
+        self
+    }
+
+    /// Mark the body as a stream that a 307/308 redirect cannot repeat
+    fn streamed(mut self) -> Self {
+        self.streamed = true;
+        self
+    }
+
+    /// Count followed redirects against a budget shared with the caller's retries
+    fn hops(mut self, hops: &'a HopBudget<'a>) -> Self {
+        self.hops = Some(hops);
+        self
+    }
+}
+
+/// Status, headers and body text of a completed request
+///
+/// Header names are canonically cased (`Content-Type`, `ETag`); repeated headers
+/// are joined with `, ` and non-UTF-8 values are decoded lossily. Use
+/// `Response::header` to look one up regardless of casing.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: StatusCode,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl Response {
+    /// Value of the header `name`, matched case-insensitively (`etag`, `ETag`, `ETAG`)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&canonical_header_name(name))
+            .map(String::as_str)
+    }
+}
+
+/// Response read in full
+struct Received {
+    status: StatusCode,
+    headers: hyper::HeaderMap,
+    body: Bytes,
+}
+
+/// Response whose body has not been read yet
+struct OpenResponse {
+    response: hyper::Response<ResponseBody>,
+    /// Dispatch slot, held until the body has been consumed
+    permit: DispatchPermit,
+    /// Fires when `abort_all` is called after the request started
+    aborted: watch::Receiver<u64>,
+}
+
+/// Decides from a successful response body whether to try the request again
+pub type RetryPredicate = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Client settings that may change on a live client (see `with_config_reload`)
+#[derive(Debug, Clone)]
+pub(crate) struct LiveSettings {
+    pub(crate) default_headers: HashMap<String, String>,
+    pub(crate) timeout: Duration,
+    pub(crate) max_retries: usize,
+    pub(crate) retry_delay: Duration,
+    pub(crate) max_redirects: usize,
+    pub(crate) max_total_hops: Option<usize>,
+}
+
+impl LiveSettings {
+    pub(crate) fn from_config(config: &Config) -> Self {
+        Self {
+            default_headers: config.client.default_headers.clone(),
+            timeout: config.timeout(),
+            max_retries: config.client.max_retries,
+            retry_delay: config.retry_delay(),
+            max_redirects: config.client.max_redirects,
+            max_total_hops: config.client.max_total_hops,
+        }
+    }
+}
 
 /// Main API client using proven driver patterns with plugin integration
 pub struct ApiClient {
     config: Config,
     plugin_manager: Arc<PluginManager>,
-    client: Client<
-        HttpsConnector<hyper_util::client::legacy::connect::HttpConnector>,
-        http_body_util::combinators::BoxBody<Bytes, Box<dyn std::error::Error + Send + Sync>>,
-    >,
-    timeout_duration: Duration,
+    connector: HttpsConnector,
+    client: RwLock<HttpClient>,
+    live: Arc<RwLock<LiveSettings>>,
+    #[cfg(feature = "config-watch")]
+    config_watcher: Option<notify::RecommendedWatcher>,
     custom_headers: HashMap<String, String>,
     user_agent: String,
+    host_header: Option<String>,
+    events: Arc<EventBus>,
+    dispatch: Arc<DispatchQueue>,
+    aborts: watch::Sender<u64>,
+    downloads: InFlightDownloads,
+    json_cache: Option<JsonCache>,
+    cookies: Option<CookieJar>,
+    retry_if: Option<RetryPredicate>,
+    serializers: SerializerRegistry,
+    traffic: Arc<TrafficCounters>,
+    chunked_uploads: bool,
+    /// Advertised in `Accept-Encoding` and decoded transparently
+    accept_encodings: Vec<&'static str>,
+    /// Proxy routing shared with the connector
+    proxy: Option<Arc<Matcher>>,
+    body_codec: Option<Arc<dyn BodyCodec>>,
 }
 
 /// Builder pattern for ApiClient configuration
@@ -37,6 +540,19 @@ pub struct ApiClientBuilder {
     plugin_manager: Option<PluginManager>,
     custom_headers: HashMap<String, String>,
     user_agent: Option<String>,
+    host_header: Option<String>,
+    bearer_token_file: Option<std::path::PathBuf>,
+    /// Scheme of the `Authorization` header set by an auth helper, if any
+    auth_scheme: Option<&'static str>,
+    max_concurrent_requests: Option<usize>,
+    resolver: Option<Arc<dyn Resolver>>,
+    resolve_pins: ResolvePins,
+    json_cache: Option<(usize, Duration)>,
+    cookie_jar: bool,
+    retry_if: Option<RetryPredicate>,
+    serializers: Option<SerializerRegistry>,
+    chunked_uploads: bool,
+    body_codec: Option<Arc<dyn BodyCodec>>,
 }
 
 impl ApiClientBuilder {
@@ -47,6 +563,18 @@ impl ApiClientBuilder {
             plugin_manager: None,
             custom_headers: HashMap::new(),
             user_agent: None,
+            host_header: None,
+            bearer_token_file: None,
+            auth_scheme: None,
+            max_concurrent_requests: None,
+            resolver: None,
+            resolve_pins: ResolvePins::new(),
+            json_cache: None,
+            cookie_jar: false,
+            retry_if: None,
+            serializers: None,
+            chunked_uploads: false,
+            body_codec: None,
         }
     }
 
@@ -62,9 +590,11 @@ impl ApiClientBuilder {
         self
     }
 
-    /// Add custom header
+    /// Add custom header; names are case-insensitive, so a later `X-Foo` replaces `x-foo`
     pub fn with_header(mut self, key: String, value: String) -> Result<Self> {
-        HeaderValidator::validate_header(&key, &value)?;
+        check_header(&key, &value)?;
+        self.custom_headers
+            .retain(|name, _| !name.eq_ignore_ascii_case(&key));
         self.custom_headers.insert(key, value);
         Ok(self)
     }
@@ -75,12 +605,166 @@ impl ApiClientBuilder {
         self
     }
 
-    /// Build the ApiClient
-    pub async fn build(self) -> Result<ApiClient> {
-        let config = self.config.unwrap_or_default();
+    /// Send this `Host` header while still connecting to each URL's own authority.
+    ///
+    /// A redirect to another origin is sent with that origin's own `Host`.
+    pub fn with_host_header(mut self, host: String) -> Result<Self> {
+        check_header("host", &host)?;
+        self.host_header = Some(host);
+        Ok(self)
+    }
+
+    /// Send `Authorization: Basic <base64(user:password)>` with every request.
+    ///
+    /// Credentials with control characters, or a user name containing `:`, fail
+    /// with `BuildError::InvalidHeader`. Combining this with another auth helper
+    /// fails with `BuildError::ConflictingAuth`.
+    pub fn with_basic_auth(self, user: &str, password: &str) -> Result<Self> {
+        check_header("authorization", user)?;
+        check_header("authorization", password)?;
+        if user.contains(':') {
+            return Err(BuildError::InvalidHeader {
+                name: "authorization".to_string(),
+                reason: "Basic auth user name must not contain ':'".to_string(),
+            }
+            .into());
+        }
+        let credentials = BASE64_STANDARD.encode(format!("{}:{}", user, password));
+        self.with_auth("Basic", credentials)
+    }
+
+    /// Send `Authorization: Bearer <token>` with every request.
+    ///
+    /// A token with control characters fails with `BuildError::InvalidHeader`;
+    /// combining this with another auth helper fails with `BuildError::ConflictingAuth`.
+    pub fn with_bearer_token(self, token: &str) -> Result<Self> {
+        self.with_auth("Bearer", token.to_string())
+    }
+
+    /// Set `Authorization: <scheme> <credentials>` unless another helper already set it
+    fn with_auth(mut self, scheme: &'static str, credentials: String) -> Result<Self> {
+        if let Some(existing) = self.auth_scheme {
+            return Err(BuildError::ConflictingAuth {
+                existing,
+                requested: scheme,
+            }
+            .into());
+        }
+        self = self.with_header(
+            "Authorization".to_string(),
+            format!("{} {}", scheme, credentials),
+        )?;
+        self.auth_scheme = Some(scheme);
+        Ok(self)
+    }
+
+    /// Send `Authorization: Bearer <token>` with the token read from `path` when building.
+    ///
+    /// Surrounding whitespace and the trailing newline are trimmed. A missing,
+    /// unreadable or empty file fails `build` with `BuildError::TokenFile`.
+    pub fn with_bearer_token_file(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.bearer_token_file = Some(path.into());
+        self
+    }
+
+    /// Resolve hostnames with `resolver` instead of the system resolver
+    pub fn with_resolver(mut self, resolver: Arc<dyn Resolver>) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    /// Connect to `ip` for `host:port` without resolving it, like curl's `--resolve`.
+    ///
+    /// The URL is otherwise untouched, so TLS SNI, certificate checks and the
+    /// `Host` header still use `host`. Other ports of the same host resolve normally.
+    pub fn with_resolve(mut self, host: &str, port: u16, ip: IpAddr) -> Self {
+        self.resolve_pins
+            .insert((host.to_ascii_lowercase(), port), ip);
+        self
+    }
+
+    /// Cap the number of requests in flight at once.
+    ///
+    /// Defaults to `streaming.max_concurrent_streams`. Requests beyond the cap
+    /// wait in a queue ordered by `Priority`.
+    pub fn with_max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.max_concurrent_requests = Some(limit);
+        self
+    }
+
+    /// Cache up to `capacity` `download_json` results in memory for `ttl`.
+    ///
+    /// Entries are keyed by URL and evicted least recently used first. This is
+    /// separate from any HTTP caching plugin and ignores response cache headers.
+    pub fn with_json_cache(mut self, capacity: usize, ttl: Duration) -> Self {
+        self.json_cache = Some((capacity, ttl));
+        self
+    }
+
+    /// Keep cookies from `Set-Cookie` responses and send them on later requests.
+    ///
+    /// Cookies are scoped by domain and path, `Secure` ones only go over https,
+    /// and expired ones are dropped. `client.max_cookies_per_domain` and
+    /// `client.max_cookie_size` bound what is kept. Without this, responses'
+    /// cookies are ignored.
+    pub fn with_cookie_jar(mut self) -> Self {
+        self.cookie_jar = true;
+        self
+    }
+
+    /// Retry successful responses whose body `predicate` accepts.
+    ///
+    /// For APIs that report transient failures as `200` with a body like
+    /// `{"retryable":true}`. Applies to `execute_request_with_retry`, where such
+    /// attempts share `max_retries`, `retry_delay` and `max_total_hops` with
+    /// failed ones. Once retries run out, the last body is returned as is.
+    pub fn with_retry_if(mut self, predicate: RetryPredicate) -> Self {
+        self.retry_if = Some(predicate);
+        self
+    }
+
+    /// Use `registry` to encode and decode bodies in the typed methods (`get_typed`, `post_typed`, ...)
+    pub fn with_serializers(mut self, registry: SerializerRegistry) -> Self {
+        self.serializers = Some(registry);
+        self
+    }
+
+    /// Send every request body with chunked transfer encoding.
+    ///
+    /// By default bodies of known length (JSON, forms, bytes, files) carry an
+    /// explicit `Content-Length` and only streaming bodies are sent chunked.
+    pub fn with_chunked_uploads(mut self, always: bool) -> Self {
+        self.chunked_uploads = always;
+        self
+    }
+
+    /// Pass every request body through `codec.encode` before sending and every
+    /// response body read in full through `codec.decode` (see `BodyCodec`)
+    pub fn with_body_codec(mut self, codec: Arc<dyn BodyCodec>) -> Self {
+        self.body_codec = Some(codec);
+        self
+    }
+
+    /// Build the ApiClient.
+    ///
+    /// Misconfigurations (invalid default headers or user agent, a bad `base_url`,
+    /// unusable TLS files or settings) fail with `ApiError::Build`.
+    pub async fn build(mut self) -> Result<ApiClient> {
+        if let Some(path) = self.bearer_token_file.take() {
+            if let Some(existing) = self.auth_scheme {
+                return Err(BuildError::ConflictingAuth {
+                    existing,
+                    requested: "Bearer token file",
+                }
+                .into());
+            }
+            let token = read_token_file(&path).await?;
+            self = self.with_auth("Bearer", token)?;
+        }
+        let config = self.config.take().unwrap_or_default();
 
         // Use config-based plugin loading if no explicit plugin manager provided
-        let plugin_manager = if let Some(manager) = self.plugin_manager {
+        let plugin_manager = if let Some(manager) = self.plugin_manager.take() {
             Arc::new(manager)
         } else {
             // Try to load plugins from config, fall back to empty manager
@@ -90,39 +774,155 @@ impl ApiClientBuilder {
             }
         };
 
-        let connector = HttpsConnector::new();
-        let client = Client::builder(TokioExecutor::new()).build(connector);
-        let timeout_duration = Duration::from_secs(config.client.timeout as u64);
+        self.assemble(config, plugin_manager)
+    }
+
+    /// Validate `config` and create the client; shared by `build` and `ApiClient::try_new`
+    fn assemble(self, config: Config, plugin_manager: Arc<PluginManager>) -> Result<ApiClient> {
         let user_agent = self
             .user_agent
             .unwrap_or_else(|| config.client.user_agent.clone());
+        check_header("user-agent", &user_agent)?;
+        for (key, value) in &config.client.default_headers {
+            check_header(key, value)?;
+        }
+        if let Some(base_url) = &config.client.base_url {
+            check_base_url(base_url)?;
+        }
+        let accept_encodings = config
+            .client
+            .accept_encodings
+            .iter()
+            .map(|name| {
+                supported_encoding(name).ok_or_else(|| BuildError::UnsupportedEncoding {
+                    encoding: name.clone(),
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let events = Arc::new(EventBus::default());
+        let proxy = proxy_matcher(&config.client)?;
+        let mut options = ConnectOptions {
+            keepalive: config.keepalive_interval(),
+            events: events.clone(),
+            proxy: proxy.clone(),
+            ..ConnectOptions::default()
+        };
+        if let Some(resolver) = self.resolver {
+            options.resolver = resolver;
+        }
+        options.pins = Arc::new(self.resolve_pins);
+        let connector = https_connector(&config.client.tls, &options)?;
+        let live = Arc::new(RwLock::new(LiveSettings::from_config(&config)));
+        let dispatch = DispatchQueue::new(
+            self.max_concurrent_requests
+                .unwrap_or(config.streaming.max_concurrent_streams),
+        );
+        let cookies = self.cookie_jar.then(|| {
+            CookieJar::with_limits(
+                config.client.max_cookies_per_domain,
+                config.client.max_cookie_size,
+            )
+        });
 
         Ok(ApiClient {
             config,
             plugin_manager,
-            client,
-            timeout_duration,
+            client: RwLock::new(pooled_client(connector.clone())),
+            connector,
+            live,
+            #[cfg(feature = "config-watch")]
+            config_watcher: None,
             custom_headers: self.custom_headers,
             user_agent,
+            host_header: self.host_header,
+            events,
+            dispatch,
+            aborts: watch::Sender::new(0),
+            downloads: InFlightDownloads::default(),
+            json_cache: self
+                .json_cache
+                .map(|(capacity, ttl)| JsonCache::new(capacity, ttl)),
+            cookies,
+            retry_if: self.retry_if,
+            serializers: self.serializers.unwrap_or_default(),
+            traffic: Arc::default(),
+            chunked_uploads: self.chunked_uploads,
+            accept_encodings,
+            proxy,
+            body_codec: self.body_codec,
         })
     }
 }
 
+/// Check a header the client will send on every request
+fn check_header(name: &str, value: &str) -> std::result::Result<(), BuildError> {
+    let invalid = |reason: String| BuildError::InvalidHeader {
+        name: name.to_string(),
+        reason,
+    };
+    HeaderValidator::validate_header(name, value).map_err(|e| {
+        invalid(match e {
+            ApiError::Other(reason) => reason,
+            e => e.to_string(),
+        })
+    })?;
+    hyper::header::HeaderName::from_bytes(name.as_bytes()).map_err(|e| invalid(e.to_string()))?;
+    hyper::header::HeaderValue::from_str(value).map_err(|e| invalid(e.to_string()))?;
+    Ok(())
+}
+
+/// Token from a mounted secret file, without surrounding whitespace
+async fn read_token_file(path: &std::path::Path) -> std::result::Result<String, BuildError> {
+    let invalid = |reason: String| BuildError::TokenFile {
+        path: path.to_path_buf(),
+        reason,
+    };
+    let contents = fs::read_to_string(path)
+        .await
+        .map_err(|e| invalid(e.to_string()))?;
+    let token = contents.trim();
+    if token.is_empty() {
+        return Err(invalid("file is empty".to_string()));
+    }
+    Ok(token.to_string())
+}
+
+/// `base_url` must be an absolute http(s) URL for `ApiClient::path` to join onto
+fn check_base_url(base_url: &str) -> std::result::Result<(), BuildError> {
+    let invalid = |reason: String| BuildError::InvalidBaseUrl {
+        url: base_url.to_string(),
+        reason,
+    };
+    let url = url::Url::parse(base_url).map_err(|e| invalid(e.to_string()))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid(format!("unsupported scheme {}", url.scheme())));
+    }
+    Ok(())
+}
+
+impl Default for ApiClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ApiClient {
+    /// Create a client from configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a configuration `ApiClientBuilder::build` would reject (invalid
+    /// default headers, `base_url`, TLS, proxy or accept encoding settings); use
+    /// `try_new` to get the `ApiError::Build` instead.
     pub fn new(config: Config) -> Self {
-        let connector = HttpsConnector::new();
-        let client = Client::builder(TokioExecutor::new()).build(connector);
-        let plugin_manager = Arc::new(PluginManager::new());
-        let timeout_duration = Duration::from_secs(config.client.timeout as u64);
+        Self::try_new(config).unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        Self {
-            user_agent: config.client.user_agent.clone(),
-            config,
-            plugin_manager,
-            client,
-            timeout_duration,
-            custom_headers: HashMap::new(),
-        }
+    /// Create a client from configuration, failing with `ApiError::Build` on
+    /// the same misconfigurations as `ApiClientBuilder::build`
+    pub fn try_new(config: Config) -> Result<Self> {
+        ApiClientBuilder::new().assemble(config, Arc::new(PluginManager::new()))
     }
 
     /// Create client with custom plugin manager
@@ -137,319 +937,1230 @@ impl ApiClient {
     }
 
     /// Get reference to configuration
+    ///
+    /// This is the configuration the client was built with; fields applied by
+    /// `with_config_reload` are reflected by `timeout()` and friends instead.
     pub fn config(&self) -> &Config {
         &self.config
     }
 
-    /// Execute HTTP GET request with plugin support
-    pub async fn get(&self, url: &str) -> Result<String> {
-        let _ = UrlValidator::validate(url)?;
-        // Pre-request plugin hook
-        self.plugin_manager.execute_pre_request(url).await?;
-
-        let mut request_builder = Request::builder()
-            .method(Method::GET)
-            .uri(url)
-            .header("user-agent", &self.user_agent);
-
-        // Merge headers with custom overriding defaults
-        let mut final_headers = self.config.client.default_headers.clone();
-        final_headers.extend(
-            self.custom_headers
+    /// Current per-request timeout
+    pub fn timeout(&self) -> Duration {
+        self.live_settings().timeout
+    }
+
+    /// Current maximum number of retries
+    pub fn max_retries(&self) -> usize {
+        self.live_settings().max_retries
+    }
+
+    /// Current default headers (before custom headers are applied)
+    pub fn default_headers(&self) -> HashMap<String, String> {
+        self.live_settings().default_headers
+    }
+
+    /// Cookies stored so far, when built with `ApiClientBuilder::with_cookie_jar`
+    pub fn cookie_jar(&self) -> Option<&CookieJar> {
+        self.cookies.as_ref()
+    }
+
+    /// Snapshot of the features and settings this client is running with
+    pub fn capabilities(&self) -> Capabilities {
+        let live = self.live_settings();
+        #[cfg(feature = "config-watch")]
+        let config_reload = self.config_watcher.is_some();
+        #[cfg(not(feature = "config-watch"))]
+        let config_reload = false;
+
+        Capabilities {
+            tls_backend: tls_backend().to_string(),
+            http_versions: vec!["http/1.1".to_string()],
+            compression: self
+                .accept_encodings
                 .iter()
-                .map(|(k, v)| (k.clone(), v.clone())),
-        );
+                .map(|e| e.to_string())
+                .collect(),
+            follows_redirects: live.max_redirects > 0,
+            cookies: self.cookies.is_some(),
+            timeout_ms: live.timeout.as_millis() as u64,
+            max_retries: live.max_retries,
+            retry_delay_ms: live.retry_delay.as_millis() as u64,
+            max_concurrent_requests: self.dispatch.limit(),
+            max_request_size: self.config.client.max_request_size,
+            max_response_size: self.config.client.max_response_size,
+            keepalive_interval_secs: self.config.streaming.keepalive_interval,
+            json_cache_capacity: self.json_cache.as_ref().map(JsonCache::capacity),
+            host_header: self.host_header.clone(),
+            config_reload,
+            content_types: self.serializers.content_types(),
+            plugins: self
+                .plugin_manager
+                .plugins
+                .iter()
+                .map(|plugin| plugin.name().to_string())
+                .collect(),
+        }
+    }
 
-        // Apply merged headers to request
-        for (key, value) in &final_headers {
-            HeaderValidator::validate_header(key, value)?;
-            request_builder = request_builder.header(key, value);
+    /// Codecs used by the typed methods
+    pub fn serializers(&self) -> &SerializerRegistry {
+        &self.serializers
+    }
+
+    /// Body bytes sent and received and requests dispatched since the client was created
+    pub fn traffic_stats(&self) -> TrafficStats {
+        self.traffic.snapshot()
+    }
+
+    /// Snapshot of the live settings
+    fn live_settings(&self) -> LiveSettings {
+        self.live.read().unwrap().clone()
+    }
+
+    /// Reload safe-to-change settings whenever the config file changes.
+    ///
+    /// Default headers, timeout, retry and redirect limits are applied to the live
+    /// client; changes to other fields (storage paths, TLS, plugins) are logged
+    /// and ignored until restart. Unparseable files keep the current settings.
+    #[cfg(feature = "config-watch")]
+    pub fn with_config_reload(mut self, path: impl Into<std::path::PathBuf>) -> Result<Self> {
+        let watcher = reload::watch_config(path.into(), self.config.clone(), self.live.clone())?;
+        self.config_watcher = Some(watcher);
+        Ok(self)
+    }
+
+    /// Drop all pooled connections so the next request connects afresh.
+    ///
+    /// Useful after network changes (VPN up/down) that leave stale pooled
+    /// connections. Configuration is preserved, and in-flight requests keep
+    /// using the pool they started on until they complete.
+    pub fn reset_connections(&self) {
+        let fresh = pooled_client(self.connector.clone());
+        *self.client.write().unwrap() = fresh;
+    }
+
+    /// Current connection pool (cheap handle clone)
+    fn http_client(&self) -> HttpClient {
+        self.client.read().unwrap().clone()
+    }
+
+    /// Subscribe to request lifecycle events.
+    ///
+    /// Events are delivered without blocking requests; if the receiver falls
+    /// behind and its channel fills up, further events are dropped for it.
+    pub fn subscribe(&self) -> mpsc::Receiver<ClientEvent> {
+        self.events.subscribe(EVENT_CHANNEL_CAPACITY)
+    }
+
+    /// Like `subscribe`, plus `ClientEvent::Connection` timings for the DNS,
+    /// TCP and TLS phases of every new connection.
+    pub fn subscribe_with_connections(&self) -> mpsc::Receiver<ClientEvent> {
+        self.events
+            .subscribe_with_connections(EVENT_CHANNEL_CAPACITY)
+    }
+
+    /// Headers every request with `method` would carry, before per-request options.
+    ///
+    /// Later sources override earlier ones: the client user agent,
+    /// `accept-encoding` from `client.accept_encodings` (unless empty),
+    /// `client.default_headers` from the config, headers added with
+    /// `ApiClientBuilder::with_header`, then the `content-type` the verb methods
+    /// send a JSON body with (POST, PUT, PATCH), since a body's own type always wins.
+    /// Names are compared case-insensitively and returned lowercased, so each
+    /// header appears once. The Host override is not included.
+    pub fn effective_headers(&self, method: &Method) -> HashMap<String, String> {
+        let content_type = matches!(*method, Method::POST | Method::PUT | Method::PATCH)
+            .then_some(DEFAULT_CONTENT_TYPE);
+        self.merge_headers(&self.live_settings(), content_type)
+    }
+
+    /// Shared by `effective_headers` and the send path so both agree on precedence
+    fn merge_headers(
+        &self,
+        live: &LiveSettings,
+        content_type: Option<&str>,
+    ) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert("user-agent".to_string(), self.user_agent.clone());
+        if !self.accept_encodings.is_empty() {
+            headers.insert(
+                "accept-encoding".to_string(),
+                self.accept_encodings.join(", "),
+            );
         }
+        let lowercased = |headers: &HashMap<String, String>| {
+            headers
+                .iter()
+                .map(|(name, value)| (name.to_ascii_lowercase(), value.clone()))
+                .collect::<Vec<_>>()
+        };
+        headers.extend(lowercased(&live.default_headers));
+        headers.extend(lowercased(&self.custom_headers));
+        // The call's own body type outranks any configured default
+        if let Some(content_type) = content_type {
+            headers.insert("content-type".to_string(), content_type.to_string());
+        }
+        headers
+    }
 
-        let request = request_builder
-            .body(
-                Empty::<Bytes>::new()
-                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
-                    .boxed(),
-            )
-            .map_err(|e| ApiError::other(format!("Failed to build request: {}", e)))?;
+    /// Cancel every request currently queued or in flight.
+    ///
+    /// Affected requests resolve to `ApiError::Cancelled`; requests started
+    /// after this call are unaffected.
+    pub fn abort_all(&self) {
+        self.aborts.send_modify(|generation| *generation += 1);
+    }
+
+    /// Cancel outstanding requests and give plugins a chance to flush via `Plugin::finalize`.
+    ///
+    /// Dropping the client does not finalize plugins; call this before letting it go.
+    /// Every plugin is finalized even if one fails, and the first failure is returned.
+    pub async fn shutdown(&self) -> Result<()> {
+        self.abort_all();
+        self.plugin_manager.finalize_all().await
+    }
+
+    /// Execute HTTP GET request with plugin support
+    pub async fn get(&self, url: &str) -> Result<String> {
+        Ok(self.get_response(url).await?.body)
+    }
+
+    /// Execute HTTP GET request with `query` pairs percent-encoded onto the URL.
+    ///
+    /// Pairs go after any query the URL already has; repeated keys are all sent.
+    pub async fn get_with_query(&self, url: &str, query: &[(&str, &str)]) -> Result<String> {
+        self.get(&append_query(url, query)?).await
+    }
 
-        let response = timeout(self.timeout_duration, self.client.request(request))
+    /// Execute HTTP GET request, keeping the status and headers along with the body
+    pub async fn get_response(&self, url: &str) -> Result<Response> {
+        self.send_response(Method::GET, url, None, empty_body())
             .await
-            .map_err(|_| ApiError::Timeout)?
-            .map_err(|e| ApiError::other(format!("Client error: {}", e)))?;
+    }
 
-        let status = response.status();
-        let status_code = status.as_u16();
+    /// Execute HTTP GET request with per-request options (e.g. plugin metadata)
+    pub async fn get_with(&self, url: &str, options: &RequestOptions) -> Result<String> {
+        let request = Outgoing::new(Method::GET, url, None, empty_body());
+        self.send_outgoing(request.options(options)).await
+    }
 
-        // Post-request plugin hook
-        self.plugin_manager
-            .execute_post_request(url, status_code)
+    /// GET several URLs concurrently, collecting each outcome keyed by its URL
+    pub async fn get_many(&self, urls: &[&str]) -> BatchResult<String> {
+        self.batch(urls, |url| self.get(url)).await
+    }
+
+    /// Send any method, with an optional JSON body, returning the response body
+    /// as `options.expect` asks: text, raw bytes or parsed JSON.
+    ///
+    /// Non-2xx responses are errors as with `get`.
+    pub async fn request(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&serde_json::Value>,
+        options: &RequestOptions,
+    ) -> Result<ResponseContent> {
+        let request = match body {
+            Some(data) => {
+                let json_body = serde_json::to_string(data)?;
+                Outgoing::new(method, url, Some("application/json"), full_body(json_body))
+            }
+            None => Outgoing::new(method, url, None, empty_body()),
+        };
+        let Received { status, body, .. } = self.receive(request.options(options)).await?;
+        let text = |body: Bytes| {
+            String::from_utf8(body.to_vec())
+                .map_err(|e| self.failed(ApiError::other(format!("Invalid UTF-8: {}", e))))
+        };
+        match options.expect {
+            BodyExpectation::Utf8 => text(body).map(ResponseContent::Text),
+            BodyExpectation::Bytes => Ok(ResponseContent::Bytes(body)),
+            BodyExpectation::Json => {
+                let value = Self::parse_json_body(status, &text(body)?)?;
+                Ok(ResponseContent::Json(
+                    value.unwrap_or(serde_json::Value::Null),
+                ))
+            }
+        }
+    }
+
+    /// Execute HTTP GET request, queued by `priority` when the concurrency cap is reached
+    pub async fn get_with_priority(&self, url: &str, priority: Priority) -> Result<String> {
+        self.send_outgoing(Outgoing::new(Method::GET, url, None, empty_body()).priority(priority))
+            .await
+    }
+
+    /// GET and stream the body chunk by chunk, without buffering it.
+    ///
+    /// Waiting longer than `streaming.stream_timeout` for a chunk ends the stream
+    /// with a read timeout. Like `get_stream_map`, the request keeps its dispatch
+    /// slot until the stream is dropped.
+    pub async fn get_stream(&self, url: &str) -> Result<BoxStream<'static, Result<Bytes>>> {
+        let OpenResponse {
+            response, permit, ..
+        } = self
+            .open(Outgoing::new(Method::GET, url, None, empty_body()))
             .await?;
 
-        if !status.is_success() {
-            let error = ApiError::HttpStatus { status };
-            self.plugin_manager.execute_error(&error).await?;
-            return Err(error);
+        let chunks = response
+            .into_body()
+            .into_data_stream()
+            .map_err(BodyError::into_api_error);
+        let stream = with_read_timeout(chunks, self.config.stream_timeout()).inspect(move |_| {
+            let _slot = &permit;
+        });
+        Ok(stream.boxed())
+    }
+
+    /// GET and stream the body through an async transform, chunk by chunk.
+    ///
+    /// Errors from the transform end the stream. The request keeps its dispatch
+    /// slot until the stream is dropped.
+    pub async fn get_stream_map<F, Fut>(
+        &self,
+        url: &str,
+        f: F,
+    ) -> Result<BoxStream<'static, Result<Bytes>>>
+    where
+        F: FnMut(Bytes) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Bytes>> + Send + 'static,
+    {
+        let OpenResponse {
+            response, permit, ..
+        } = self
+            .open(Outgoing::new(Method::GET, url, None, empty_body()))
+            .await?;
+
+        let stream = response
+            .into_body()
+            .into_data_stream()
+            .map_err(BodyError::into_api_error)
+            .and_then(f)
+            // Moving the permit into the stream holds the dispatch slot until it is dropped
+            .inspect(move |_| {
+                let _slot = &permit;
+            });
+        Ok(stream.boxed())
+    }
+
+    /// GET a multipart (e.g. `multipart/x-mixed-replace`) response and stream each part as it completes.
+    ///
+    /// The boundary is taken from the response `Content-Type`. Like `get_stream_map`,
+    /// the request keeps its dispatch slot until the stream is dropped.
+    pub async fn get_multipart(&self, url: &str) -> Result<BoxStream<'static, Result<Part>>> {
+        let OpenResponse {
+            response, permit, ..
+        } = self
+            .open(Outgoing::new(Method::GET, url, None, empty_body()))
+            .await?;
+
+        let content_type = response
+            .headers()
+            .get(hyper::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let boundary = boundary_from_content_type(content_type).ok_or_else(|| {
+            ApiError::stream(format!(
+                "Expected a multipart response with a boundary, got content type '{}'",
+                content_type
+            ))
+        })?;
+
+        let body = response.into_body().into_data_stream();
+        let stream = MultipartStream::new(body, &boundary).inspect(move |_| {
+            let _slot = &permit;
+        });
+        Ok(stream.boxed())
+    }
+
+    /// GET a newline-delimited body (NDJSON, logs) and stream it line by line.
+    ///
+    /// Lines come without their `\n` or `\r\n`. Like `get_stream_map`, the request
+    /// keeps its dispatch slot until the stream is dropped.
+    pub async fn get_lines(&self, url: &str) -> Result<BoxStream<'static, Result<Bytes>>> {
+        let OpenResponse {
+            response, permit, ..
+        } = self
+            .open(Outgoing::new(Method::GET, url, None, empty_body()))
+            .await?;
+
+        let body = response.into_body().into_data_stream();
+        let stream = LineStream::new(body).inspect(move |_| {
+            let _slot = &permit;
+        });
+        Ok(stream.boxed())
+    }
+
+    /// GET a newline-delimited JSON body and stream it as one `T` per line.
+    ///
+    /// Blank lines are skipped and a line that fails to deserialize yields
+    /// `ApiError::Serialization` without ending the stream. Waiting longer than
+    /// `streaming.stream_timeout` for a chunk ends the stream with a read timeout.
+    /// Like `get_lines`, the request keeps its dispatch slot until the stream is
+    /// dropped.
+    pub async fn get_ndjson<T>(&self, url: &str) -> Result<BoxStream<'static, Result<T>>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let OpenResponse {
+            response, permit, ..
+        } = self
+            .open(Outgoing::new(Method::GET, url, None, empty_body()))
+            .await?;
+
+        let chunks = response
+            .into_body()
+            .into_data_stream()
+            .map_err(BodyError::into_api_error);
+        let body = with_read_timeout(chunks, self.config.stream_timeout()).boxed();
+        let stream = NdjsonStream::new(body).inspect(move |_| {
+            let _slot = &permit;
+        });
+        Ok(stream.boxed())
+    }
+
+    /// GET and deserialize the body into `S` on 2xx, or into `E` with the status otherwise
+    pub async fn get_either<S: DeserializeOwned, E: DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<std::result::Result<S, (u16, E)>> {
+        let Response { status, body, .. } = self
+            .exchange(Outgoing::new(Method::GET, url, None, empty_body()).any_status())
+            .await?;
+        if status.is_success() {
+            Ok(Ok(serde_json::from_str(&body)?))
+        } else {
+            Ok(Err((status.as_u16(), serde_json::from_str(&body)?)))
         }
+    }
 
-        let body_bytes = http_body_util::BodyExt::collect(response.into_body())
+    /// GET returning the status and body of any HTTP response.
+    ///
+    /// Non-2xx statuses are returned as data rather than `ApiError::HttpStatus`
+    /// and do not reach the plugin error hook; connection failures and
+    /// timeouts are still errors.
+    pub async fn get_raw(&self, url: &str) -> Result<(u16, String)> {
+        self.send_raw(Outgoing::new(Method::GET, url, None, empty_body()))
             .await
-            .map_err(|e| ApiError::other(format!("Failed to read response body: {}", e)))?
-            .to_bytes();
+    }
+
+    /// DELETE returning the status and body of any HTTP response (see `get_raw`)
+    pub async fn delete_raw(&self, url: &str) -> Result<(u16, String)> {
+        self.send_raw(Outgoing::new(Method::DELETE, url, None, empty_body()))
+            .await
+    }
+
+    /// POST JSON, returning the status and body of any HTTP response (see `get_raw`)
+    pub async fn post_json_raw(
+        &self,
+        url: &str,
+        data: &serde_json::Value,
+    ) -> Result<(u16, String)> {
+        self.send_json_raw(Method::POST, url, data).await
+    }
 
-        String::from_utf8(body_bytes.to_vec())
-            .map_err(|e| ApiError::other(format!("Invalid UTF-8: {}", e)))
+    /// PUT JSON, returning the status and body of any HTTP response (see `get_raw`)
+    pub async fn put_json_raw(&self, url: &str, data: &serde_json::Value) -> Result<(u16, String)> {
+        self.send_json_raw(Method::PUT, url, data).await
+    }
+
+    /// PATCH JSON, returning the status and body of any HTTP response (see `get_raw`)
+    pub async fn patch_json_raw(
+        &self,
+        url: &str,
+        data: &serde_json::Value,
+    ) -> Result<(u16, String)> {
+        self.send_json_raw(Method::PATCH, url, data).await
+    }
+
+    async fn send_json_raw(
+        &self,
+        method: Method,
+        url: &str,
+        data: &serde_json::Value,
+    ) -> Result<(u16, String)> {
+        let json_body = serde_json::to_string(data)?;
+        self.send_raw(Outgoing::new(
+            method,
+            url,
+            Some("application/json"),
+            full_body(json_body),
+        ))
+        .await
     }
 
     /// Execute HTTP POST request with JSON data and plugin support
     pub async fn post_json(&self, url: &str, data: &serde_json::Value) -> Result<String> {
-        let _ = UrlValidator::validate(url)?;
-        // Pre-request plugin hook
-        self.plugin_manager.execute_pre_request(url).await?;
+        Ok(self.post_json_response(url, data).await?.body)
+    }
 
+    /// Send a POST request with JSON data, keeping the status and headers along with the body
+    pub async fn post_json_response(
+        &self,
+        url: &str,
+        data: &serde_json::Value,
+    ) -> Result<Response> {
         let json_body = serde_json::to_string(data)?;
+        self.send_response(
+            Method::POST,
+            url,
+            Some("application/json"),
+            full_body(json_body),
+        )
+        .await
+    }
 
-        let mut request_builder = Request::builder()
-            .method(Method::POST)
-            .uri(url)
-            .header("content-type", "application/json")
-            .header("user-agent", &self.user_agent);
+    /// Send `body` as-is in a POST request, labelled with `content_type`
+    pub async fn post_bytes(
+        &self,
+        url: &str,
+        body: impl Into<Bytes>,
+        content_type: &str,
+    ) -> Result<String> {
+        self.send(Method::POST, url, Some(content_type), full_body(body))
+            .await
+    }
 
-        // Merge headers with custom overriding defaults
-        let mut final_headers = self.config.client.default_headers.clone();
-        final_headers.extend(
-            self.custom_headers
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone())),
-        );
+    /// Send `body` as-is with any method, labelled with `content_type`
+    pub async fn request_bytes(
+        &self,
+        method: Method,
+        url: &str,
+        body: impl Into<Bytes>,
+        content_type: &str,
+    ) -> Result<String> {
+        self.send(method, url, Some(content_type), full_body(body))
+            .await
+    }
 
-        // Apply merged headers to request
-        for (key, value) in &final_headers {
-            HeaderValidator::validate_header(key, value)?;
-            request_builder = request_builder.header(key, value);
+    /// Send `calls` as one JSON-RPC 2.0 batch, returning their outcomes in call order.
+    ///
+    /// A call the server answered with an error object is an `Err` in its
+    /// `JsonRpcResponse::result`; transport, HTTP and malformed-reply failures
+    /// (including a missing reply) fail the whole batch. An empty batch sends nothing.
+    pub async fn jsonrpc_batch(
+        &self,
+        url: &str,
+        calls: &[(&str, serde_json::Value)],
+    ) -> Result<Vec<JsonRpcResponse>> {
+        if calls.is_empty() {
+            return Ok(Vec::new());
         }
+        let body = self.post_json(url, &jsonrpc::batch_body(calls)).await?;
+        jsonrpc::correlate(calls, &body)
+    }
 
-        let request = request_builder
-            .body(
-                Full::new(Bytes::from(json_body))
-                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
-                    .boxed(),
-            )
-            .map_err(|e| ApiError::other(format!("Failed to build request: {}", e)))?;
+    /// Send a POST request with JSON data and per-request options
+    pub async fn post_json_with(
+        &self,
+        url: &str,
+        data: &serde_json::Value,
+        options: &RequestOptions,
+    ) -> Result<String> {
+        let json_body = serde_json::to_string(data)?;
+        let request = Outgoing::new(
+            Method::POST,
+            url,
+            Some("application/json"),
+            full_body(json_body),
+        );
+        self.send_outgoing(request.options(options)).await
+    }
 
-        let response = timeout(self.timeout_duration, self.client.request(request))
+    /// POST fields as an `application/x-www-form-urlencoded` body
+    pub async fn post_form(&self, url: &str, fields: &[(&str, &str)]) -> Result<String> {
+        let form_body = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(fields)
+            .finish();
+        self.send(
+            Method::POST,
+            url,
+            Some("application/x-www-form-urlencoded"),
+            full_body(form_body),
+        )
+        .await
+    }
+
+    /// POST a `multipart/form-data` body with a generated boundary.
+    ///
+    /// File parts are streamed from disk rather than read into memory.
+    pub async fn post_multipart(&self, url: &str, form: MultipartForm) -> Result<String> {
+        let (content_type, reader, length) = form.into_body().await?;
+        let body = ReaderBody::new(reader, Some(length))
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+            .boxed();
+        self.send_outgoing(Outgoing::new(Method::POST, url, Some(&content_type), body).streamed())
             .await
-            .map_err(|_| ApiError::Timeout)?
-            .map_err(|e| ApiError::other(format!("Client error: {}", e)))?;
+    }
 
-        let status = response.status();
-        let status_code = status.as_u16();
+    /// Send a PUT request with JSON data
+    pub async fn put_json(&self, url: &str, data: &serde_json::Value) -> Result<String> {
+        Ok(self.put_json_response(url, data).await?.body)
+    }
 
-        // Post-request plugin hook
-        self.plugin_manager
-            .execute_post_request(url, status_code)
-            .await?;
+    /// Send a PUT request with JSON data, keeping the status and headers along with the body
+    pub async fn put_json_response(&self, url: &str, data: &serde_json::Value) -> Result<Response> {
+        let json_body = serde_json::to_string(data)?;
+        self.send_response(
+            Method::PUT,
+            url,
+            Some("application/json"),
+            full_body(json_body),
+        )
+        .await
+    }
 
-        if !status.is_success() {
-            let error = ApiError::HttpStatus { status };
-            self.plugin_manager.execute_error(&error).await?;
-            return Err(error);
-        }
+    /// PUT a body streamed from `reader`.
+    ///
+    /// With `length` the request carries `Content-Length` and the reader must
+    /// produce exactly that many bytes; without it the body is sent chunked.
+    /// A read error aborts the request.
+    pub async fn put_reader<R>(
+        &self,
+        url: &str,
+        reader: R,
+        content_type: &str,
+        length: Option<u64>,
+    ) -> Result<String>
+    where
+        R: tokio::io::AsyncRead + Send + Sync + 'static,
+    {
+        let body = ReaderBody::new(reader, length)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+            .boxed();
+        self.send_outgoing(Outgoing::new(Method::PUT, url, Some(content_type), body).streamed())
+            .await
+    }
 
-        let body_bytes = http_body_util::BodyExt::collect(response.into_body())
+    /// POST a chunked body whose chunks arrive on `rx`, finishing when the channel closes.
+    ///
+    /// With `Result<Bytes, E>` items, sending an `Err` aborts the request with that
+    /// error instead of completing a partial upload (see `BodyChunk`).
+    pub async fn post_channel<T: BodyChunk>(
+        &self,
+        url: &str,
+        rx: mpsc::Receiver<T>,
+        content_type: &str,
+    ) -> Result<String> {
+        let body = ChannelBody::new(rx).boxed();
+        self.send_outgoing(Outgoing::new(Method::POST, url, Some(content_type), body).streamed())
             .await
-            .map_err(|e| ApiError::other(format!("Failed to read response body: {}", e)))?
-            .to_bytes();
+    }
 
-        String::from_utf8(body_bytes.to_vec())
-            .map_err(|e| ApiError::other(format!("Invalid UTF-8: {}", e)))
+    /// POST JSON values from `stream` as a chunked NDJSON body (`application/x-ndjson`),
+    /// one value per line, without buffering the whole body.
+    ///
+    /// An `Err` from the stream aborts the request instead of sending a truncated body.
+    pub async fn post_ndjson_stream<S>(&self, url: &str, stream: S) -> Result<String>
+    where
+        S: futures::Stream<Item = Result<serde_json::Value>> + Send + 'static,
+    {
+        let body = streamed_body(stream.map(|item| {
+            item.and_then(|value| {
+                let mut line = serde_json::to_vec(&value)?;
+                line.push(b'\n');
+                Ok(Bytes::from(line))
+            })
+        }));
+        self.send_outgoing(
+            Outgoing::new(Method::POST, url, Some("application/x-ndjson"), body).streamed(),
+        )
+        .await
     }
 
-    /// Send a PUT request with JSON data
-    pub async fn put_json(&self, url: &str, data: &serde_json::Value) -> Result<String> {
-        let _ = UrlValidator::validate(url)?;
-        // Pre-request plugin hook
-        self.plugin_manager.execute_pre_request(url).await?;
+    /// Stream `body` up to `url` and the response back down, without buffering either.
+    ///
+    /// For proxies: any status is returned as data and redirects are passed on
+    /// rather than followed. An `Err` from `body` aborts the request. The request
+    /// keeps its dispatch slot until the response stream is dropped.
+    pub async fn pipe<S>(
+        &self,
+        method: Method,
+        url: &str,
+        body: S,
+    ) -> Result<(
+        u16,
+        HashMap<String, String>,
+        BoxStream<'static, Result<Bytes>>,
+    )>
+    where
+        S: futures::Stream<Item = Result<Bytes>> + Send + 'static,
+    {
+        let request = Outgoing::new(method, url, None, streamed_body(body))
+            .any_status()
+            .pass_redirects();
+        let OpenResponse {
+            response, permit, ..
+        } = self.open(request).await?;
+
+        let status = response.status().as_u16();
+        let headers = canonical_header_map(response.headers());
+        let stream = response
+            .into_body()
+            .into_data_stream()
+            .map_err(BodyError::into_api_error)
+            .inspect(move |_| {
+                let _slot = &permit;
+            });
+        Ok((status, headers, stream.boxed()))
+    }
 
+    /// Send a PUT request with JSON data and per-request options (e.g. `If-Match`)
+    pub async fn put_json_with(
+        &self,
+        url: &str,
+        data: &serde_json::Value,
+        options: &RequestOptions,
+    ) -> Result<String> {
         let json_body = serde_json::to_string(data)?;
+        let request = Outgoing::new(
+            Method::PUT,
+            url,
+            Some("application/json"),
+            full_body(json_body),
+        );
+        self.send_outgoing(request.options(options)).await
+    }
 
-        let mut request_builder = Request::builder()
-            .method(Method::PUT)
-            .uri(url)
-            .header("content-type", "application/json")
-            .header("user-agent", &self.user_agent);
-
-        // Merge headers with custom overriding defaults
-        let mut final_headers = self.config.client.default_headers.clone();
-        final_headers.extend(
-            self.custom_headers
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone())),
+    /// Send a PATCH request with JSON data and per-request options (e.g. `If-Match`)
+    pub async fn patch_json_with(
+        &self,
+        url: &str,
+        data: &serde_json::Value,
+        options: &RequestOptions,
+    ) -> Result<String> {
+        let json_body = serde_json::to_string(data)?;
+        let request = Outgoing::new(
+            Method::PATCH,
+            url,
+            Some("application/json"),
+            full_body(json_body),
         );
+        self.send_outgoing(request.options(options)).await
+    }
 
-        // Apply merged headers to request
-        for (key, value) in &final_headers {
-            HeaderValidator::validate_header(key, value)?;
-            request_builder = request_builder.header(key, value);
-        }
+    /// Send a DELETE request with per-request options (e.g. `If-Match`)
+    pub async fn delete_with(&self, url: &str, options: &RequestOptions) -> Result<String> {
+        let request = Outgoing::new(Method::DELETE, url, None, empty_body());
+        self.send_outgoing(request.options(options)).await
+    }
 
-        let request = request_builder
-            .body(
-                Full::new(Bytes::from(json_body))
-                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
-                    .boxed(),
-            )
-            .map_err(|e| ApiError::other(format!("Failed to build request: {}", e)))?;
+    /// Send a DELETE request
+    pub async fn delete(&self, url: &str) -> Result<String> {
+        Ok(self.delete_response(url).await?.body)
+    }
 
-        let response = timeout(self.timeout_duration, self.client.request(request))
+    /// Send a DELETE request, keeping the status and headers along with the body
+    pub async fn delete_response(&self, url: &str) -> Result<Response> {
+        self.send_response(Method::DELETE, url, None, empty_body())
             .await
-            .map_err(|_| ApiError::Timeout)?
-            .map_err(|e| ApiError::other(format!("Client error: {}", e)))?;
+    }
 
-        let status = response.status();
-        let status_code = status.as_u16();
+    /// DELETE several URLs concurrently, collecting each outcome keyed by its URL
+    pub async fn delete_many(&self, urls: &[&str]) -> BatchResult<String> {
+        self.batch(urls, |url| self.delete(url)).await
+    }
 
-        // Post-request plugin hook
-        self.plugin_manager
-            .execute_post_request(url, status_code)
-            .await?;
+    /// Run `call` for every URL concurrently (within the concurrency limit)
+    async fn batch<'a, T, F, Fut>(&'a self, urls: &[&'a str], call: F) -> BatchResult<T>
+    where
+        F: Fn(&'a str) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let results = futures::future::join_all(urls.iter().map(|url| call(url))).await;
+        BatchResult::new(
+            urls.iter()
+                .map(|url| url.to_string())
+                .zip(results)
+                .collect(),
+        )
+    }
 
-        if !status.is_success() {
-            return Err(ApiError::HttpStatus { status });
+    /// Send a PATCH request with JSON data
+    pub async fn patch_json(&self, url: &str, data: &serde_json::Value) -> Result<String> {
+        Ok(self.patch_json_response(url, data).await?.body)
+    }
+
+    /// Send a PATCH request with JSON data, keeping the status and headers along with the body
+    pub async fn patch_json_response(
+        &self,
+        url: &str,
+        data: &serde_json::Value,
+    ) -> Result<Response> {
+        let json_body = serde_json::to_string(data)?;
+        self.send_response(
+            Method::PATCH,
+            url,
+            Some("application/json"),
+            full_body(json_body),
+        )
+        .await
+    }
+
+    /// Enforce `max_request_size`: sized bodies are rejected up front, streaming
+    /// bodies fail once they produce more than the limit
+    fn limit_request_body(&self, body: RequestBody) -> Result<RequestBody> {
+        let limit = self.config.client.max_request_size;
+        if let Some(len) = body.size_hint().exact() {
+            if len > limit {
+                return Err(ApiError::other(format!(
+                    "Request body of {} bytes exceeds max_request_size {}",
+                    len, limit
+                )));
+            }
         }
+        Ok(Limited::new(body, limit as usize).boxed())
+    }
 
-        let body_bytes = http_body_util::BodyExt::collect(response.into_body())
+    /// Shared request path for all verbs at normal priority
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        content_type: Option<&str>,
+        body: RequestBody,
+    ) -> Result<String> {
+        Ok(self
+            .send_response(method, url, content_type, body)
+            .await?
+            .body)
+    }
+
+    /// Shared request path for all verbs at normal priority, keeping status and headers
+    async fn send_response(
+        &self,
+        method: Method,
+        url: &str,
+        content_type: Option<&str>,
+        body: RequestBody,
+    ) -> Result<Response> {
+        self.exchange(Outgoing::new(method, url, content_type, body))
             .await
-            .map_err(|e| ApiError::other(format!("Failed to read response body: {}", e)))?
-            .to_bytes();
+    }
 
-        String::from_utf8(body_bytes.to_vec())
-            .map_err(|e| ApiError::other(format!("Invalid UTF-8: {}", e)))
+    /// Send and return the body text
+    async fn send_outgoing(&self, request: Outgoing<'_>) -> Result<String> {
+        Ok(self.exchange(request).await?.body)
     }
 
-    /// Send a DELETE request
-    pub async fn delete(&self, url: &str) -> Result<String> {
-        let _ = UrlValidator::validate(url)?;
-        // Pre-request plugin hook
-        self.plugin_manager.execute_pre_request(url).await?;
-
-        let mut request_builder = Request::builder()
-            .method(Method::DELETE)
-            .uri(url)
-            .header("user-agent", &self.user_agent);
-
-        // Merge headers with custom overriding defaults
-        let mut final_headers = self.config.client.default_headers.clone();
-        final_headers.extend(
-            self.custom_headers
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone())),
-        );
+    /// Send and return the status and headers with the body as UTF-8 text
+    async fn exchange(&self, request: Outgoing<'_>) -> Result<Response> {
+        let Received {
+            status,
+            headers,
+            body,
+        } = self.receive(request).await?;
+        match String::from_utf8(body.to_vec()) {
+            Ok(text) => Ok(Response {
+                status,
+                headers: canonical_header_map(&headers),
+                body: text,
+            }),
+            Err(e) => Err(self.failed(ApiError::other(format!("Invalid UTF-8: {}", e)))),
+        }
+    }
 
-        // Apply merged headers to request
-        for (key, value) in &final_headers {
-            HeaderValidator::validate_header(key, value)?;
-            request_builder = request_builder.header(key, value);
+    /// Send without turning the status into an error; return it with the body text
+    async fn send_raw(&self, request: Outgoing<'_>) -> Result<(u16, String)> {
+        let Response { status, body, .. } = self.exchange(request.any_status()).await?;
+        Ok((status.as_u16(), body))
+    }
+
+    /// Wait for a dispatch slot, then send and read the whole body while broadcasting lifecycle events
+    async fn receive(&self, request: Outgoing<'_>) -> Result<Received> {
+        let metadata = request.options.metadata.clone();
+        let OpenResponse {
+            response,
+            permit: _permit,
+            mut aborted,
+        } = self.open(request).await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+
+        let result = tokio::select! {
+            result = read_body(response, self.config.client.max_response_size) => result,
+            Ok(()) = aborted.changed() => Err(ApiError::Cancelled),
+        };
+        let result = match (result, &self.body_codec) {
+            (Ok(body), Some(codec)) if !body.is_empty() => codec.decode(body),
+            (result, _) => result,
+        };
+        let result = match result {
+            Ok(body) => self
+                .plugin_manager
+                .execute_post_response_with(
+                    &String::from_utf8_lossy(&body),
+                    plugin_context(PluginHook::PostResponse, &metadata),
+                )
+                .await
+                .map(|()| body),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(body) => Ok(Received {
+                status,
+                headers,
+                body,
+            }),
+            Err(e) => Err(self.failed(e)),
+        }
+    }
+
+    /// Report a request that failed after it was sent
+    fn failed(&self, error: ApiError) -> ApiError {
+        self.events.emit(ClientEvent::RequestFailed {
+            error_code: error.code(),
+        });
+        error
+    }
+
+    /// Wait for a dispatch slot and send, returning the response with its body unread
+    async fn open(&self, request: Outgoing<'_>) -> Result<OpenResponse> {
+        // Subscribing marks the current generation seen, so only later aborts apply
+        let mut aborted = self.aborts.subscribe();
+        let permit = tokio::select! {
+            permit = self.dispatch.acquire(request.priority) => permit,
+            Ok(()) = aborted.changed() => return Err(ApiError::Cancelled),
+        };
+        self.events.emit(ClientEvent::RequestStarted {
+            method: request.method.to_string(),
+            url: request.url.to_string(),
+        });
+
+        let result = tokio::select! {
+            result = self.send_following(request) => result,
+            Ok(()) = aborted.changed() => Err(ApiError::Cancelled),
+        };
+        match result {
+            Ok(response) => Ok(OpenResponse {
+                response,
+                permit,
+                aborted,
+            }),
+            Err(e) => {
+                self.events.emit(ClientEvent::RequestFailed {
+                    error_code: e.code(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Send, following redirects up to `max_redirects` unless the caller follows them itself.
+    ///
+    /// 303, and 301/302 after a POST, continue as a GET without the body; other
+    /// redirects repeat the method and body, which only works for bodies of up to
+    /// `MAX_REPLAY_BODY` bytes that were not streamed. Credentials are not sent on
+    /// to another origin.
+    async fn send_following(&self, request: Outgoing<'_>) -> Result<hyper::Response<ResponseBody>> {
+        let max_redirects = self.live_settings().max_redirects;
+        if request.pass_redirects || max_redirects == 0 {
+            return self.send_inner(request).await;
+        }
+        let Outgoing {
+            mut method,
+            url,
+            mut content_type,
+            mut body,
+            priority,
+            mut options,
+            error_for_status,
+            mut streamed,
+            hops,
+            ..
+        } = request;
+        let mut current = url.to_string();
+        let mut redirects = 0;
+
+        loop {
+            // Keep a copy of a small body in case a 307/308 asks for it again
+            let has_body = !body.is_end_stream();
+            let (sent, recording) = if has_body && !streamed {
+                let (recorded, recording) = RecordedBody::new(body, MAX_REPLAY_BODY);
+                (recorded.boxed(), Some(recording))
+            } else {
+                (body, None)
+            };
+            let mut hop = Outgoing::new(method.clone(), &current, content_type, sent)
+                .priority(priority)
+                .options(&options)
+                .pass_redirects();
+            hop.error_for_status = error_for_status;
+            let response = self.send_inner(hop).await?;
+
+            let status = response.status();
+            let Some(location) = redirect_location(status, response.headers()) else {
+                return Ok(response);
+            };
+            if redirects >= max_redirects {
+                return Err(too_many_redirects(url, max_redirects));
+            }
+            if let Some(hops) = hops {
+                hops.take()?;
+            }
+            let location = location.to_str().map_err(|_| ApiError::InvalidResponse)?;
+            let previous = url::Url::parse(&current)?;
+            let next = previous.join(location)?;
+            UrlValidator::validate(next.as_str())?;
+
+            let as_get = (status == StatusCode::SEE_OTHER && method != Method::HEAD)
+                || (matches!(status.as_u16(), 301 | 302) && method == Method::POST);
+            if as_get {
+                method = Method::GET;
+                content_type = None;
+                body = empty_body();
+            } else if has_body {
+                let replay = recording.and_then(BodyRecording::into_bytes);
+                body = full_body(replay.ok_or_else(|| {
+                    ApiError::other(format!(
+                        "Request body not replayable for the {} redirect to {}: \
+                         streamed bodies and bodies over {} bytes are not kept",
+                        status.as_u16(),
+                        next,
+                        MAX_REPLAY_BODY
+                    ))
+                })?);
+                streamed = false;
+            } else {
+                body = empty_body();
+            }
+            if next.origin() != previous.origin() {
+                options = options
+                    .remove_header("authorization")
+                    .remove_header("cookie")
+                    .remove_header("host");
+            }
+            current = next.into();
+            redirects += 1;
         }
+    }
 
-        let request = request_builder
-            .body(
-                Empty::<Bytes>::new()
-                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
-                    .boxed(),
-            )
-            .map_err(|e| ApiError::other(format!("Failed to build request: {}", e)))?;
-
-        let response = timeout(self.timeout_duration, self.client.request(request))
-            .await
-            .map_err(|_| ApiError::Timeout)?
-            .map_err(|e| ApiError::other(format!("Client error: {}", e)))?;
-
-        let status = response.status();
-        let status_code = status.as_u16();
-
-        // Post-request plugin hook
+    async fn send_inner(&self, request: Outgoing<'_>) -> Result<hyper::Response<ResponseBody>> {
+        let Outgoing {
+            method,
+            url,
+            content_type,
+            body,
+            options,
+            error_for_status,
+            pass_redirects,
+            ..
+        } = request;
+        let _ = UrlValidator::validate(url)?;
+        let body = self.limit_request_body(body)?;
+        let body = match &self.body_codec {
+            Some(codec) if !body.is_end_stream() => {
+                let raw = body
+                    .collect()
+                    .await
+                    .map_err(|e| ApiError::other(format!("Failed to read request body: {}", e)))?
+                    .to_bytes();
+                full_body(codec.encode(raw)?)
+            }
+            _ => body,
+        };
+        let body = self.traffic.count_sent(body);
+        // Pre-request plugin hook, then headers plugins add to the request
+        let context = plugin_context(PluginHook::PreRequest, &options.metadata);
         self.plugin_manager
-            .execute_post_request(url, status_code)
+            .execute_pre_request_with(url, context.clone())
             .await?;
-
-        if !status.is_success() {
-            return Err(ApiError::HttpStatus { status });
+        let plugin_headers = self
+            .plugin_manager
+            .collect_request_headers_with(url, context)
+            .await?;
+        // Later hooks of this request see what plugins added
+        let mut metadata = options.metadata.clone();
+        if !plugin_headers.is_empty() {
+            let added: serde_json::Map<_, _> = plugin_headers
+                .iter()
+                .map(|(name, value)| (name.to_ascii_lowercase(), value.clone().into()))
+                .collect();
+            metadata.insert(PLUGIN_HEADERS_KEY.to_string(), added.into());
         }
 
-        let body_bytes = http_body_util::BodyExt::collect(response.into_body())
-            .await
-            .map_err(|e| ApiError::other(format!("Failed to read response body: {}", e)))?
-            .to_bytes();
-
-        String::from_utf8(body_bytes.to_vec())
-            .map_err(|e| ApiError::other(format!("Invalid UTF-8: {}", e)))
-    }
-
-    /// Send a PATCH request with JSON data
-    pub async fn patch_json(&self, url: &str, data: &serde_json::Value) -> Result<String> {
-        let _ = UrlValidator::validate(url)?;
-        // Pre-request plugin hook
-        self.plugin_manager.execute_pre_request(url).await?;
+        let method_name = method.to_string();
+        let mut request_builder = Request::builder().method(method).uri(url);
 
-        let json_body = serde_json::to_string(data)?;
+        let live = self.live_settings();
+        let mut headers = self.merge_headers(&live, content_type);
+        if let Some(user_agent) = &options.user_agent {
+            headers.insert("user-agent".to_string(), user_agent.clone());
+        }
+        for (name, value) in options.headers() {
+            headers.insert(name.to_string(), value);
+        }
+        for (name, value) in plugin_headers {
+            headers.insert(name.to_ascii_lowercase(), value);
+        }
+        // Range offsets count bytes of the encoded body, but decoded bytes are what
+        // reach the caller; only an unencoded body keeps the two in step
+        if headers.contains_key("range") {
+            headers.insert("accept-encoding".to_string(), "identity".to_string());
+        }
+        let removed = |name: &str| {
+            options
+                .remove_headers
+                .iter()
+                .any(|removed| removed.eq_ignore_ascii_case(name))
+        };
+        headers.retain(|name, _| !removed(name));
+        for (key, value) in headers {
+            HeaderValidator::validate_header(&key, &value)?;
+            request_builder = request_builder.header(key, value);
+        }
 
-        let mut request_builder = Request::builder()
-            .method(Method::PATCH)
-            .uri(url)
-            .header("content-type", "application/json")
-            .header("user-agent", &self.user_agent);
+        // Explicit Cookie headers win over the jar
+        if let Some(jar) = &self.cookies {
+            let explicit = request_builder
+                .headers_ref()
+                .is_some_and(|headers| headers.contains_key(hyper::header::COOKIE));
+            if let Some(cookies) = jar.cookie_header(url).filter(|_| !explicit) {
+                request_builder = request_builder.header(hyper::header::COOKIE, cookies);
+            }
+        }
 
-        // Merge headers with custom overriding defaults
-        let mut final_headers = self.config.client.default_headers.clone();
-        final_headers.extend(
-            self.custom_headers
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone())),
-        );
+        // hyper only fills in Host from the URL when it is not already set; a
+        // removed `host` (e.g. after a cross-origin redirect) drops the override
+        if let Some(host) = self.host_header.as_ref().filter(|_| !removed("host")) {
+            request_builder = request_builder.header(hyper::header::HOST, host);
+        }
+        // Tunnels authenticate on CONNECT; plain HTTP requests carry it themselves
+        if let Some(auth) = proxy_authorization(self.proxy.as_deref(), url) {
+            request_builder = request_builder.header(hyper::header::PROXY_AUTHORIZATION, auth);
+        }
 
-        // Apply merged headers to request
-        for (key, value) in &final_headers {
-            HeaderValidator::validate_header(key, value)?;
-            request_builder = request_builder.header(key, value);
+        // Frame non-empty bodies explicitly unless the caller already chose
+        let framed = request_builder.headers_ref().is_some_and(|headers| {
+            headers.contains_key(hyper::header::CONTENT_LENGTH)
+                || headers.contains_key(hyper::header::TRANSFER_ENCODING)
+        });
+        if !framed && !body.is_end_stream() {
+            request_builder = match body.size_hint().exact() {
+                Some(len) if !self.chunked_uploads => {
+                    request_builder.header(hyper::header::CONTENT_LENGTH, len)
+                }
+                _ => request_builder.header(hyper::header::TRANSFER_ENCODING, "chunked"),
+            };
         }
 
         let request = request_builder
-            .body(
-                Full::new(Bytes::from(json_body))
-                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
-                    .boxed(),
-            )
+            .body(body)
             .map_err(|e| ApiError::other(format!("Failed to build request: {}", e)))?;
 
-        let response = timeout(self.timeout_duration, self.client.request(request))
+        self.traffic.request_dispatched();
+        let started = std::time::Instant::now();
+        let response = timeout(live.timeout, self.http_client().request(request))
             .await
-            .map_err(|_| ApiError::Timeout)?
-            .map_err(|e| ApiError::other(format!("Client error: {}", e)))?;
+            .map_err(|_| {
+                ApiError::Timeout(
+                    TimeoutDetail::new(TimeoutPhase::Request, started.elapsed(), live.timeout)
+                        .for_request(method_name, url),
+                )
+            })?
+            .map_err(|e| ApiError::connect(&e))?
+            .map(|body| self.traffic.count_received(body));
+        let response = decode_response(response, &self.accept_encodings);
+        if let Some(jar) = &self.cookies {
+            jar.store(url, response.headers());
+        }
 
         let status = response.status();
         let status_code = status.as_u16();
+        self.events.emit(ClientEvent::ResponseReceived {
+            status: status_code,
+        });
 
-        // Post-request plugin hook
+        // Post-request plugin hook, with the time to response headers for latency tracking
+        let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
         self.plugin_manager
-            .execute_post_request(url, status_code)
+            .execute_post_request_with(
+                url,
+                status_code,
+                plugin_context(PluginHook::PostRequest, &metadata)
+                    .with_metadata("elapsed_ms", elapsed_ms.into()),
+            )
             .await?;
 
-        if !status.is_success() {
-            return Err(ApiError::HttpStatus { status });
+        // Pre-response plugin hook, with the content type for response validators
+        let mut context = plugin_context(PluginHook::PreResponse, &metadata);
+        if let Some(content_type) = response
+            .headers()
+            .get(hyper::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+        {
+            context = context.with_metadata("content_type", content_type.into());
         }
+        self.plugin_manager
+            .execute_pre_response_with(status_code, context)
+            .await?;
 
-        let body_bytes = http_body_util::BodyExt::collect(response.into_body())
-            .await
-            .map_err(|e| ApiError::other(format!("Failed to read response body: {}", e)))?
-            .to_bytes();
+        let redirect = pass_redirects && redirect_location(status, response.headers()).is_some();
+        if error_for_status && !status.is_success() && !redirect {
+            let error = match status {
+                StatusCode::PRECONDITION_FAILED => ApiError::PreconditionFailed {
+                    body: self.error_body(response, live.timeout).await,
+                },
+                StatusCode::UNAUTHORIZED => {
+                    let detail = AuthDetail::from_response(status, response.headers());
+                    ApiError::Authentication(AuthDetail {
+                        body: self.error_body(response, live.timeout).await,
+                        ..detail
+                    })
+                }
+                _ => ApiError::HttpStatus {
+                    status,
+                    headers: canonical_header_map(response.headers()),
+                    body: self.error_body(response, live.timeout).await,
+                },
+            };
+            self.plugin_manager
+                .execute_error_with(&error, plugin_context(PluginHook::OnError, &metadata))
+                .await?;
+            return Err(error);
+        }
+
+        Ok(response)
+    }
+
+    /// Up to `client.max_error_body` bytes of an error response's body, as lossy UTF-8.
+    ///
+    /// Reading stops at the cap or after `limit`, keeping what arrived; the body
+    /// codec only decodes bodies read in full.
+    async fn error_body(&self, response: hyper::Response<ResponseBody>, limit: Duration) -> String {
+        let cap = self.config.client.max_error_body;
+        let mut body = response.into_body().into_data_stream();
+        let mut bytes = Vec::new();
+        let read = async {
+            while let Some(Ok(chunk)) = body.next().await {
+                bytes.extend_from_slice(&chunk);
+                if bytes.len() > cap {
+                    bytes.truncate(cap);
+                    return false;
+                }
+            }
+            true
+        };
+        let complete = timeout(limit, read).await.unwrap_or(false);
 
-        String::from_utf8(body_bytes.to_vec())
-            .map_err(|e| ApiError::other(format!("Invalid UTF-8: {}", e)))
+        let bytes = match &self.body_codec {
+            Some(codec) if complete && !bytes.is_empty() => {
+                let raw = Bytes::from(bytes);
+                codec.decode(raw.clone()).unwrap_or(raw)
+            }
+            _ => Bytes::from(bytes),
+        };
+        String::from_utf8_lossy(&bytes).into_owned()
     }
 
-    /// Execute HTTP request with retry logic and plugin support
+    /// Execute HTTP request with retry logic and plugin support.
+    ///
+    /// Redirects are followed up to `max_redirects` per attempt, with the same
+    /// credential stripping as every other request, and failures retried up to
+    /// `max_retries`, as are bodies matched by `ApiClientBuilder::with_retry_if`.
+    /// Each retry starts over at `url`. When `max_total_hops` is set, redirects
+    /// and retries together stop there with `ApiError::TooManyHops`, whichever
+    /// limit comes first.
     pub async fn execute_request_with_retry(
         &self,
         url: &str,
@@ -457,37 +2168,68 @@ impl ApiClient {
         body: Option<&serde_json::Value>,
     ) -> Result<String> {
         let _ = UrlValidator::validate(url)?;
-        let max_retries = self.config.client.max_retries;
-        let mut last_error = None;
-
-        for attempt in 0..=max_retries {
-            if attempt > 0 {
-                // Retry plugin hook
-                self.plugin_manager.execute_retry(attempt as u32).await?;
-                tokio::time::sleep(self.config.retry_delay()).await;
-            }
-
-            let result = match method {
-                Method::GET => self.get(url).await,
-                Method::POST => {
-                    if let Some(json_data) = body {
-                        self.post_json(url, json_data).await
-                    } else {
-                        return Err(ApiError::other("POST request requires JSON body"));
-                    }
-                }
-                _ => return Err(ApiError::other(format!("Unsupported method: {}", method))),
+        let live = self.live_settings();
+        let hops = HopBudget::new(url, live.max_total_hops);
+        let json_body = match (&method, body) {
+            (&Method::GET, _) => None,
+            (&Method::POST, Some(json_data)) => Some(serde_json::to_string(json_data)?),
+            (&Method::POST, None) => {
+                return Err(ApiError::other("POST request requires JSON body"))
+            }
+            _ => return Err(ApiError::other(format!("Unsupported method: {}", method))),
+        };
+        let mut retries = 0;
+
+        loop {
+            let request = match &json_body {
+                Some(json_body) => Outgoing::new(
+                    Method::POST,
+                    url,
+                    Some("application/json"),
+                    full_body(json_body.clone()),
+                ),
+                None => Outgoing::new(Method::GET, url, None, empty_body()),
             };
 
-            match result {
-                Ok(response) => return Ok(response),
-                Err(e) => {
-                    last_error = Some(e);
+            // Each attempt takes its own dispatch slot, so no slot is held while backing off
+            let error = match self.receive(request.hops(&hops)).await {
+                Ok(received) => {
+                    let body = String::from_utf8(received.body.to_vec()).map_err(|e| {
+                        self.failed(ApiError::other(format!("Invalid UTF-8: {}", e)))
+                    })?;
+                    let retryable = self.retry_if.as_ref().is_some_and(|retry| retry(&body));
+                    if !retryable || retries >= live.max_retries {
+                        return Ok(body);
+                    }
+                    ApiError::other(format!("Retryable response body from {}", url))
                 }
+                Err(e @ ApiError::TooManyHops { .. }) => return Err(e),
+                Err(e) => e,
+            };
+            if retries >= live.max_retries {
+                return Err(error);
             }
+            hops.take()?;
+            retries += 1;
+            let reason = error.to_string();
+            tracing::debug!(
+                "Retrying {} (attempt {}) in {:?}: {}",
+                url,
+                retries,
+                live.retry_delay,
+                reason
+            );
+            self.events.emit(ClientEvent::RetryAttempted {
+                attempt: retries as u32,
+                delay: live.retry_delay,
+                reason: reason.clone(),
+            });
+            // Retry plugin hook
+            self.plugin_manager
+                .execute_retry(retries as u32, live.retry_delay, &reason)
+                .await?;
+            tokio::time::sleep(live.retry_delay).await;
         }
-
-        Err(last_error.unwrap_or_else(|| ApiError::other("All retry attempts failed")))
     }
 
     /// Download a file from URL and save to local filesystem
@@ -501,15 +2243,79 @@ impl ApiClient {
         url: &str,
         filename: &str,
         use_local: bool,
+    ) -> Result<std::path::PathBuf> {
+        self.download_file_with(url, filename, use_local, &RequestOptions::default())
+            .await
+    }
+
+    /// Download a file with per-request options.
+    ///
+    /// With `RequestOptions::range`, exactly the bytes of the `206 Partial Content`
+    /// reply are written. With `RequestOptions::max_size`, a download that grows
+    /// past it is aborted. The body goes to a temp file that replaces the
+    /// destination only once complete, so a failed or cancelled download leaves
+    /// an existing file of the same name untouched.
+    pub async fn download_file_with(
+        &self,
+        url: &str,
+        filename: &str,
+        use_local: bool,
+        options: &RequestOptions,
+    ) -> Result<std::path::PathBuf> {
+        let _ = UrlValidator::validate(url)?;
+        let (file_path, key) = self.download_target(filename, use_local).await?;
+
+        // Only identical requests can share a fetch; a range is part of what is fetched
+        let source = match options.range {
+            Some(range) => format!("{} bytes={}", url, range),
+            None => url.to_string(),
+        };
+        self.download_coalesced(key, &source, || {
+            self.fetch_to_file(url, &file_path, options)
+        })
+        .await
+    }
+
+    /// Download a file like `download_file`, fetching it as `parts` concurrent
+    /// `Range` requests.
+    ///
+    /// A `HEAD` request first checks for `Accept-Ranges: bytes` and the size; the
+    /// file is then pre-sized and each part written at its own offset. Without
+    /// range support or a known size (or with `parts <= 1`) the file is fetched
+    /// as a single stream instead.
+    pub async fn download_file_parallel(
+        &self,
+        url: &str,
+        filename: &str,
+        parts: usize,
     ) -> Result<std::path::PathBuf> {
         let _ = UrlValidator::validate(url)?;
+        let (file_path, key) = self.download_target(filename, false).await?;
+        // Same bytes as a plain download, so either may share the other's fetch
+        self.download_coalesced(key, url, || async {
+            let result = write_via_temp(&file_path, |temp| async move {
+                self.fetch_parallel(url, &temp, parts).await
+            })
+            .await;
+            if let Err(e) = &result {
+                self.events.emit(ClientEvent::RequestFailed {
+                    error_code: e.code(),
+                });
+            }
+            result.map(|()| file_path.clone())
+        })
+        .await
+    }
+
+    /// Where `filename` is downloaded to, and the canonical path used to coalesce downloads
+    async fn download_target(
+        &self,
+        filename: &str,
+        use_local: bool,
+    ) -> Result<(std::path::PathBuf, std::path::PathBuf)> {
         // Sanitize filename to prevent path traversal attacks
         let sanitized_filename = Self::sanitize_filename(filename)?;
 
-        // Get the response as bytes
-        let response_text = self.get(url).await?;
-        let response_bytes = response_text.into_bytes();
-
         // Choose download directory based on local flag
         let downloads_dir = if use_local {
             // Use ./.downloads/ directory for local downloads
@@ -519,27 +2325,409 @@ impl ApiClient {
             self.config.storage.base_path.join("downloads")
         };
 
-        let file_path = downloads_dir.join(sanitized_filename);
-
         // Ensure downloads directory exists
         fs::create_dir_all(&downloads_dir)
             .await
             .map_err(|e| ApiError::other(format!("Failed to create downloads directory: {}", e)))?;
 
-        // Write the file
-        let mut file = fs::File::create(&file_path)
+        let file_path = downloads_dir.join(&sanitized_filename);
+        let key = fs::canonicalize(&downloads_dir)
+            .await
+            .map_err(|e| ApiError::other(format!("Failed to resolve downloads directory: {}", e)))?
+            .join(&sanitized_filename);
+        Ok((file_path, key))
+    }
+
+    /// Run `fetch` unless a download of `source` to `key` is already in flight, then share its result
+    async fn download_coalesced<F, Fut>(
+        &self,
+        key: std::path::PathBuf,
+        source: &str,
+        fetch: F,
+    ) -> Result<std::path::PathBuf>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<std::path::PathBuf>>,
+    {
+        // Concurrent downloads to the same path share one fetch instead of clobbering the file
+        loop {
+            match self.downloads.claim(key.clone(), source) {
+                Claim::Leader(guard) => {
+                    let result = fetch().await;
+                    guard.finish(&result);
+                    return result;
+                }
+                Claim::Follower(follower) => {
+                    if let Some(result) = follower.wait().await {
+                        return result;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Stream `url` into `filename` under the storage data directory.
+    ///
+    /// The body is written as it arrives, without buffering, to a temp file next to
+    /// the destination that is renamed into place once complete. Both
+    /// `client.max_response_size` and `storage.max_file_size` are enforced (up
+    /// front from `Content-Length` when known). On failure only the temp file is
+    /// removed, so an existing file of the same name is left untouched.
+    pub async fn fetch_to_storage(&self, url: &str, filename: &str) -> Result<std::path::PathBuf> {
+        let filename = Self::sanitize_filename(filename)?;
+        let destination = self.config.data_dir().join(&filename);
+        let temp_name = temp_sibling(std::path::Path::new(&filename))
+            .to_string_lossy()
+            .into_owned();
+        let OpenResponse {
+            response,
+            permit: _permit,
+            mut aborted,
+        } = self
+            .open(Outgoing::new(Method::GET, url, None, empty_body()))
+            .await?;
+
+        let storage = crate::storage::StorageManager::new(self.config.clone());
+        let saved = tokio::select! {
+            result = self.save_response(&storage, response, &temp_name) => result,
+            Ok(()) = aborted.changed() => Err(ApiError::Cancelled),
+        };
+        let result = match saved {
+            Ok(temp_path) => fs::rename(&temp_path, &destination)
+                .await
+                .map(|()| destination)
+                .map_err(|e| ApiError::other(format!("Failed to move download into place: {}", e))),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = &result {
+            let _ = storage.delete_file(&temp_name).await;
+            self.events.emit(ClientEvent::RequestFailed {
+                error_code: e.code(),
+            });
+        }
+        result
+    }
+
+    /// Stream `url` as an archive and extract its entries under `dest_dir` (`archive` feature).
+    ///
+    /// Entries are written as the body arrives. Entry names are checked with the
+    /// `PathValidator` filename rules, and an absolute or `..` name aborts the
+    /// extraction with `ApiError::Validation`; entries before it stay on disk.
+    /// Returns the files written. `client.max_response_size` caps the archive size.
+    #[cfg(feature = "archive")]
+    pub async fn download_and_extract(
+        &self,
+        url: &str,
+        dest_dir: &std::path::Path,
+        format: ArchiveFormat,
+    ) -> Result<Vec<std::path::PathBuf>> {
+        let OpenResponse {
+            response,
+            permit: _permit,
+            mut aborted,
+        } = self
+            .open(Outgoing::new(Method::GET, url, None, empty_body()))
+            .await?;
+
+        let (chunks, received) = mpsc::channel(8);
+        let dest_dir = dest_dir.to_path_buf();
+        let extraction = tokio::task::spawn_blocking(move || {
+            archive::extract(archive::ChunkReader::new(received), format, &dest_dir)
+        });
+
+        let limit = self.config.client.max_response_size;
+        let feed = async {
+            let mut body = response.into_body().into_data_stream();
+            let mut total = 0u64;
+            while let Some(chunk) = body.next().await {
+                let chunk = chunk.map_err(BodyError::into_api_error)?;
+                total += chunk.len() as u64;
+                if let Some(limit) = limit.filter(|limit| total > *limit) {
+                    return Err(response_too_large(limit));
+                }
+                if chunks.send(chunk).await.is_err() {
+                    // The extractor stopped early; its result says why
+                    break;
+                }
+            }
+            Ok(())
+        };
+        let fed = tokio::select! {
+            result = feed => result,
+            Ok(()) = aborted.changed() => Err(ApiError::Cancelled),
+        };
+        drop(chunks);
+
+        let extracted = extraction
             .await
-            .map_err(|e| ApiError::other(format!("Failed to create file: {}", e)))?;
+            .map_err(|e| ApiError::other(format!("Archive extraction failed: {}", e)))?;
+        let result = fed.and(extracted);
+        if let Err(e) = &result {
+            self.events.emit(ClientEvent::RequestFailed {
+                error_code: e.code(),
+            });
+        }
+        result
+    }
+
+    /// Write a response body through `StorageManager::save_stream` under the response cap
+    async fn save_response(
+        &self,
+        storage: &crate::storage::StorageManager,
+        response: hyper::Response<ResponseBody>,
+        filename: &str,
+    ) -> Result<std::path::PathBuf> {
+        let response_limit = self.config.client.max_response_size;
+        let file_limit = self.config.storage.max_file_size;
+        if let Some(length) = response.body().size_hint().exact() {
+            if let Some(limit) = response_limit.filter(|limit| length > *limit) {
+                return Err(response_too_large(limit));
+            }
+            if length > file_limit {
+                return Err(ApiError::storage(format!(
+                    "File size {} exceeds limit {}",
+                    length, file_limit
+                )));
+            }
+        }
+
+        let mut received = 0u64;
+        let body = response
+            .into_body()
+            .into_data_stream()
+            .map_err(BodyError::into_api_error)
+            .and_then(move |chunk| {
+                received += chunk.len() as u64;
+                let result = match response_limit {
+                    Some(limit) if received > limit => Err(response_too_large(limit)),
+                    _ => Ok(chunk),
+                };
+                futures::future::ready(result)
+            });
+        storage.save_stream(Box::pin(body), filename, None).await
+    }
+
+    /// Fetch `url` and stream the body to `file_path` through a temp file, which
+    /// replaces `file_path` only once the download is complete
+    async fn fetch_to_file(
+        &self,
+        url: &str,
+        file_path: &std::path::Path,
+        options: &RequestOptions,
+    ) -> Result<std::path::PathBuf> {
+        let result = write_via_temp(file_path, |temp| async move {
+            self.fetch_resuming(url, &temp, options).await
+        })
+        .await;
+        if let Err(e) = &result {
+            self.events.emit(ClientEvent::RequestFailed {
+                error_code: e.code(),
+            });
+        }
+        result.map(|()| file_path.to_path_buf())
+    }
+
+    /// Stream `url` to `file_path`, continuing with a `Range` request when the
+    /// connection drops mid-body.
+    ///
+    /// Up to `max_retries` continuations are attempted. A server that answers a
+    /// continuation with anything but a `206` starting at the bytes already
+    /// written gets the download restarted from scratch instead. Requests for an
+    /// explicit range are not continued.
+    async fn fetch_resuming(
+        &self,
+        url: &str,
+        file_path: &std::path::Path,
+        options: &RequestOptions,
+    ) -> Result<()> {
+        let live = self.live_settings();
+        let max_retries = if options.range.is_none() {
+            live.max_retries
+        } else {
+            0
+        };
+        let limit = options.max_size.or(self.config.client.max_response_size);
+        let mut file = create_file(file_path).await?;
+        let mut written = 0u64;
+        let mut attempt = 0u32;
+
+        loop {
+            let mut attempt_options = options.clone();
+            if written > 0 {
+                attempt_options.range = Some(ByteRange::From(written));
+            }
+            let OpenResponse {
+                response,
+                permit: _permit,
+                mut aborted,
+            } = self
+                .open(Outgoing::new(Method::GET, url, None, empty_body()).options(&attempt_options))
+                .await?;
+
+            if written > 0 && !resumes_at(&response, written) {
+                tracing::warn!(
+                    "Server cannot resume {} at byte {}; restarting the download",
+                    url,
+                    written
+                );
+                file = create_file(file_path).await?;
+                written = 0;
+            } else if options.range.is_some() && response.status() != StatusCode::PARTIAL_CONTENT {
+                tracing::warn!(
+                    "Server ignored the Range header for {}; saving the full response",
+                    url
+                );
+            }
+
+            let result = tokio::select! {
+                result = write_body(response, &mut file, &mut written, limit) => result,
+                Ok(()) = aborted.changed() => Err(WriteError::Fatal(ApiError::Cancelled)),
+            };
+            match result {
+                Ok(()) => return Ok(()),
+                Err(WriteError::Interrupted(e)) if (attempt as usize) < max_retries => {
+                    attempt += 1;
+                    tracing::warn!(
+                        "Download of {} interrupted after {} bytes ({}); resuming",
+                        url,
+                        written,
+                        e
+                    );
+                    let reason = e.to_string();
+                    self.events.emit(ClientEvent::RetryAttempted {
+                        attempt,
+                        delay: live.retry_delay,
+                        reason: reason.clone(),
+                    });
+                    self.plugin_manager
+                        .execute_retry(attempt, live.retry_delay, &reason)
+                        .await?;
+                    tokio::time::sleep(live.retry_delay).await;
+                }
+                Err(WriteError::Interrupted(e) | WriteError::Fatal(e)) => return Err(e),
+                Err(WriteError::TooLarge(limit)) => {
+                    return Err(match options.max_size {
+                        Some(_) => download_too_large(limit),
+                        None => response_too_large(limit),
+                    });
+                }
+            }
+        }
+    }
 
-        file.write_all(&response_bytes)
+    /// Fetch `url` into `file_path` as `parts` concurrent ranges when the server allows it
+    async fn fetch_parallel(
+        &self,
+        url: &str,
+        file_path: &std::path::Path,
+        parts: usize,
+    ) -> Result<()> {
+        let headers = match self
+            .receive(Outgoing::new(Method::HEAD, url, None, empty_body()))
             .await
-            .map_err(|e| ApiError::other(format!("Failed to write file: {}", e)))?;
+        {
+            Ok(Received { headers, .. }) => headers,
+            // No usable HEAD; the download itself reports any real problem
+            Err(ApiError::HttpStatus { .. }) => hyper::HeaderMap::new(),
+            Err(e) => return Err(e),
+        };
+        let accepts_ranges = headers
+            .get(hyper::header::ACCEPT_RANGES)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("bytes"));
+        let length = headers
+            .get(hyper::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        let length = match length {
+            Some(length) if accepts_ranges && parts > 1 && length >= parts as u64 => length,
+            _ => {
+                tracing::debug!("Downloading {} as a single stream", url);
+                return self
+                    .fetch_resuming(url, file_path, &RequestOptions::default())
+                    .await;
+            }
+        };
+        if let Some(limit) = self
+            .config
+            .client
+            .max_response_size
+            .filter(|limit| length > *limit)
+        {
+            return Err(response_too_large(limit));
+        }
 
-        file.flush()
+        create_file(file_path)
+            .await?
+            .set_len(length)
             .await
-            .map_err(|e| ApiError::other(format!("Failed to flush file: {}", e)))?;
+            .map_err(|e| ApiError::other(format!("Failed to allocate file: {}", e)))?;
+        let part_len = length.div_ceil(parts as u64);
+        let ranges = (0..parts as u64)
+            .map(|part| part * part_len)
+            .take_while(|start| *start < length)
+            .map(|start| (start, (start + part_len).min(length) - 1));
+        futures::future::try_join_all(
+            ranges.map(|(start, end)| self.fetch_part(url, file_path, start, end)),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Fetch bytes `start..=end` of `url` and write them at the same offset of `file_path`
+    async fn fetch_part(
+        &self,
+        url: &str,
+        file_path: &std::path::Path,
+        start: u64,
+        end: u64,
+    ) -> Result<()> {
+        let options = RequestOptions::new().range(ByteRange::Bounded { start, end });
+        let OpenResponse {
+            response,
+            permit: _permit,
+            mut aborted,
+        } = self
+            .open(Outgoing::new(Method::GET, url, None, empty_body()).options(&options))
+            .await?;
+        if !resumes_at(&response, start) {
+            return Err(ApiError::other(format!(
+                "Server ignored the Range header for {} bytes={}-{}",
+                url, start, end
+            )));
+        }
 
-        Ok(file_path)
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .open(file_path)
+            .await
+            .map_err(|e| ApiError::other(format!("Failed to open file: {}", e)))?;
+        file.seek(std::io::SeekFrom::Start(start))
+            .await
+            .map_err(|e| ApiError::other(format!("Failed to seek file: {}", e)))?;
+        let expected = end - start + 1;
+        let mut written = 0u64;
+        let result = tokio::select! {
+            result = write_body(response, &mut file, &mut written, Some(expected)) => result,
+            Ok(()) = aborted.changed() => Err(WriteError::Fatal(ApiError::Cancelled)),
+        };
+        match result {
+            Ok(()) => {}
+            Err(WriteError::Interrupted(e) | WriteError::Fatal(e)) => return Err(e),
+            Err(WriteError::TooLarge(_)) => {
+                return Err(ApiError::other(format!(
+                    "Range bytes={}-{} of {} is longer than {} bytes",
+                    start, end, url, expected
+                )))
+            }
+        }
+        if written != expected {
+            return Err(ApiError::other(format!(
+                "Range bytes={}-{} of {} ended after {} of {} bytes",
+                start, end, url, written, expected
+            )));
+        }
+        Ok(())
     }
 
     /// Sanitize filename to prevent path traversal attacks
@@ -570,15 +2758,236 @@ impl ApiClient {
         Ok(filename.to_string())
     }
 
-    /// Download JSON data and deserialize it
+    /// Build a URL from a path template such as `/users/{id}/posts/{post}`.
+    ///
+    /// Each value is percent-encoded as a single path segment. Every placeholder
+    /// must be filled and every parameter used. Relative templates are resolved
+    /// against `config.client.base_url`; absolute URLs are used as-is.
+    pub fn path(&self, template: &str, params: &[(&str, &str)]) -> Result<String> {
+        let expanded = expand_path_template(template, params)?;
+        if url::Url::parse(&expanded).is_ok() {
+            return Ok(expanded);
+        }
+
+        let base = self.config.client.base_url.as_deref().ok_or_else(|| {
+            ApiError::config(format!(
+                "Relative path {} requires client.base_url",
+                expanded
+            ))
+        })?;
+        Ok(format!(
+            "{}/{}",
+            base.trim_end_matches('/'),
+            expanded.trim_start_matches('/')
+        ))
+    }
+
+    /// Download JSON data and deserialize it.
+    ///
+    /// A 204/205 or empty body fails with `ApiError::EmptyResponse`; use
+    /// `download_json_optional` for endpoints that may legitimately return nothing.
+    /// With `ApiClientBuilder::with_json_cache`, fresh cached bodies are served without a request.
     pub async fn download_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
-        let response_text = self.get(url).await?;
-        let json_data: T = serde_json::from_str(&response_text)
-            .map_err(|e| ApiError::other(format!("Failed to deserialize JSON: {}", e)))?;
-        Ok(json_data)
+        if let Some(body) = self.json_cache.as_ref().and_then(|cache| cache.get(url)) {
+            if let Some(value) = Self::parse_json_body(StatusCode::OK, &body)? {
+                return Ok(value);
+            }
+        }
+
+        let Response {
+            status,
+            body: response_text,
+            ..
+        } = self
+            .exchange(Outgoing::new(Method::GET, url, None, empty_body()))
+            .await?;
+        let value = Self::parse_json_body(status, &response_text)?
+            .ok_or(ApiError::EmptyResponse { status })?;
+        if let Some(cache) = &self.json_cache {
+            cache.insert(url, response_text);
+        }
+        Ok(value)
+    }
+
+    /// Download JSON data, returning `None` for a 204/205 or empty body
+    pub async fn download_json_optional<T: DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<Option<T>> {
+        let Response {
+            status,
+            body: response_text,
+            ..
+        } = self
+            .exchange(Outgoing::new(Method::GET, url, None, empty_body()))
+            .await?;
+        Self::parse_json_body(status, &response_text)
+    }
+
+    /// GET, returning the status and headers along with the body text.
+    ///
+    /// Header names are canonically cased (`Content-Type`, `ETag`); repeated
+    /// headers are joined with `, `.
+    pub async fn get_with_headers(
+        &self,
+        url: &str,
+    ) -> Result<(StatusCode, HashMap<String, String>, String)> {
+        let Response {
+            status,
+            headers,
+            body,
+        } = self.get_response(url).await?;
+        Ok((status, headers, body))
+    }
+
+    /// Send a HEAD request and return the status and headers, e.g. to check that a
+    /// resource exists or read its `Content-Length` without downloading it.
+    ///
+    /// The body is never read, even if the server wrongly sends one, so it is
+    /// always empty.
+    pub async fn head(&self, url: &str) -> Result<Response> {
+        let OpenResponse { response, .. } = self
+            .open(Outgoing::new(Method::HEAD, url, None, empty_body()))
+            .await?;
+        Ok(Response {
+            status: response.status(),
+            headers: canonical_header_map(response.headers()),
+            body: String::new(),
+        })
+    }
+
+    /// Send an OPTIONS request and return the status, headers (`Allow`, CORS
+    /// `Access-Control-*`) and body
+    pub async fn options_response(&self, url: &str) -> Result<Response> {
+        self.send_response(Method::OPTIONS, url, None, empty_body())
+            .await
+    }
+
+    /// Send an OPTIONS request and parse the `Allow` header into the methods the
+    /// server accepts for `url`.
+    ///
+    /// A response without `Allow` yields an empty list; unparseable entries are skipped.
+    pub async fn options(&self, url: &str) -> Result<Vec<Method>> {
+        let Received { headers, .. } = self
+            .receive(Outgoing::new(Method::OPTIONS, url, None, empty_body()))
+            .await?;
+        Ok(headers
+            .get_all(hyper::header::ALLOW)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|method| !method.is_empty())
+            .filter_map(|method| Method::from_bytes(method.as_bytes()).ok())
+            .collect())
+    }
+
+    /// GET and decode the body with the serializer for the response `Content-Type`.
+    ///
+    /// Responses without a content type are decoded as JSON. A 204/205 or empty
+    /// body fails with `ApiError::EmptyResponse`.
+    pub async fn get_typed<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let received = self
+            .receive(Outgoing::new(Method::GET, url, None, empty_body()))
+            .await?;
+        self.decode_received(received, DEFAULT_CONTENT_TYPE)
+    }
+
+    /// POST `body` encoded as `content_type` and decode the typed response
+    pub async fn post_typed<B, T>(&self, url: &str, content_type: &str, body: &B) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.send_typed(Method::POST, url, content_type, body).await
+    }
+
+    /// PUT `body` encoded as `content_type` and decode the typed response
+    pub async fn put_typed<B, T>(&self, url: &str, content_type: &str, body: &B) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.send_typed(Method::PUT, url, content_type, body).await
+    }
+
+    /// Encode with the request content type; decode with the response one, falling back to the request's
+    async fn send_typed<B, T>(
+        &self,
+        method: Method,
+        url: &str,
+        content_type: &str,
+        body: &B,
+    ) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let encoded = self.serializers.encode(content_type, body)?;
+        let received = self
+            .receive(Outgoing::new(
+                method,
+                url,
+                Some(content_type),
+                full_body(encoded),
+            ))
+            .await?;
+        self.decode_received(received, content_type)
+    }
+
+    fn decode_received<T: DeserializeOwned>(
+        &self,
+        received: Received,
+        fallback: &str,
+    ) -> Result<T> {
+        let Received {
+            status,
+            headers,
+            body,
+        } = received;
+        if is_no_content(status) || body.is_empty() {
+            return Err(ApiError::EmptyResponse { status });
+        }
+        let content_type = headers
+            .get(hyper::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or(fallback);
+        self.serializers.decode(content_type, &body)
+    }
+
+    /// Execute HTTP GET request, returning `None` when the server sends no content by design (204/205).
+    ///
+    /// A body cut short of its declared length is an error, never an empty string.
+    pub async fn get_optional(&self, url: &str) -> Result<Option<String>> {
+        let Response {
+            status,
+            body: response_text,
+            ..
+        } = self
+            .exchange(Outgoing::new(Method::GET, url, None, empty_body()))
+            .await?;
+        if is_no_content(status) {
+            Ok(None)
+        } else {
+            Ok(Some(response_text))
+        }
+    }
+
+    fn parse_json_body<T: DeserializeOwned>(status: StatusCode, body: &str) -> Result<Option<T>> {
+        if is_no_content(status) || body.trim().is_empty() {
+            return Ok(None);
+        }
+        serde_json::from_str(body)
+            .map(Some)
+            .map_err(|e| ApiError::other(format!("Failed to deserialize JSON: {}", e)))
     }
 }
 
+/// Statuses that carry no body by definition
+fn is_no_content(status: StatusCode) -> bool {
+    status == StatusCode::NO_CONTENT || status == StatusCode::RESET_CONTENT
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -670,6 +3079,67 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_path_substitutes_and_encodes_params() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::new(temp_dir.path().to_path_buf());
+        config.client.base_url = Some("https://api.example.com/v1/".to_string());
+        let client = ApiClient::new(config);
+
+        let url = client
+            .path(
+                "/users/{id}/posts/{post}",
+                &[("id", "7"), ("post", "a b/c")],
+            )
+            .unwrap();
+        assert_eq!(url, "https://api.example.com/v1/users/7/posts/a%20b%2Fc");
+
+        let absolute = client
+            .path("https://other.example.com/items/{id}", &[("id", "42")])
+            .unwrap();
+        assert_eq!(absolute, "https://other.example.com/items/42");
+    }
+
+    #[test]
+    fn test_path_rejects_missing_parameter() {
+        let temp_dir = TempDir::new().unwrap();
+        let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+        let result = client.path(
+            "https://api.example.com/users/{id}/posts/{post}",
+            &[("id", "7")],
+        );
+        match result {
+            Err(ApiError::Other(msg)) => assert!(msg.contains("post"), "{}", msg),
+            other => panic!("Expected missing parameter error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_append_query_encodes_and_merges() {
+        let url = append_query(
+            "https://api.example.com/search?sort=asc#top",
+            &[
+                ("q", "a b&c=d/e?"),
+                ("tag", "x"),
+                ("tag", "y"),
+                ("empty", ""),
+                ("k y", "100%"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            url,
+            "https://api.example.com/search?sort=asc&q=a%20b%26c%3Dd%2Fe%3F&tag=x&tag=y&empty=&k%20y=100%25#top"
+        );
+
+        assert_eq!(
+            append_query("https://api.example.com/items", &[]).unwrap(),
+            "https://api.example.com/items"
+        );
+        assert!(append_query("not a url", &[("q", "x")]).is_err());
+    }
+
     #[tokio::test]
     async fn test_download_json() {
         let _temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,224 @@
+//! Per-request options
+
+use crate::error::{ApiError, Result};
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Per-request settings layered over the client defaults
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestOptions {
+    /// Sent as `If-Match`; a 412 reply surfaces as `ApiError::PreconditionFailed`
+    pub if_match: Option<String>,
+    /// Sent as `If-None-Match` (e.g. `*` to create only if absent)
+    pub if_none_match: Option<String>,
+    /// Sent as `Range: bytes=...`; a `206 Partial Content` reply is a success
+    pub range: Option<ByteRange>,
+    /// Added to `PluginContext.metadata` for every hook of this request (never sent)
+    pub metadata: HashMap<String, serde_json::Value>,
+    /// Sent as `User-Agent` instead of the client's user agent
+    pub user_agent: Option<String>,
+    /// How `ApiClient::request` interprets a successful response body
+    pub expect: BodyExpectation,
+    /// Cap on the bytes `ApiClient::download_file_with` saves, in place of `client.max_response_size`
+    pub max_size: Option<u64>,
+    /// Client-level headers (defaults, custom, plugin-added) left off this request; case-insensitive
+    pub remove_headers: Vec<String>,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only apply the write if the resource still has this ETag
+    pub fn if_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+
+    /// Only apply the write if the resource does not match this ETag
+    pub fn if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_none_match = Some(etag.into());
+        self
+    }
+
+    /// Request only part of the resource
+    pub fn range(mut self, range: ByteRange) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Identify as `user_agent` for this request only
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Have `ApiClient::request` return the body as `expect` describes
+    pub fn expect(mut self, expect: BodyExpectation) -> Self {
+        self.expect = expect;
+        self
+    }
+
+    /// Abort a download (deleting the partial file) once it passes `bytes`
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Omit `name` from this request even if the client would send it by default
+    pub fn remove_header(mut self, name: impl Into<String>) -> Self {
+        self.remove_headers.push(name.into());
+        self
+    }
+
+    /// Make `value` visible to plugins as `context.metadata[key]`
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Headers these options set on the request, replacing default or custom ones of the same name
+    pub(crate) fn headers(&self) -> Vec<(&'static str, String)> {
+        [
+            ("if-match", self.if_match.clone()),
+            ("if-none-match", self.if_none_match.clone()),
+            ("range", self.range.map(|range| format!("bytes={}", range))),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .collect()
+    }
+}
+
+/// Form a response body is returned in by `ApiClient::request`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BodyExpectation {
+    /// Text; a body that is not UTF-8 is an error
+    #[default]
+    Utf8,
+    /// Raw bytes, whatever they contain
+    Bytes,
+    /// Parsed JSON; an empty body or 204/205 is `null`
+    Json,
+}
+
+/// Response body in the form asked for with `RequestOptions::expect`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResponseContent {
+    Text(String),
+    Bytes(Bytes),
+    Json(serde_json::Value),
+}
+
+impl ResponseContent {
+    /// The body as bytes, whichever form it was returned in
+    pub fn into_bytes(self) -> Bytes {
+        match self {
+            Self::Text(text) => Bytes::from(text),
+            Self::Bytes(bytes) => bytes,
+            Self::Json(value) => Bytes::from(value.to_string()),
+        }
+    }
+}
+
+/// A single HTTP byte range, written like the `Range` header without the unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// `start-end`, both inclusive
+    Bounded { start: u64, end: u64 },
+    /// `start-`, from `start` to the end of the resource
+    From(u64),
+    /// `-len`, the last `len` bytes
+    Suffix(u64),
+}
+
+impl ByteRange {
+    /// Number of bytes requested, when known without the resource size
+    pub fn byte_count(&self) -> Option<u64> {
+        match *self {
+            Self::Bounded { start, end } => Some(end - start + 1),
+            Self::From(_) => None,
+            Self::Suffix(len) => Some(len),
+        }
+    }
+}
+
+impl fmt::Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bounded { start, end } => write!(f, "{}-{}", start, end),
+            Self::From(start) => write!(f, "{}-", start),
+            Self::Suffix(len) => write!(f, "-{}", len),
+        }
+    }
+}
+
+impl FromStr for ByteRange {
+    type Err = ApiError;
+
+    /// Parse `start-end`, `start-` or `-len`
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            ApiError::validation(format!(
+                "Invalid byte range {:?}, expected START-END, START- or -LEN",
+                s
+            ))
+        };
+        let number = |part: &str| {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            part.parse::<u64>().map_err(|_| invalid())
+        };
+
+        let (start, end) = s.trim().split_once('-').ok_or_else(invalid)?;
+        match (start.is_empty(), end.is_empty()) {
+            (false, false) => {
+                let (start, end) = (number(start)?, number(end)?);
+                if end < start {
+                    return Err(invalid());
+                }
+                Ok(Self::Bounded { start, end })
+            }
+            (false, true) => Ok(Self::From(number(start)?)),
+            (true, false) => match number(end)? {
+                0 => Err(invalid()),
+                len => Ok(Self::Suffix(len)),
+            },
+            (true, true) => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_range_parsing() {
+        assert_eq!(
+            "0-1023".parse::<ByteRange>().unwrap(),
+            ByteRange::Bounded {
+                start: 0,
+                end: 1023
+            }
+        );
+        assert_eq!("512-".parse::<ByteRange>().unwrap(), ByteRange::From(512));
+        assert_eq!("-100".parse::<ByteRange>().unwrap(), ByteRange::Suffix(100));
+        assert_eq!(
+            "0-1023".parse::<ByteRange>().unwrap().byte_count(),
+            Some(1024)
+        );
+
+        for invalid in ["", "-", "10", "5-2", "a-b", "-0", "1-2-3", "+1-2"] {
+            assert!(
+                matches!(invalid.parse::<ByteRange>(), Err(ApiError::Validation(_))),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
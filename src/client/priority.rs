@@ -0,0 +1,185 @@
+//! Priority-ordered admission in front of the request concurrency limit
+//!
+//! When every slot is taken, waiting requests are queued per priority level and
+//! handed the next free slot highest priority first, FIFO within a level.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+
+/// Dispatch priority for a request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Background work, dispatched after everything else
+    Low,
+    /// Default priority for all requests
+    #[default]
+    Normal,
+    /// Interactive requests, dispatched ahead of queued lower priorities
+    High,
+}
+
+impl Priority {
+    const LEVELS: usize = 3;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Concurrency slot held for the duration of a request
+pub(crate) struct DispatchPermit {
+    permit: Option<OwnedSemaphorePermit>,
+    queue: Arc<DispatchQueue>,
+}
+
+impl Drop for DispatchPermit {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit.take() {
+            drop(permit);
+            self.queue.pump();
+        }
+    }
+}
+
+type Waiter = oneshot::Sender<DispatchPermit>;
+
+/// Concurrency limit with priority-ordered waiters
+pub(crate) struct DispatchQueue {
+    limit: usize,
+    slots: Arc<Semaphore>,
+    waiters: Mutex<[VecDeque<Waiter>; Priority::LEVELS]>,
+}
+
+impl DispatchQueue {
+    /// Allow at most `limit` requests in flight (a limit of 0 is treated as 1)
+    pub(crate) fn new(limit: usize) -> Arc<Self> {
+        let limit = limit.clamp(1, Semaphore::MAX_PERMITS);
+        Arc::new(Self {
+            limit,
+            slots: Arc::new(Semaphore::new(limit)),
+            waiters: Mutex::new(Default::default()),
+        })
+    }
+
+    /// Maximum number of requests in flight
+    pub(crate) fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Wait for a free slot; dropping the future gives up the place in the queue
+    pub(crate) async fn acquire(self: &Arc<Self>, priority: Priority) -> DispatchPermit {
+        let rx = {
+            let mut waiters = self.waiters.lock().unwrap();
+            if waiters.iter().all(VecDeque::is_empty) {
+                if let Ok(permit) = self.slots.clone().try_acquire_owned() {
+                    return self.wrap(permit);
+                }
+            }
+            let (tx, rx) = oneshot::channel();
+            waiters[priority.index()].push_back(tx);
+            rx
+        };
+        // A slot may have been released between the failed try and queueing
+        self.pump();
+        rx.await.expect("dispatch queue outlives its waiters")
+    }
+
+    /// Number of requests waiting for a slot
+    #[cfg(test)]
+    pub(crate) fn pending(&self) -> usize {
+        self.waiters
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|level| level.iter().filter(|tx| !tx.is_closed()).count())
+            .sum()
+    }
+
+    fn wrap(self: &Arc<Self>, permit: OwnedSemaphorePermit) -> DispatchPermit {
+        DispatchPermit {
+            permit: Some(permit),
+            queue: Arc::clone(self),
+        }
+    }
+
+    /// Hand free slots to the highest-priority live waiters
+    fn pump(self: &Arc<Self>) {
+        let mut waiters = self.waiters.lock().unwrap();
+        loop {
+            // Drop waiters that gave up before looking for a slot
+            for level in waiters.iter_mut() {
+                level.retain(|tx| !tx.is_closed());
+            }
+            let Some(level) = waiters.iter_mut().rev().find(|level| !level.is_empty()) else {
+                return;
+            };
+            let Ok(permit) = self.slots.clone().try_acquire_owned() else {
+                return;
+            };
+            let tx = level.pop_front().expect("level is not empty");
+            if let Err(mut rejected) = tx.send(self.wrap(permit)) {
+                // Waiter vanished after the check; release without re-entering pump
+                drop(rejected.permit.take());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_high_priority_dispatched_before_queued_low() {
+        let queue = DispatchQueue::new(1);
+        let running = queue.acquire(Priority::Normal).await;
+        let (order_tx, mut order_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let mut tasks = Vec::new();
+        for (label, priority) in [
+            ("low-1", Priority::Low),
+            ("low-2", Priority::Low),
+            ("low-3", Priority::Low),
+            ("high", Priority::High),
+        ] {
+            let waiter = queue.clone();
+            let order_tx = order_tx.clone();
+            tasks.push(tokio::spawn(async move {
+                let _permit = waiter.acquire(priority).await;
+                order_tx.send(label).unwrap();
+            }));
+            // Make sure each request is queued before the next one
+            while queue.pending() < tasks.len() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }
+
+        drop(running);
+        for task in tasks {
+            task.await.unwrap();
+        }
+        drop(order_tx);
+
+        let mut order = Vec::new();
+        while let Some(label) = order_rx.recv().await {
+            order.push(label);
+        }
+        assert_eq!(order, ["high", "low-1", "low-2", "low-3"]);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_waiter_does_not_leak_slot() {
+        let queue = DispatchQueue::new(1);
+        let running = queue.acquire(Priority::Normal).await;
+
+        let abandoned =
+            tokio::time::timeout(Duration::from_millis(10), queue.acquire(Priority::High)).await;
+        assert!(abandoned.is_err());
+
+        drop(running);
+        let next = tokio::time::timeout(Duration::from_secs(1), queue.acquire(Priority::Low)).await;
+        assert!(next.is_ok());
+    }
+}
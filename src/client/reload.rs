@@ -0,0 +1,87 @@
+//! Config file watching for live clients (`config-watch` feature)
+
+use super::LiveSettings;
+use crate::config::Config;
+use crate::error::{ApiError, Result};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Start watching `path`, applying safe changes to `live`.
+///
+/// The parent directory is watched so editors that replace the file on save
+/// are still picked up.
+pub(crate) fn watch_config(
+    path: PathBuf,
+    initial: Config,
+    live: Arc<RwLock<LiveSettings>>,
+) -> Result<notify::RecommendedWatcher> {
+    let watch_dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .ok_or_else(|| ApiError::config(format!("Invalid config path: {}", path.display())))?;
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::warn!("Config watch error: {}", e);
+                return;
+            }
+        };
+        if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+            return;
+        }
+        if !event
+            .paths
+            .iter()
+            .any(|p| p.file_name() == Some(file_name.as_os_str()))
+        {
+            return;
+        }
+        if let Err(e) = apply_reload(&path, &initial, &live) {
+            tracing::warn!("Keeping current settings, config reload failed: {}", e);
+        }
+    })
+    .map_err(|e| ApiError::config(format!("Failed to start config watcher: {}", e)))?;
+
+    watcher
+        .watch(&watch_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| ApiError::config(format!("Failed to watch {}: {}", watch_dir.display(), e)))?;
+
+    Ok(watcher)
+}
+
+/// Re-read the config file and apply the safe-to-change fields
+fn apply_reload(path: &Path, initial: &Config, live: &RwLock<LiveSettings>) -> Result<()> {
+    let content = std::fs::read_to_string(path)?;
+    let reloaded = Config::from_toml(&content)?;
+
+    warn_ignored_changes(initial, &reloaded);
+    *live.write().unwrap() = LiveSettings::from_config(&reloaded);
+    tracing::info!("Reloaded client settings from {}", path.display());
+    Ok(())
+}
+
+/// Log changes that cannot be applied without rebuilding the client
+fn warn_ignored_changes(initial: &Config, reloaded: &Config) {
+    if initial.storage.base_path != reloaded.storage.base_path
+        || initial.storage.temp_path != reloaded.storage.temp_path
+    {
+        tracing::warn!("Ignoring live change to storage paths; restart to apply");
+    }
+    if initial.client.tls != reloaded.client.tls {
+        tracing::warn!("Ignoring live change to client.tls; restart to apply");
+    }
+    if initial.client.user_agent != reloaded.client.user_agent {
+        tracing::warn!("Ignoring live change to client.user_agent; restart to apply");
+    }
+    if initial.plugins.enabled_plugins != reloaded.plugins.enabled_plugins {
+        tracing::warn!("Ignoring live change to plugins; restart to apply");
+    }
+}
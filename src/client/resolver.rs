@@ -0,0 +1,257 @@
+//! Pluggable DNS resolution for the connector
+//!
+//! `ApiClientBuilder::with_resolver` replaces the system resolver, e.g. for tests
+//! or custom service discovery. Only hostnames go through the resolver; IP
+//! literals in URLs connect directly. `ApiClientBuilder::with_resolve` pins a
+//! `host:port` to an address without any lookup, like curl's `--resolve`.
+
+use crate::error::{ApiError, ResolveFailure, Result};
+use async_trait::async_trait;
+use hyper::Uri;
+use hyper_util::client::legacy::connect::dns::Name;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Resolves a hostname to the addresses the connector should try, in order.
+///
+/// Failures surface from requests as `ApiError::Dns` for the host; return
+/// `ApiError::Dns` yourself to control the reason shown.
+#[async_trait]
+pub trait Resolver: Send + Sync {
+    async fn resolve(&self, host: &str) -> Result<Vec<IpAddr>>;
+}
+
+/// Resolver backed by the operating system (`getaddrinfo`)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemResolver;
+
+#[async_trait]
+impl Resolver for SystemResolver {
+    async fn resolve(&self, host: &str) -> Result<Vec<IpAddr>> {
+        let addrs = tokio::net::lookup_host((host, 0))
+            .await
+            .map_err(|e| ApiError::Dns {
+                host: host.to_string(),
+                reason: e.to_string(),
+            })?;
+        Ok(addrs.map(|addr| addr.ip()).collect())
+    }
+}
+
+/// Fixed host-to-address table; unknown hosts fail to resolve
+#[derive(Debug, Default, Clone)]
+pub struct StaticResolver {
+    hosts: HashMap<String, Vec<IpAddr>>,
+}
+
+impl StaticResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map `host` (case-insensitive) to `ip`; repeated calls add fallback addresses
+    pub fn with_host(mut self, host: impl Into<String>, ip: IpAddr) -> Self {
+        self.hosts
+            .entry(host.into().to_ascii_lowercase())
+            .or_default()
+            .push(ip);
+        self
+    }
+}
+
+#[async_trait]
+impl Resolver for StaticResolver {
+    async fn resolve(&self, host: &str) -> Result<Vec<IpAddr>> {
+        self.hosts
+            .get(&host.to_ascii_lowercase())
+            .cloned()
+            .ok_or_else(|| ApiError::Dns {
+                host: host.to_string(),
+                reason: "no static address".to_string(),
+            })
+    }
+}
+
+/// Adapts a `Resolver` to the tower service hyper's `HttpConnector` expects
+#[derive(Clone)]
+pub(crate) struct ConnectorResolver {
+    resolver: Arc<dyn Resolver>,
+}
+
+impl ConnectorResolver {
+    pub(crate) fn new(resolver: Arc<dyn Resolver>) -> Self {
+        Self { resolver }
+    }
+}
+
+type ResolveFuture = Pin<
+    Box<
+        dyn Future<
+                Output = std::result::Result<
+                    std::vec::IntoIter<SocketAddr>,
+                    Box<dyn std::error::Error + Send + Sync>,
+                >,
+            > + Send,
+    >,
+>;
+
+impl tower_service::Service<Name> for ConnectorResolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+    type Future = ResolveFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let resolver = self.resolver.clone();
+        Box::pin(async move {
+            let host = name.as_str();
+            let start = std::time::Instant::now();
+            let resolved = resolver.resolve(host).await;
+            if resolved.is_ok() {
+                super::instrument::dns_resolved(start.elapsed());
+            }
+            let ips = resolved.map_err(|e| {
+                let reason = match e {
+                    ApiError::Dns { reason, .. } => reason,
+                    other => other.to_string(),
+                };
+                ResolveFailure {
+                    host: host.to_string(),
+                    reason,
+                }
+            })?;
+            #[cfg(feature = "strict-security")]
+            let ips = public_only(host, ips)?;
+            if ips.is_empty() {
+                return Err(ResolveFailure {
+                    host: host.to_string(),
+                    reason: "resolved to no addresses".to_string(),
+                }
+                .into());
+            }
+            // The connector fills in the port from the URL
+            let addrs: Vec<_> = ips.into_iter().map(|ip| SocketAddr::new(ip, 0)).collect();
+            Ok(addrs.into_iter())
+        })
+    }
+}
+
+/// Fixed addresses for `(lowercased host, port)` pairs
+pub(crate) type ResolvePins = HashMap<(String, u16), IpAddr>;
+
+/// TCP connector that dials pinned `host:port` pairs at their fixed address.
+///
+/// Only the socket address changes: the layers above still see the original
+/// URL, so TLS SNI and certificate checks use the hostname, as does `Host`.
+#[derive(Clone)]
+pub(crate) struct PinnedConnector<C> {
+    inner: C,
+    pins: Arc<ResolvePins>,
+}
+
+impl<C> PinnedConnector<C> {
+    pub(crate) fn new(inner: C, pins: Arc<ResolvePins>) -> Self {
+        Self { inner, pins }
+    }
+
+    /// `dst` with its authority replaced by the pinned address, if it has one
+    fn pinned(&self, dst: &Uri) -> Option<Uri> {
+        let host = dst.host()?.to_ascii_lowercase();
+        let port = dst.port_u16().unwrap_or(match dst.scheme_str() {
+            Some("https") => 443,
+            _ => 80,
+        });
+        let ip = self.pins.get(&(host, port))?;
+        Uri::builder()
+            .scheme(dst.scheme_str().unwrap_or("http"))
+            .authority(SocketAddr::new(*ip, port).to_string())
+            .path_and_query("/")
+            .build()
+            .ok()
+    }
+}
+
+impl<C: tower_service::Service<Uri>> tower_service::Service<Uri> for PinnedConnector<C> {
+    type Response = C::Response;
+    type Error = C::Error;
+    type Future = C::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let dst = self.pinned(&dst).unwrap_or(dst);
+        self.inner.call(dst)
+    }
+}
+
+/// Reject resolved private addresses so a public name cannot rebind to an internal host
+#[cfg(feature = "strict-security")]
+fn public_only(host: &str, ips: Vec<IpAddr>) -> Result<Vec<IpAddr>> {
+    let is_internal = |ip: &IpAddr| match ip {
+        IpAddr::V4(ip) => ip.is_private() || ip.is_loopback() || ip.is_link_local(),
+        IpAddr::V6(ip) => ip.is_loopback(),
+    };
+    if ips.iter().any(is_internal) {
+        return Err(ApiError::other(format!(
+            "{} resolved to a private address",
+            host
+        )));
+    }
+    Ok(ips)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_static_resolver_is_case_insensitive() {
+        let resolver =
+            StaticResolver::new().with_host("Service.Internal", "10.1.2.3".parse().unwrap());
+        assert_eq!(
+            resolver.resolve("service.internal").await.unwrap(),
+            vec!["10.1.2.3".parse::<IpAddr>().unwrap()]
+        );
+        assert!(resolver.resolve("other.internal").await.is_err());
+    }
+
+    #[test]
+    fn test_pins_match_host_and_port() {
+        let pins = ResolvePins::from([
+            (
+                ("api.example.com".to_string(), 443),
+                "10.0.0.7".parse().unwrap(),
+            ),
+            (
+                ("api.example.com".to_string(), 8080),
+                "::1".parse().unwrap(),
+            ),
+        ]);
+        let connector = PinnedConnector::new((), Arc::new(pins));
+        let pinned = |url: &str| {
+            connector
+                .pinned(&url.parse().unwrap())
+                .map(|uri| uri.to_string())
+        };
+
+        assert_eq!(
+            pinned("https://API.example.com/v1").as_deref(),
+            Some("https://10.0.0.7:443/")
+        );
+        assert_eq!(
+            pinned("http://api.example.com:8080/").as_deref(),
+            Some("http://[::1]:8080/")
+        );
+        assert_eq!(pinned("http://api.example.com/"), None);
+        assert_eq!(pinned("https://other.example.com/"), None);
+    }
+}
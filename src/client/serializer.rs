@@ -0,0 +1,153 @@
+//! Content-type keyed body (de)serializers for the typed request methods
+//!
+//! Codecs work on `serde_json::Value` so any `Serialize`/`DeserializeOwned`
+//! type can pass through a format registered at runtime.
+
+use crate::error::{ApiError, Result};
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Content type used when a response does not declare one
+pub const DEFAULT_CONTENT_TYPE: &str = "application/json";
+
+type EncodeFn = dyn Fn(&Value) -> Result<Bytes> + Send + Sync;
+type DecodeFn = dyn Fn(&[u8]) -> Result<Value> + Send + Sync;
+
+#[derive(Clone)]
+struct Codec {
+    encode: Arc<EncodeFn>,
+    decode: Arc<DecodeFn>,
+}
+
+/// Maps content types (e.g. `application/cbor`) to encode/decode functions.
+///
+/// Lookups ignore parameters and case, so `application/json; charset=utf-8`
+/// uses the JSON codec. Unregistered `+json` types also fall back to JSON.
+#[derive(Clone)]
+pub struct SerializerRegistry {
+    codecs: HashMap<String, Codec>,
+}
+
+impl SerializerRegistry {
+    /// Registry with the built-in JSON codec
+    pub fn new() -> Self {
+        let mut registry = Self {
+            codecs: HashMap::new(),
+        };
+        registry
+            .register(
+                DEFAULT_CONTENT_TYPE,
+                |value| Ok(Bytes::from(serde_json::to_vec(value)?)),
+                |body| Ok(serde_json::from_slice(body)?),
+            )
+            .expect("application/json is a valid content type");
+        registry
+    }
+
+    /// Register (or replace) the codec for `content_type`
+    pub fn register<E, D>(&mut self, content_type: &str, encode: E, decode: D) -> Result<()>
+    where
+        E: Fn(&Value) -> Result<Bytes> + Send + Sync + 'static,
+        D: Fn(&[u8]) -> Result<Value> + Send + Sync + 'static,
+    {
+        let key = essence(content_type).ok_or_else(|| {
+            ApiError::validation(format!("Invalid content type: {}", content_type))
+        })?;
+        self.codecs.insert(
+            key,
+            Codec {
+                encode: Arc::new(encode),
+                decode: Arc::new(decode),
+            },
+        );
+        Ok(())
+    }
+
+    /// Whether a codec would be used for `content_type`
+    pub fn supports(&self, content_type: &str) -> bool {
+        self.codec(content_type).is_some()
+    }
+
+    /// Registered content types, sorted
+    pub fn content_types(&self) -> Vec<String> {
+        let mut types: Vec<String> = self.codecs.keys().cloned().collect();
+        types.sort();
+        types
+    }
+
+    /// Serialize `value` as a `content_type` body
+    pub fn encode<T: Serialize + ?Sized>(&self, content_type: &str, value: &T) -> Result<Bytes> {
+        let codec = self.codec(content_type).ok_or_else(|| {
+            ApiError::validation(format!("No serializer registered for {}", content_type))
+        })?;
+        (codec.encode)(&serde_json::to_value(value)?)
+    }
+
+    /// Deserialize a `content_type` body into `T`
+    pub fn decode<T: DeserializeOwned>(&self, content_type: &str, body: &[u8]) -> Result<T> {
+        let codec = self.codec(content_type).ok_or_else(|| {
+            ApiError::other(format!("No deserializer registered for {}", content_type))
+        })?;
+        let value = (codec.decode)(body)?;
+        serde_json::from_value(value)
+            .map_err(|e| ApiError::other(format!("Failed to deserialize {}: {}", content_type, e)))
+    }
+
+    fn codec(&self, content_type: &str) -> Option<&Codec> {
+        let mime: mime::Mime = content_type.parse().ok()?;
+        self.codecs
+            .get(mime.essence_str())
+            .or_else(|| match mime.suffix() {
+                Some(mime::JSON) => self.codecs.get(DEFAULT_CONTENT_TYPE),
+                _ => None,
+            })
+    }
+}
+
+impl Default for SerializerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for SerializerRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SerializerRegistry")
+            .field("content_types", &self.content_types())
+            .finish()
+    }
+}
+
+/// `type/subtype` without parameters, lowercased
+fn essence(content_type: &str) -> Option<String> {
+    let mime: mime::Mime = content_type.parse().ok()?;
+    Some(mime.essence_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_lookup_ignores_parameters_and_suffix() {
+        let registry = SerializerRegistry::new();
+        assert!(registry.supports("Application/JSON; charset=utf-8"));
+        assert!(registry.supports("application/problem+json"));
+        assert!(!registry.supports("application/cbor"));
+
+        let value: Value = registry
+            .decode("application/problem+json", br#"{"title":"nope"}"#)
+            .unwrap();
+        assert_eq!(value, json!({"title": "nope"}));
+        assert!(matches!(
+            registry.encode("application/cbor", &value),
+            Err(ApiError::Validation(_))
+        ));
+    }
+}
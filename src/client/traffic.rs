@@ -0,0 +1,110 @@
+//! Cumulative body byte accounting for bandwidth and cost tracking
+
+use super::connector::{RequestBody, ResponseBody};
+use super::encoding::BodyError;
+use bytes::Buf;
+use http_body_util::BodyExt;
+use hyper::body::{Body, Frame, Incoming, SizeHint};
+use pin_project::pin_project;
+use serde::Serialize;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Totals since the client was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TrafficStats {
+    /// Request body bytes handed to the connection
+    pub bytes_sent: u64,
+    /// Response body bytes read, whether collected, streamed or written to disk
+    pub bytes_received: u64,
+    /// Requests dispatched, counting each retry attempt
+    pub requests: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct TrafficCounters {
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    requests: AtomicU64,
+}
+
+impl TrafficCounters {
+    pub(crate) fn request_dispatched(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count data frames as the connection pulls them, so streaming uploads are covered
+    pub(crate) fn count_sent(self: &Arc<Self>, body: RequestBody) -> RequestBody {
+        Counted::new(body, Arc::clone(self), |counters| &counters.bytes_sent).boxed()
+    }
+
+    /// Count data frames as the caller reads them, however the body is consumed
+    pub(crate) fn count_received(self: &Arc<Self>, body: Incoming) -> ResponseBody {
+        Counted::new(body, Arc::clone(self), |counters| &counters.bytes_received)
+            .map_err(BodyError::Http)
+            .boxed()
+    }
+
+    pub(crate) fn snapshot(&self) -> TrafficStats {
+        TrafficStats {
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            requests: self.requests.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Body wrapper adding each data frame's length to one of the counters.
+///
+/// Unlike `BodyExt::map_frame`, this keeps the inner size hint, so known
+/// lengths are still sent as `Content-Length`.
+#[pin_project]
+struct Counted<B> {
+    #[pin]
+    inner: B,
+    counters: Arc<TrafficCounters>,
+    tally: fn(&TrafficCounters) -> &AtomicU64,
+}
+
+impl<B> Counted<B> {
+    fn new(
+        inner: B,
+        counters: Arc<TrafficCounters>,
+        tally: fn(&TrafficCounters) -> &AtomicU64,
+    ) -> Self {
+        Self {
+            inner,
+            counters,
+            tally,
+        }
+    }
+}
+
+impl<B: Body> Body for Counted<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<std::result::Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let polled = this.inner.poll_frame(cx);
+        if let Poll::Ready(Some(Ok(frame))) = &polled {
+            if let Some(data) = frame.data_ref() {
+                (this.tally)(this.counters).fetch_add(data.remaining() as u64, Ordering::Relaxed);
+            }
+        }
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
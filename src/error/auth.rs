@@ -0,0 +1,246 @@
+//! `WWW-Authenticate` challenges carried by `ApiError::Authentication`
+
+use hyper::header::WWW_AUTHENTICATE;
+use hyper::{HeaderMap, StatusCode};
+use std::collections::HashMap;
+use std::fmt;
+
+/// One challenge from a `WWW-Authenticate` header (RFC 7235)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuthChallenge {
+    /// Auth scheme as sent, e.g. `Bearer`; compare with `is`
+    pub scheme: String,
+    pub realm: Option<String>,
+    /// Parameters other than `realm`, keyed by lowercased name
+    pub params: HashMap<String, String>,
+    /// Opaque data sent instead of parameters (e.g. by `Negotiate`)
+    pub token68: Option<String>,
+}
+
+impl AuthChallenge {
+    /// Whether this challenge uses `scheme`, compared case-insensitively
+    pub fn is(&self, scheme: &str) -> bool {
+        self.scheme.eq_ignore_ascii_case(scheme)
+    }
+
+    /// Parameter `name`, compared case-insensitively
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Every challenge in one header value, in order; malformed trailing data is ignored
+    pub fn parse(value: &str) -> Vec<AuthChallenge> {
+        let mut parser = Parser { rest: value };
+        let mut challenges = Vec::new();
+        loop {
+            parser.skip(|c| c == ',' || c == ' ' || c == '\t');
+            let scheme = parser.word();
+            if scheme.is_empty() {
+                break;
+            }
+            let mut challenge = AuthChallenge {
+                scheme: scheme.to_string(),
+                ..Default::default()
+            };
+            parser.params(&mut challenge);
+            challenges.push(challenge);
+        }
+        challenges
+    }
+
+    /// Challenges from every `WWW-Authenticate` header of a response
+    pub fn from_headers(headers: &HeaderMap) -> Vec<AuthChallenge> {
+        headers
+            .get_all(WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(AuthChallenge::parse)
+            .collect()
+    }
+}
+
+impl fmt::Display for AuthChallenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.scheme)?;
+        match (&self.realm, &self.token68) {
+            (Some(realm), _) => write!(f, " realm={:?}", realm),
+            (None, Some(token68)) => write!(f, " {}", token68),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Cursor over a header value
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn skip(&mut self, pred: impl Fn(char) -> bool) {
+        self.rest = self.rest.trim_start_matches(pred);
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    /// A token, token68 or parameter name
+    fn word(&mut self) -> &'a str {
+        let end = self
+            .rest
+            .find([' ', '\t', ',', '=', '"'])
+            .unwrap_or(self.rest.len());
+        let (word, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        word
+    }
+
+    fn quoted(&mut self) -> String {
+        let mut value = String::new();
+        let mut chars = self.rest[1..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.rest = &self.rest[i + 2..];
+                    return value;
+                }
+                '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                c => value.push(c),
+            }
+        }
+        self.rest = "";
+        value
+    }
+
+    /// Parameters (or a token68) following a scheme, stopping before the next scheme
+    fn params(&mut self, challenge: &mut AuthChallenge) {
+        let mut first = true;
+        loop {
+            self.skip(|c| c == ' ' || c == '\t');
+            let start = self.rest;
+            let name = self.word();
+            if name.is_empty() {
+                return;
+            }
+            self.skip(|c| c == ' ' || c == '\t');
+            if self.peek() != Some('=') {
+                if first && matches!(self.peek(), None | Some(',')) {
+                    challenge.token68 = Some(name.to_string());
+                } else {
+                    // `name` is the scheme of the next challenge
+                    self.rest = start;
+                }
+                return;
+            }
+            self.rest = &self.rest[1..];
+            self.skip(|c| c == ' ' || c == '\t');
+            let value = match self.peek() {
+                Some('"') => self.quoted(),
+                Some(c) if c != ',' && c != '=' => self.word().to_string(),
+                _ => {
+                    // Padding of a token68 such as `abc==`
+                    self.skip(|c| c == '=');
+                    if first {
+                        challenge.token68 = Some(
+                            start[..start.len() - self.rest.len()]
+                                .trim_end()
+                                .to_string(),
+                        );
+                    }
+                    return;
+                }
+            };
+            let name = name.to_ascii_lowercase();
+            if name == "realm" {
+                challenge.realm = Some(value);
+            } else {
+                challenge.params.insert(name, value);
+            }
+            first = false;
+            self.skip(|c| c == ' ' || c == '\t');
+            if self.peek() != Some(',') {
+                return;
+            }
+            self.rest = &self.rest[1..];
+        }
+    }
+}
+
+/// Context carried by `ApiError::Authentication`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthDetail {
+    pub status: StatusCode,
+    /// What the server asked for, in header order (empty if it sent no challenge)
+    pub challenges: Vec<AuthChallenge>,
+    /// Up to `client.max_error_body` bytes of the response body, as on `ApiError::HttpStatus`
+    pub body: String,
+}
+
+impl AuthDetail {
+    /// Build from a rejected response's status and headers, without its body
+    pub fn from_response(status: StatusCode, headers: &HeaderMap) -> Self {
+        Self {
+            status,
+            challenges: AuthChallenge::from_headers(headers),
+            body: String::new(),
+        }
+    }
+
+    /// The challenge for `scheme`, if the server offered it
+    pub fn challenge(&self, scheme: &str) -> Option<&AuthChallenge> {
+        self.challenges
+            .iter()
+            .find(|challenge| challenge.is(scheme))
+    }
+}
+
+impl fmt::Display for AuthDetail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HTTP {}", self.status.as_u16())?;
+        for (i, challenge) in self.challenges.iter().enumerate() {
+            f.write_str(if i == 0 { ", server expects " } else { " or " })?;
+            write!(f, "{}", challenge)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multiple_challenges_and_params() {
+        let challenges = AuthChallenge::parse(
+            r#"Basic realm="simple", Bearer realm="api", error="invalid_token", error_description="The \"token\" expired", Negotiate abc+/123=="#,
+        );
+        assert_eq!(challenges.len(), 3);
+        assert!(challenges[0].is("basic"));
+        assert_eq!(challenges[0].realm.as_deref(), Some("simple"));
+        assert!(challenges[0].params.is_empty());
+
+        assert_eq!(challenges[1].scheme, "Bearer");
+        assert_eq!(challenges[1].realm.as_deref(), Some("api"));
+        assert_eq!(challenges[1].param("ERROR"), Some("invalid_token"));
+        assert_eq!(
+            challenges[1].param("error_description"),
+            Some(r#"The "token" expired"#)
+        );
+
+        assert!(challenges[2].is("Negotiate"));
+        assert_eq!(challenges[2].token68.as_deref(), Some("abc+/123=="));
+    }
+
+    #[test]
+    fn test_parse_bare_schemes_and_unquoted_values() {
+        let challenges = AuthChallenge::parse("Negotiate, Digest realm=files , qop=auth,NTLM");
+        let schemes: Vec<&str> = challenges.iter().map(|c| c.scheme.as_str()).collect();
+        assert_eq!(schemes, ["Negotiate", "Digest", "NTLM"]);
+        assert_eq!(challenges[0].token68, None);
+        assert_eq!(challenges[1].realm.as_deref(), Some("files"));
+        assert_eq!(challenges[1].param("qop"), Some("auth"));
+        assert!(AuthChallenge::parse("  ").is_empty());
+    }
+}
@@ -1,48 +1,87 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
+mod auth;
+pub use auth::{AuthChallenge, AuthDetail};
+
 pub type Result<T> = std::result::Result<T, ApiError>;
 
 #[derive(Error, Debug)]
 pub enum ApiError {
     #[error("HTTP error: {0}")]
     Http(#[from] hyper::Error),
-    
-    #[error("HTTP status error: {status}")]
-    HttpStatus { status: hyper::StatusCode },
-    
+
+    #[error("Connection error: {0}")]
+    Connect(String),
+
+    #[error("DNS resolution failed for {host}: {reason}")]
+    Dns { host: String, reason: String },
+
+    /// A non-2xx response, with its headers and (up to `client.max_error_body`
+    /// bytes of) its body, which usually says why the request failed
+    #[error("HTTP status error: {status}{}", first_line(body))]
+    HttpStatus {
+        status: hyper::StatusCode,
+        body: String,
+        headers: HashMap<String, String>,
+    },
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
-    
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
-    
+
     #[error("Configuration error: {0}")]
     Config(String),
-    
+
+    #[error("Client configuration error: {0}")]
+    Build(#[from] BuildError),
+
+    #[error("Validation error: {0}")]
+    Validation(String),
+
     #[error("Plugin error: {0}")]
     Plugin(String),
-    
+
     #[error("Storage error: {0}")]
     Storage(String),
-    
+
     #[error("Stream error: {0}")]
     Stream(String),
-    
+
     #[error("URL parse error: {0}")]
     UrlParse(#[from] url::ParseError),
-    
-    #[error("Timeout error")]
-    Timeout,
-    
+
+    #[error("{0}")]
+    Timeout(TimeoutDetail),
+
     #[error("Invalid response format")]
     InvalidResponse,
-    
+
+    #[error("Empty response body (HTTP {status})")]
+    EmptyResponse { status: hyper::StatusCode },
+
     #[error("Authentication error: {0}")]
-    Authentication(String),
-    
+    Authentication(AuthDetail),
+
     #[error("Rate limit exceeded")]
     RateLimit,
-    
+
+    #[error("Request cancelled")]
+    Cancelled,
+
+    /// A 412 reply to a conditional request, with (up to `client.max_error_body`
+    /// bytes of) its body
+    #[error("Precondition failed (HTTP 412)")]
+    PreconditionFailed { body: String },
+
+    #[error("Gave up on {url} after {limit} redirects and retries combined (max_total_hops)")]
+    TooManyHops { url: String, limit: usize },
+
     #[error("Unknown error: {0}")]
     Other(String),
 }
@@ -51,20 +90,345 @@ impl ApiError {
     pub fn config(msg: impl Into<String>) -> Self {
         Self::Config(msg.into())
     }
-    
+
+    pub fn validation(msg: impl Into<String>) -> Self {
+        Self::Validation(msg.into())
+    }
+
     pub fn plugin(msg: impl Into<String>) -> Self {
         Self::Plugin(msg.into())
     }
-    
+
     pub fn storage(msg: impl Into<String>) -> Self {
         Self::Storage(msg.into())
     }
-    
+
     pub fn stream(msg: impl Into<String>) -> Self {
         Self::Stream(msg.into())
     }
-    
+
     pub fn other(msg: impl Into<String>) -> Self {
         Self::Other(msg.into())
     }
-}
\ No newline at end of file
+
+    /// `HttpStatus` for a response whose body and headers were not kept
+    pub fn http_status(status: hyper::StatusCode) -> Self {
+        Self::HttpStatus {
+            status,
+            body: String::new(),
+            headers: HashMap::new(),
+        }
+    }
+
+    /// Connection failure from the HTTP client, keeping the underlying causes in the message.
+    ///
+    /// A failure to resolve the host anywhere in the chain becomes `ApiError::Dns`.
+    pub fn connect(error: &(dyn std::error::Error + 'static)) -> Self {
+        let mut cause = Some(error);
+        while let Some(current) = cause {
+            if let Some(failure) = current.downcast_ref::<ResolveFailure>() {
+                return Self::Dns {
+                    host: failure.host.clone(),
+                    reason: failure.reason.clone(),
+                };
+            }
+            cause = current.source();
+        }
+
+        let mut message = error.to_string();
+        let mut source = error.source();
+        while let Some(cause) = source {
+            message.push_str(": ");
+            message.push_str(&cause.to_string());
+            source = cause.source();
+        }
+        Self::Connect(message)
+    }
+
+    /// Stable short code identifying the error variant (for events and logs)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Http(_) => "http",
+            Self::Connect(_) => "connect",
+            Self::Dns { .. } => "dns",
+            Self::HttpStatus { .. } => "http_status",
+            Self::Serialization(_) => "serialization",
+            Self::Io(_) => "io",
+            Self::Config(_) => "config",
+            Self::Build(_) => "build",
+            Self::Validation(_) => "validation",
+            Self::Plugin(_) => "plugin",
+            Self::Storage(_) => "storage",
+            Self::Stream(_) => "stream",
+            Self::UrlParse(_) => "url_parse",
+            Self::Timeout(_) => "timeout",
+            Self::InvalidResponse => "invalid_response",
+            Self::EmptyResponse { .. } => "empty_response",
+            Self::Authentication(_) => "authentication",
+            Self::RateLimit => "rate_limit",
+            Self::Cancelled => "cancelled",
+            Self::PreconditionFailed { .. } => "precondition_failed",
+            Self::TooManyHops { .. } => "too_many_hops",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// Broad category of the failure, for callers that branch on kind rather than variant
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Config(_) | Self::Build(_) | Self::Validation(_) | Self::UrlParse(_) => {
+                ErrorKind::Validation
+            }
+            Self::Http(_) | Self::Connect(_) | Self::Dns { .. } => ErrorKind::Network,
+            Self::Timeout(_) => ErrorKind::Timeout,
+            Self::HttpStatus { status, .. } if status.is_client_error() => ErrorKind::ClientStatus,
+            Self::HttpStatus { status, .. } if status.is_server_error() => ErrorKind::ServerStatus,
+            Self::PreconditionFailed { .. } | Self::Authentication(_) | Self::RateLimit => {
+                ErrorKind::ClientStatus
+            }
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// `": <first line>"` of an error body for `Display`, or nothing when it is blank
+fn first_line(body: &str) -> String {
+    const MAX_CHARS: usize = 200;
+    let line = body.lines().map(str::trim).find(|line| !line.is_empty());
+    match line {
+        Some(line) if line.chars().count() > MAX_CHARS => {
+            format!(": {}…", line.chars().take(MAX_CHARS).collect::<String>())
+        }
+        Some(line) => format!(": {}", line),
+        None => String::new(),
+    }
+}
+
+/// Misconfiguration detected while building an `ApiClient`
+#[derive(Error, Debug)]
+pub enum BuildError {
+    /// A custom, default, `Host` or `User-Agent` header is not a valid header
+    #[error("Invalid header {name}: {reason}")]
+    InvalidHeader { name: String, reason: String },
+
+    /// `client.base_url` is not an absolute http(s) URL
+    #[error("Invalid base_url {url}: {reason}")]
+    InvalidBaseUrl { url: String, reason: String },
+
+    /// `client.http_proxy` or `client.https_proxy` is not an http:// proxy URL
+    #[error("Invalid proxy {url}: {reason}")]
+    InvalidProxy { url: String, reason: String },
+
+    /// `client.accept_encodings` names a coding the client cannot decode
+    #[error("Unsupported accept encoding {encoding}: expected gzip or deflate")]
+    UnsupportedEncoding { encoding: String },
+
+    /// More than one of `with_basic_auth`, `with_bearer_token` and
+    /// `with_bearer_token_file` was used on the same builder
+    #[error("Conflicting authentication: {requested} auth requested after {existing} auth was already set")]
+    ConflictingAuth {
+        existing: &'static str,
+        requested: &'static str,
+    },
+
+    /// The bearer token file is missing, unreadable or empty
+    #[error("Cannot read bearer token file {}: {reason}", path.display())]
+    TokenFile { path: PathBuf, reason: String },
+
+    /// A configured TLS certificate or key file could not be read
+    #[error("Cannot read TLS {what} {}: {reason}", path.display())]
+    MissingCertFile {
+        what: &'static str,
+        path: PathBuf,
+        reason: String,
+    },
+
+    /// A TLS certificate or key file was read but is not usable
+    #[error("Invalid TLS {what} {}: {reason}", path.display())]
+    InvalidCertificate {
+        what: &'static str,
+        path: PathBuf,
+        reason: String,
+    },
+
+    /// Only one of `client_cert` and `client_key` is set
+    #[error("TLS client_cert and client_key must be set together")]
+    IncompleteClientIdentity,
+
+    /// The TLS settings need a backend other than the compiled-in one
+    #[error("{setting} requires the `rustls` TLS backend, but this build uses {backend}")]
+    UnsupportedTlsBackend {
+        setting: &'static str,
+        backend: &'static str,
+    },
+
+    /// The TLS backend itself failed to initialize
+    #[error("Failed to initialize TLS: {0}")]
+    Tls(String),
+}
+
+/// Resolution failure handed through the connector, turned into `ApiError::Dns` by `ApiError::connect`
+#[derive(Error, Debug)]
+#[error("failed to resolve {host}: {reason}")]
+pub(crate) struct ResolveFailure {
+    pub(crate) host: String,
+    pub(crate) reason: String,
+}
+
+/// Category of an `ApiError`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// Invalid input or configuration; retrying will not help
+    Validation,
+    /// Connection-level failure (DNS, refused, reset, TLS)
+    Network,
+    /// The request or a body read ran out of time
+    Timeout,
+    /// The server rejected the request (HTTP 4xx)
+    ClientStatus,
+    /// The server failed to handle the request (HTTP 5xx)
+    ServerStatus,
+    /// Local I/O, storage, plugin, malformed responses and anything else
+    Other,
+}
+
+/// Part of a request that ran out of time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutPhase {
+    /// Sending the request and waiting for response headers
+    Request,
+    /// Waiting for the next chunk of a response body
+    Read,
+    /// A whole operation, including retries, exceeded its deadline
+    Total,
+}
+
+impl fmt::Display for TimeoutPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request => f.write_str("request"),
+            Self::Read => f.write_str("read"),
+            Self::Total => f.write_str("total"),
+        }
+    }
+}
+
+/// Context carried by `ApiError::Timeout`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeoutDetail {
+    pub phase: TimeoutPhase,
+    pub elapsed: Duration,
+    pub limit: Duration,
+    pub method: Option<String>,
+    pub url: Option<String>,
+}
+
+impl TimeoutDetail {
+    pub fn new(phase: TimeoutPhase, elapsed: Duration, limit: Duration) -> Self {
+        Self {
+            phase,
+            elapsed,
+            limit,
+            method: None,
+            url: None,
+        }
+    }
+
+    /// Attach the request the timeout happened on
+    pub fn for_request(mut self, method: impl Into<String>, url: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self.url = Some(url.into());
+        self
+    }
+}
+
+impl fmt::Display for TimeoutDetail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} timeout after {} (limit {})",
+            self.phase,
+            format_duration(self.elapsed),
+            format_duration(self.limit)
+        )?;
+        match (&self.method, &self.url) {
+            (Some(method), Some(url)) => write!(f, " for {} {}", method, url),
+            (None, Some(url)) => write!(f, " for {}", url),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Whole seconds when exact, otherwise milliseconds below a second, else tenths
+fn format_duration(duration: Duration) -> String {
+    if duration.subsec_nanos() == 0 {
+        format!("{}s", duration.as_secs())
+    } else if duration < Duration::from_secs(1) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeout_display_includes_phase_elapsed_and_limit() {
+        let error = ApiError::Timeout(
+            TimeoutDetail::new(
+                TimeoutPhase::Read,
+                Duration::from_secs(30),
+                Duration::from_secs(30),
+            )
+            .for_request("GET", "https://api.example.com/data"),
+        );
+        assert_eq!(
+            error.to_string(),
+            "read timeout after 30s (limit 30s) for GET https://api.example.com/data"
+        );
+    }
+
+    #[test]
+    fn test_kind_groups_status_codes() {
+        let status = |code| ApiError::http_status(hyper::StatusCode::from_u16(code).unwrap());
+        assert_eq!(status(404).kind(), ErrorKind::ClientStatus);
+        assert_eq!(status(503).kind(), ErrorKind::ServerStatus);
+        assert_eq!(status(302).kind(), ErrorKind::Other);
+        assert_eq!(
+            ApiError::PreconditionFailed {
+                body: String::new()
+            }
+            .kind(),
+            ErrorKind::ClientStatus
+        );
+        assert_eq!(
+            ApiError::validation("bad header").kind(),
+            ErrorKind::Validation
+        );
+    }
+
+    #[test]
+    fn test_timeout_display_formats_fractional_durations() {
+        let detail = TimeoutDetail::new(
+            TimeoutPhase::Request,
+            Duration::from_millis(1520),
+            Duration::from_millis(1500),
+        );
+        assert_eq!(
+            detail.to_string(),
+            "request timeout after 1.5s (limit 1.5s)"
+        );
+
+        let detail = TimeoutDetail::new(
+            TimeoutPhase::Request,
+            Duration::from_millis(250),
+            Duration::from_millis(200),
+        );
+        assert_eq!(
+            detail.to_string(),
+            "request timeout after 250ms (limit 200ms)"
+        );
+    }
+}
@@ -1,25 +1,40 @@
 // Progressive re-enabling of modules using driver patterns
-pub mod client;  // Phase 1: Re-enabling with driver HTTP patterns
+pub mod client; // Phase 1: Re-enabling with driver HTTP patterns
 pub mod config;
+pub mod driver;
 pub mod error;
-pub mod plugin;  // Phase 1: Re-enabled with driver patterns
-pub mod sec;     // Security helpers and validators
+pub mod models; // Typed responses of the public test APIs
+pub mod plugin; // Phase 1: Re-enabled with driver patterns
+pub mod sec; // Security helpers and validators
 pub mod storage; // Phase 2: Re-enabling sophisticated storage features
 pub mod streaming; // Phase 2: Re-enabling streaming infrastructure
-pub mod driver;
+pub mod testing; // Fuzz/property-test drivers for stream adapters
 
-pub use client::{ApiClient, ApiClientBuilder};
+#[cfg(feature = "archive")]
+pub use client::ArchiveFormat;
+pub use client::{
+    ApiClient, ApiClientBuilder, BatchResult, BodyChunk, BodyCodec, BodyExpectation, ByteRange,
+    Capabilities, ClientEvent, ConnectionEvent, CookieJar, JsonRpcError, JsonRpcResponse,
+    MultipartForm, Priority, RequestOptions, Resolver, Response, ResponseContent, RetryPredicate,
+    SerializerRegistry, StaticResolver, TrafficStats,
+};
 pub use config::Config;
-pub use error::{ApiError, Result};
-pub use plugin::{Plugin, PluginManager, LoggingPlugin};
+pub use error::{ApiError, AuthChallenge, AuthDetail, BuildError, ErrorKind, Result};
+pub use plugin::{
+    ContentTypeGuardPlugin, LatencyPlugin, LoggingPlugin, MetricsPlugin, Plugin, PluginManager,
+    TraceContextPlugin,
+};
 // pub use storage::StorageManager;
-// pub use streaming::StreamHandler;
+pub use streaming::StreamHandler;
 
 /// Re-export commonly used types
 pub mod prelude {
-    pub use crate::{ApiClient, ApiClientBuilder, Config, ApiError, Result, Plugin, PluginManager, LoggingPlugin};
-    // pub use crate::{StorageManager, StreamHandler};
+    pub use crate::{
+        ApiClient, ApiClientBuilder, ApiError, Config, LoggingPlugin, Plugin, PluginManager,
+        Priority, Result, StreamHandler,
+    };
+    // pub use crate::StorageManager;
     pub use async_trait::async_trait;
     pub use serde::{Deserialize, Serialize};
     pub use std::collections::HashMap;
-}
\ No newline at end of file
+}
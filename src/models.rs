@@ -0,0 +1,75 @@
+//! Typed responses of the public APIs exercised by the `test_*` binaries
+//!
+//! Deserializing into these instead of indexing `serde_json::Value` shows typed
+//! usage of `download_json` and fails loudly if an API changes shape.
+
+use serde::Deserialize;
+
+/// `https://api.ipify.org/?format=json`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct IpifyResponse {
+    pub ip: String,
+}
+
+/// `https://dog.ceo/api/breeds/image/random`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DogApiResponse {
+    /// Image URL on success, error description otherwise
+    pub message: String,
+    pub status: String,
+}
+
+impl DogApiResponse {
+    pub fn is_success(&self) -> bool {
+        self.status == "success"
+    }
+}
+
+/// One entry of `https://official-joke-api.appspot.com/jokes/ten`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Joke {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub category: String,
+    pub setup: String,
+    pub punchline: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_ipify() {
+        let ip: IpifyResponse = serde_json::from_str(r#"{"ip":"203.0.113.7"}"#).unwrap();
+        assert_eq!(ip.ip, "203.0.113.7");
+    }
+
+    #[test]
+    fn test_deserialize_dog_api() {
+        let dog: DogApiResponse = serde_json::from_str(
+            r#"{"message":"https://images.dog.ceo/breeds/hound-afghan/n02088094_1003.jpg","status":"success"}"#,
+        )
+        .unwrap();
+        assert!(dog.is_success());
+        assert!(dog.message.ends_with(".jpg"));
+    }
+
+    #[test]
+    fn test_deserialize_jokes() {
+        let jokes: Vec<Joke> = serde_json::from_str(
+            r#"[{"type":"general","setup":"Why?","punchline":"Because.","id":42}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            jokes,
+            [Joke {
+                id: 42,
+                category: "general".to_string(),
+                setup: "Why?".to_string(),
+                punchline: "Because.".to_string(),
+            }]
+        );
+        assert!(serde_json::from_str::<Joke>(r#"{"setup":"Missing the rest"}"#).is_err());
+    }
+}
@@ -0,0 +1,288 @@
+//! Request latency percentiles
+//!
+//! `LatencyHistogram` is a small HDR-style histogram: durations are kept in
+//! microseconds, exactly below 128µs and in log-linear buckets above, so every
+//! reported value is within 1/64 (~1.6%) of a recorded one at any magnitude.
+
+use super::{Plugin, PluginContext, PluginHook};
+use crate::error::Result;
+use async_trait::async_trait;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Values below this are recorded exactly; above it each power of two is split in `HALF_BUCKET` buckets
+const SUB_BUCKET_COUNT: u64 = 128;
+const HALF_BUCKET: u64 = SUB_BUCKET_COUNT / 2;
+const SUB_BUCKET_BITS: u32 = SUB_BUCKET_COUNT.trailing_zeros();
+
+/// Histogram of durations with bounded relative error
+#[derive(Debug, Clone, Default)]
+pub struct LatencyHistogram {
+    counts: Vec<u64>,
+    total: u64,
+    min: u64,
+    max: u64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one duration
+    pub fn record(&mut self, duration: Duration) {
+        let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        let index = bucket_index(micros);
+        if index >= self.counts.len() {
+            self.counts.resize(index + 1, 0);
+        }
+        self.counts[index] += 1;
+        self.min = if self.total == 0 {
+            micros
+        } else {
+            self.min.min(micros)
+        };
+        self.max = self.max.max(micros);
+        self.total += 1;
+    }
+
+    /// Number of recorded durations
+    pub fn count(&self) -> u64 {
+        self.total
+    }
+
+    /// Smallest recorded duration
+    pub fn min(&self) -> Option<Duration> {
+        (self.total > 0).then(|| Duration::from_micros(self.min))
+    }
+
+    /// Largest recorded duration
+    pub fn max(&self) -> Option<Duration> {
+        (self.total > 0).then(|| Duration::from_micros(self.max))
+    }
+
+    /// Duration at or below which `percentile` percent (0–100) of recordings fall
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.total == 0 {
+            return None;
+        }
+        let fraction = percentile.clamp(0.0, 100.0) / 100.0;
+        let rank = ((fraction * self.total as f64).ceil() as u64).max(1);
+
+        let mut seen = 0;
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let value = bucket_highest(index).clamp(self.min, self.max);
+                return Some(Duration::from_micros(value));
+            }
+        }
+        self.max()
+    }
+
+    pub fn p50(&self) -> Option<Duration> {
+        self.percentile(50.0)
+    }
+
+    pub fn p95(&self) -> Option<Duration> {
+        self.percentile(95.0)
+    }
+
+    pub fn p99(&self) -> Option<Duration> {
+        self.percentile(99.0)
+    }
+}
+
+fn bucket_index(value: u64) -> usize {
+    if value < SUB_BUCKET_COUNT {
+        return value as usize;
+    }
+    // Shift that brings `value` into [HALF_BUCKET, SUB_BUCKET_COUNT)
+    let shift = (63 - value.leading_zeros()) - (SUB_BUCKET_BITS - 1);
+    (SUB_BUCKET_COUNT + (shift as u64 - 1) * HALF_BUCKET + ((value >> shift) - HALF_BUCKET))
+        as usize
+}
+
+/// Largest value that lands in bucket `index`
+fn bucket_highest(index: usize) -> u64 {
+    let index = index as u64;
+    if index < SUB_BUCKET_COUNT {
+        return index;
+    }
+    let shift = (index - SUB_BUCKET_COUNT) / HALF_BUCKET + 1;
+    let sub_bucket = (index - SUB_BUCKET_COUNT) % HALF_BUCKET + HALF_BUCKET;
+    (sub_bucket << shift) | ((1 << shift) - 1)
+}
+
+/// Records how long each request took to get its response headers.
+///
+/// Register a shared handle and read percentiles from it while the client runs:
+///
+/// ```ignore
+/// let latency = Arc::new(LatencyPlugin::new());
+/// plugin_manager.register_plugin(latency.clone())?;
+/// // ... requests ...
+/// println!("p95: {:?}", latency.p95());
+/// ```
+#[derive(Debug, Default)]
+pub struct LatencyPlugin {
+    histogram: Mutex<LatencyHistogram>,
+}
+
+impl LatencyPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one duration, as the plugin does for every completed request
+    pub fn record(&self, duration: Duration) {
+        self.histogram.lock().unwrap().record(duration);
+    }
+
+    /// Copy of everything recorded so far
+    pub fn snapshot(&self) -> LatencyHistogram {
+        self.histogram.lock().unwrap().clone()
+    }
+
+    pub fn count(&self) -> u64 {
+        self.histogram.lock().unwrap().count()
+    }
+
+    pub fn p50(&self) -> Option<Duration> {
+        self.histogram.lock().unwrap().p50()
+    }
+
+    pub fn p95(&self) -> Option<Duration> {
+        self.histogram.lock().unwrap().p95()
+    }
+
+    pub fn p99(&self) -> Option<Duration> {
+        self.histogram.lock().unwrap().p99()
+    }
+}
+
+#[async_trait]
+impl Plugin for LatencyPlugin {
+    fn name(&self) -> &str {
+        "latency"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Collects request latency percentiles"
+    }
+
+    fn handles_hook(&self, hook: &PluginHook) -> bool {
+        matches!(hook, PluginHook::PostRequest)
+    }
+
+    async fn handle_post_request(
+        &self,
+        _url: &str,
+        _status: u16,
+        context: &PluginContext,
+    ) -> Result<()> {
+        if let Some(elapsed_ms) = context.metadata.get("elapsed_ms").and_then(|v| v.as_f64()) {
+            self.record(Duration::from_secs_f64(elapsed_ms.max(0.0) / 1000.0));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Option<Duration>, expected_ms: u64) {
+        let actual = actual.unwrap().as_micros() as f64;
+        let expected = (expected_ms * 1000) as f64;
+        assert!(
+            (actual - expected).abs() / expected <= 1.0 / 64.0,
+            "{}µs is not within 1/64 of {}µs",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_percentiles_of_synthetic_durations() {
+        let mut histogram = LatencyHistogram::new();
+        assert_eq!(histogram.p50(), None);
+
+        for ms in 1..=1000 {
+            histogram.record(Duration::from_millis(ms));
+        }
+        assert_eq!(histogram.count(), 1000);
+        assert_close(histogram.p50(), 500);
+        assert_close(histogram.p95(), 950);
+        assert_close(histogram.p99(), 990);
+        assert_eq!(
+            histogram.percentile(100.0),
+            Some(Duration::from_millis(1000))
+        );
+        assert_close(histogram.percentile(0.0), 1);
+    }
+
+    #[test]
+    fn test_small_values_are_exact_and_outliers_show_in_tail() {
+        let mut histogram = LatencyHistogram::new();
+        for _ in 0..98 {
+            histogram.record(Duration::from_micros(40));
+        }
+        histogram.record(Duration::from_secs(2));
+        histogram.record(Duration::from_secs(3));
+
+        assert_eq!(histogram.p50(), Some(Duration::from_micros(40)));
+        assert_eq!(histogram.p95(), Some(Duration::from_micros(40)));
+        assert_close(histogram.p99(), 2000);
+        assert_eq!(histogram.max(), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn test_bucket_bounds_contain_their_values() {
+        for value in [
+            0,
+            1,
+            127,
+            128,
+            129,
+            255,
+            256,
+            1_000,
+            65_537,
+            10_000_000,
+            u64::MAX,
+        ] {
+            let index = bucket_index(value);
+            assert!(bucket_highest(index) >= value, "{}", value);
+            if index > 0 {
+                assert!(bucket_highest(index - 1) < value, "{}", value);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plugin_records_elapsed_from_context() {
+        let plugin = LatencyPlugin::new();
+        for ms in [10.0, 20.0, 30.0] {
+            let context = PluginContext::new(PluginHook::PostRequest)
+                .with_metadata("elapsed_ms", serde_json::json!(ms));
+            plugin
+                .handle_post_request("https://example.com", 200, &context)
+                .await
+                .unwrap();
+        }
+        // Requests without timing are ignored
+        let untimed = PluginContext::new(PluginHook::PostRequest);
+        plugin
+            .handle_post_request("https://example.com", 200, &untimed)
+            .await
+            .unwrap();
+
+        assert_eq!(plugin.count(), 3);
+        assert_close(plugin.p50(), 20);
+    }
+}
@@ -0,0 +1,297 @@
+//! Request counters and latency histogram in Prometheus text format
+//!
+//! `MetricsPlugin` counts responses by host and status and buckets their
+//! latency by host; `render_prometheus` writes everything in the text
+//! exposition format (version 0.0.4), ready to serve from a `/metrics` endpoint.
+
+use super::{Plugin, PluginContext, PluginHook};
+use crate::error::{ApiError, Result};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Upper bounds of the latency histogram buckets, in seconds
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Latency buckets of one host; `counts[i]` holds durations up to `LATENCY_BUCKETS[i]`,
+/// the last slot everything slower
+#[derive(Debug, Clone, Default)]
+struct HostLatency {
+    counts: [u64; LATENCY_BUCKETS.len() + 1],
+    sum: Duration,
+}
+
+#[derive(Debug, Default)]
+struct Metrics {
+    /// Keyed by (host, status)
+    requests: BTreeMap<(String, u16), u64>,
+    errors: u64,
+    latency: BTreeMap<String, HostLatency>,
+}
+
+/// Counts requests and their latency for scraping by Prometheus.
+///
+/// Register a shared handle and serve `render_prometheus()` from the application:
+///
+/// ```ignore
+/// let metrics = Arc::new(MetricsPlugin::new().with_prefix("myapp_http").with_label("service", "billing"));
+/// plugin_manager.register_plugin(metrics.clone())?;
+/// // ... requests ...
+/// let body = metrics.render_prometheus();
+/// ```
+#[derive(Debug)]
+pub struct MetricsPlugin {
+    prefix: String,
+    labels: Vec<(String, String)>,
+    metrics: Mutex<Metrics>,
+}
+
+impl Default for MetricsPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsPlugin {
+    /// Metrics named `kick_*` without extra labels
+    pub fn new() -> Self {
+        Self {
+            prefix: "kick".to_string(),
+            labels: Vec::new(),
+            metrics: Mutex::default(),
+        }
+    }
+
+    /// Start every metric name with `prefix` instead of `kick`; characters not
+    /// allowed in metric names become `_`
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = sanitize_name(&prefix.into());
+        self
+    }
+
+    /// Add a label with a fixed value to every series, e.g. the service or region
+    pub fn with_label(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels
+            .push((sanitize_name(&name.into()), value.into()));
+        self
+    }
+
+    /// Count one response, as the plugin does for every completed request
+    pub fn record(&self, host: &str, status: u16, elapsed: Duration) {
+        let mut metrics = self.metrics.lock().unwrap();
+        *metrics
+            .requests
+            .entry((host.to_string(), status))
+            .or_default() += 1;
+
+        let latency = metrics.latency.entry(host.to_string()).or_default();
+        let seconds = elapsed.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        latency.counts[bucket] += 1;
+        latency.sum += elapsed;
+    }
+
+    /// Responses counted so far, over every host and status
+    pub fn request_count(&self) -> u64 {
+        self.metrics.lock().unwrap().requests.values().sum()
+    }
+
+    /// Requests that failed without a response
+    pub fn error_count(&self) -> u64 {
+        self.metrics.lock().unwrap().errors
+    }
+
+    /// Everything recorded so far in the Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let metrics = self.metrics.lock().unwrap();
+        let prefix = &self.prefix;
+        let mut out = String::new();
+
+        let name = format!("{}_requests_total", prefix);
+        header(
+            &mut out,
+            &name,
+            "Requests that received a response, by host and status",
+            "counter",
+        );
+        for ((host, status), count) in &metrics.requests {
+            let labels = self.labels(&[("host", host), ("status", &status.to_string())]);
+            let _ = writeln!(out, "{}{} {}", name, labels, count);
+        }
+
+        let name = format!("{}_request_errors_total", prefix);
+        header(
+            &mut out,
+            &name,
+            "Requests that failed without a response",
+            "counter",
+        );
+        let _ = writeln!(out, "{}{} {}", name, self.labels(&[]), metrics.errors);
+
+        let name = format!("{}_request_duration_seconds", prefix);
+        header(
+            &mut out,
+            &name,
+            "Time until response headers arrived, by host",
+            "histogram",
+        );
+        for (host, latency) in &metrics.latency {
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(&latency.counts) {
+                cumulative += count;
+                let labels = self.labels(&[("host", host), ("le", &bound.to_string())]);
+                let _ = writeln!(out, "{}_bucket{} {}", name, labels, cumulative);
+            }
+            cumulative += latency.counts[LATENCY_BUCKETS.len()];
+            let labels = self.labels(&[("host", host), ("le", "+Inf")]);
+            let _ = writeln!(out, "{}_bucket{} {}", name, labels, cumulative);
+
+            let labels = self.labels(&[("host", host)]);
+            let _ = writeln!(out, "{}_sum{} {}", name, labels, latency.sum.as_secs_f64());
+            let _ = writeln!(out, "{}_count{} {}", name, labels, cumulative);
+        }
+        out
+    }
+
+    /// `{name="value",...}` with the fixed labels first, empty when there are none
+    fn labels(&self, series: &[(&str, &str)]) -> String {
+        let pairs: Vec<String> = self
+            .labels
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .chain(series.iter().copied())
+            .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+            .collect();
+        if pairs.is_empty() {
+            String::new()
+        } else {
+            format!("{{{}}}", pairs.join(","))
+        }
+    }
+}
+
+fn header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Metric and label names allow `[a-zA-Z_:][a-zA-Z0-9_:]*`
+fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.is_empty() || sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[async_trait]
+impl Plugin for MetricsPlugin {
+    fn name(&self) -> &str {
+        "metrics"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Counts requests and latency for Prometheus"
+    }
+
+    fn handles_hook(&self, hook: &PluginHook) -> bool {
+        matches!(hook, PluginHook::PostRequest | PluginHook::OnError)
+    }
+
+    async fn handle_post_request(
+        &self,
+        url: &str,
+        status: u16,
+        context: &PluginContext,
+    ) -> Result<()> {
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let elapsed_ms = context
+            .metadata
+            .get("elapsed_ms")
+            .and_then(|v| v.as_f64())
+            .unwrap_or_default();
+        self.record(
+            &host,
+            status,
+            Duration::from_secs_f64(elapsed_ms.max(0.0) / 1000.0),
+        );
+        Ok(())
+    }
+
+    async fn handle_error(&self, _error: &ApiError, _context: &PluginContext) -> Result<()> {
+        self.metrics.lock().unwrap().errors += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let metrics = MetricsPlugin::new();
+        metrics.record("api.example.com", 200, Duration::from_millis(3));
+        metrics.record("api.example.com", 200, Duration::from_millis(40));
+        metrics.record("api.example.com", 503, Duration::from_secs(30));
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("kick_requests_total{host=\"api.example.com\",status=\"200\"} 2\n"));
+        assert!(text.contains("kick_requests_total{host=\"api.example.com\",status=\"503\"} 1\n"));
+        assert!(text.contains(
+            "kick_request_duration_seconds_bucket{host=\"api.example.com\",le=\"0.005\"} 1\n"
+        ));
+        assert!(text.contains(
+            "kick_request_duration_seconds_bucket{host=\"api.example.com\",le=\"0.05\"} 2\n"
+        ));
+        assert!(text.contains(
+            "kick_request_duration_seconds_bucket{host=\"api.example.com\",le=\"10\"} 2\n"
+        ));
+        assert!(text.contains(
+            "kick_request_duration_seconds_bucket{host=\"api.example.com\",le=\"+Inf\"} 3\n"
+        ));
+        assert!(text.contains("kick_request_duration_seconds_count{host=\"api.example.com\"} 3\n"));
+    }
+
+    #[test]
+    fn test_prefix_and_labels_are_sanitized_and_escaped() {
+        let metrics = MetricsPlugin::new()
+            .with_prefix("my-app.http")
+            .with_label("team name", "a \"b\"\\c");
+        let text = metrics.render_prometheus();
+        assert!(text.contains("# TYPE my_app_http_requests_total counter\n"));
+        assert!(
+            text.contains("my_app_http_request_errors_total{team_name=\"a \\\"b\\\"\\\\c\"} 0\n")
+        );
+    }
+}
@@ -2,6 +2,18 @@ use crate::error::{ApiError, Result};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+
+mod latency;
+mod metrics;
+mod trace;
+pub use latency::{LatencyHistogram, LatencyPlugin};
+pub use metrics::MetricsPlugin;
+pub use trace::{TraceContext, TraceContextPlugin, TraceSpan};
+
+/// Metadata key under which later hooks of a request see the headers plugins added
+/// with `Plugin::request_headers`, as an object keyed by lowercased header name
+pub const PLUGIN_HEADERS_KEY: &str = "plugin_headers";
 
 /// Plugin hook points in the request/response lifecycle
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -29,11 +41,21 @@ impl PluginContext {
             metadata: HashMap::new(),
         }
     }
-    
+
     pub fn with_metadata(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
         self.metadata.insert(key.into(), value);
         self
     }
+
+    /// Add every entry of `metadata`, replacing existing keys
+    pub fn with_metadata_map(mut self, metadata: &HashMap<String, serde_json::Value>) -> Self {
+        self.metadata.extend(
+            metadata
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        self
+    }
 }
 
 /// Simplified plugin trait based on driver patterns
@@ -41,128 +63,198 @@ impl PluginContext {
 pub trait Plugin: Send + Sync {
     /// Plugin name
     fn name(&self) -> &str;
-    
+
     /// Plugin version
     fn version(&self) -> &str;
-    
+
     /// Plugin description
     fn description(&self) -> &str;
-    
+
     /// Initialize plugin with configuration
     async fn initialize(&mut self, _config: &serde_json::Value) -> Result<()> {
         Ok(())
     }
-    
+
     /// Check if plugin handles a specific hook
     fn handles_hook(&self, hook: &PluginHook) -> bool {
-        match hook {
-            PluginHook::PreRequest | PluginHook::PostRequest => true,
-            _ => false,
-        }
+        matches!(hook, PluginHook::PreRequest | PluginHook::PostRequest)
     }
-    
+
     /// Handle pre-request processing (simplified - no request mutation for now)
     async fn handle_pre_request(&self, _url: &str, _context: &PluginContext) -> Result<()> {
         // Default implementation - plugins can override
         Ok(())
     }
-    
+
+    /// Headers to add to the outgoing request, asked after every `handle_pre_request` passed.
+    ///
+    /// They override client and per-request headers of the same name, and later
+    /// hooks of the request see them under `context.metadata[PLUGIN_HEADERS_KEY]`.
+    async fn request_headers(
+        &self,
+        _url: &str,
+        _context: &PluginContext,
+    ) -> Result<Vec<(String, String)>> {
+        Ok(Vec::new())
+    }
+
     /// Handle post-request processing
-    async fn handle_post_request(&self, _url: &str, _status: u16, _context: &PluginContext) -> Result<()> {
+    async fn handle_post_request(
+        &self,
+        _url: &str,
+        _status: u16,
+        _context: &PluginContext,
+    ) -> Result<()> {
         // Default implementation - plugins can override
         Ok(())
     }
-    
+
     /// Handle errors
     async fn handle_error(&self, _error: &ApiError, _context: &PluginContext) -> Result<()> {
         Ok(())
     }
-    
-    /// Handle retry attempts
+
+    /// Handle retry attempts, before the client waits to retry.
+    ///
+    /// `context.metadata` holds the wait as `delay_ms` and the failure being
+    /// retried as `reason`.
     async fn handle_retry(&self, _attempt: u32, _context: &PluginContext) -> Result<()> {
         Ok(())
     }
-    
+
     /// Handle pre-response processing (before body is consumed)
     async fn handle_pre_response(&self, _status: u16, _context: &PluginContext) -> Result<()> {
         Ok(())
     }
-    
+
     /// Handle post-response processing (after body is consumed)
     async fn handle_post_response(&self, _body: &str, _context: &PluginContext) -> Result<()> {
         Ok(())
     }
-    
+
     /// Handle streaming data chunks
     async fn handle_stream(&self, _chunk: &[u8], _context: &PluginContext) -> Result<()> {
         Ok(())
     }
+
+    /// Flush or report before the client goes away (called by `ApiClient::shutdown`)
+    async fn finalize(&self) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Plugin manager for registering and executing plugins
 pub struct PluginManager {
     pub plugins: Vec<Arc<dyn Plugin>>,
+    /// `plugins.plugin_settings`, handed to `initialize` by plugin name in `register`
+    settings: HashMap<String, serde_json::Value>,
 }
 
 impl PluginManager {
     pub fn new() -> Self {
         Self {
             plugins: Vec::new(),
+            settings: HashMap::new(),
         }
     }
-    
+
+    /// Settings passed to `Plugin::initialize` by `register`, keyed by plugin name
+    pub fn with_plugin_settings(mut self, settings: HashMap<String, serde_json::Value>) -> Self {
+        self.settings = settings;
+        self
+    }
+
     /// Create PluginManager from configuration
     pub async fn from_config(plugin_config: &crate::config::PluginConfig) -> Result<Self> {
-        let mut manager = Self::new();
-        
+        let mut manager = Self::new().with_plugin_settings(plugin_config.plugin_settings.clone());
+
         for plugin_name in &plugin_config.enabled_plugins {
-            let plugin_settings = plugin_config.plugin_settings
+            let plugin_settings = plugin_config
+                .plugin_settings
                 .get(plugin_name)
                 .cloned()
                 .unwrap_or(serde_json::Value::Null);
-            
+
             // Create built-in plugins based on name
-            let plugin: Arc<dyn Plugin> = match plugin_name.as_str() {
+            match plugin_name.as_str() {
                 "logging" => {
-                    let mut logging_plugin = LoggingPlugin::new();
-                    if let Err(e) = logging_plugin.initialize(&plugin_settings).await {
-                        return Err(ApiError::other(format!("Failed to initialize logging plugin: {}", e)));
-                    }
-                    Arc::new(logging_plugin)
-                },
+                    manager
+                        .register_with(LoggingPlugin::new(), &plugin_settings)
+                        .await?;
+                }
                 "rate_limiter" => {
                     // Extract requests_per_minute from settings
                     let requests_per_minute = plugin_settings
                         .get("requests_per_minute")
                         .and_then(|v| v.as_u64())
                         .unwrap_or(60) as u32;
-                        
-                    let mut rate_limit_plugin = RateLimitPlugin::new(requests_per_minute);
-                    if let Err(e) = rate_limit_plugin.initialize(&plugin_settings).await {
-                        return Err(ApiError::other(format!("Failed to initialize rate_limiter plugin: {}", e)));
-                    }
-                    Arc::new(rate_limit_plugin)
-                },
+
+                    manager
+                        .register_with(RateLimitPlugin::new(requests_per_minute), &plugin_settings)
+                        .await?;
+                }
+                "content_type_guard" => {
+                    manager
+                        .register_with(
+                            ContentTypeGuardPlugin::new(["application/json"]),
+                            &plugin_settings,
+                        )
+                        .await?;
+                }
+                "trace_context" => {
+                    manager
+                        .register_with(TraceContextPlugin::new(), &plugin_settings)
+                        .await?;
+                }
                 unknown => {
                     return Err(ApiError::other(format!("Unknown plugin: {}", unknown)));
                 }
-            };
-            
-            manager.register_plugin(plugin)?;
+            }
         }
-        
+
         Ok(manager)
     }
-    
+
+    /// Initialize `plugin` with its entry in the plugin settings (`null` if absent), then register it
+    pub async fn register<P: Plugin + 'static>(&mut self, plugin: P) -> Result<()> {
+        let settings = self
+            .settings
+            .get(plugin.name())
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+        self.register_with(plugin, &settings).await
+    }
+
+    /// Initialize `plugin` with `settings`, then register it
+    pub async fn register_with<P: Plugin + 'static>(
+        &mut self,
+        mut plugin: P,
+        settings: &serde_json::Value,
+    ) -> Result<()> {
+        if let Err(e) = plugin.initialize(settings).await {
+            return Err(ApiError::other(format!(
+                "Failed to initialize {} plugin: {}",
+                plugin.name(),
+                e
+            )));
+        }
+        self.register_plugin(Arc::new(plugin))
+    }
+
+    /// Register a plugin as-is; `initialize` is not called, so set it up beforehand
     pub fn register_plugin(&mut self, plugin: Arc<dyn Plugin>) -> Result<()> {
         self.plugins.push(plugin);
         Ok(())
     }
-    
+
     /// Execute plugins for pre-request hook
     pub async fn execute_pre_request(&self, url: &str) -> Result<()> {
-        let context = PluginContext::new(PluginHook::PreRequest);
-        
+        self.execute_pre_request_with(url, PluginContext::new(PluginHook::PreRequest))
+            .await
+    }
+
+    /// Execute plugins for pre-request hook with request metadata
+    pub async fn execute_pre_request_with(&self, url: &str, context: PluginContext) -> Result<()> {
         for plugin in &self.plugins {
             if plugin.handles_hook(&PluginHook::PreRequest) {
                 plugin.handle_pre_request(url, &context).await?;
@@ -170,11 +262,35 @@ impl PluginManager {
         }
         Ok(())
     }
-    
+
+    /// Headers every pre-request plugin adds, in execution order (later plugins win on conflicts)
+    pub async fn collect_request_headers_with(
+        &self,
+        url: &str,
+        context: PluginContext,
+    ) -> Result<Vec<(String, String)>> {
+        let mut headers = Vec::new();
+        for plugin in &self.plugins {
+            if plugin.handles_hook(&PluginHook::PreRequest) {
+                headers.extend(plugin.request_headers(url, &context).await?);
+            }
+        }
+        Ok(headers)
+    }
+
     /// Execute plugins for post-request hook
     pub async fn execute_post_request(&self, url: &str, status: u16) -> Result<()> {
-        let context = PluginContext::new(PluginHook::PostRequest);
-        
+        self.execute_post_request_with(url, status, PluginContext::new(PluginHook::PostRequest))
+            .await
+    }
+
+    /// Execute plugins for post-request hook with request metadata
+    pub async fn execute_post_request_with(
+        &self,
+        url: &str,
+        status: u16,
+        context: PluginContext,
+    ) -> Result<()> {
         for plugin in &self.plugins {
             if plugin.handles_hook(&PluginHook::PostRequest) {
                 plugin.handle_post_request(url, status, &context).await?;
@@ -182,31 +298,45 @@ impl PluginManager {
         }
         Ok(())
     }
-    
+
     /// Execute plugins for error hook
     pub async fn execute_error(&self, error: &ApiError) -> Result<()> {
-        let context = PluginContext::new(PluginHook::OnError);
-        
+        self.execute_error_with(error, PluginContext::new(PluginHook::OnError))
+            .await
+    }
+
+    /// Execute plugins for error hook with request metadata
+    pub async fn execute_error_with(&self, error: &ApiError, context: PluginContext) -> Result<()> {
         for plugin in &self.plugins {
             plugin.handle_error(error, &context).await?;
         }
         Ok(())
     }
-    
+
     /// Execute plugins for retry hook
-    pub async fn execute_retry(&self, attempt: u32) -> Result<()> {
-        let context = PluginContext::new(PluginHook::OnRetry);
-        
+    pub async fn execute_retry(&self, attempt: u32, delay: Duration, reason: &str) -> Result<()> {
+        let context = PluginContext::new(PluginHook::OnRetry)
+            .with_metadata("delay_ms", serde_json::json!(delay.as_millis() as u64))
+            .with_metadata("reason", serde_json::json!(reason));
+
         for plugin in &self.plugins {
             plugin.handle_retry(attempt, &context).await?;
         }
         Ok(())
     }
-    
+
     /// Execute plugins for pre-response hook
     pub async fn execute_pre_response(&self, status: u16) -> Result<()> {
-        let context = PluginContext::new(PluginHook::PreResponse);
-        
+        self.execute_pre_response_with(status, PluginContext::new(PluginHook::PreResponse))
+            .await
+    }
+
+    /// Execute plugins for pre-response hook with response metadata (e.g. `content_type`)
+    pub async fn execute_pre_response_with(
+        &self,
+        status: u16,
+        context: PluginContext,
+    ) -> Result<()> {
         for plugin in &self.plugins {
             if plugin.handles_hook(&PluginHook::PreResponse) {
                 plugin.handle_pre_response(status, &context).await?;
@@ -214,11 +344,19 @@ impl PluginManager {
         }
         Ok(())
     }
-    
+
     /// Execute plugins for post-response hook
     pub async fn execute_post_response(&self, body: &str) -> Result<()> {
-        let context = PluginContext::new(PluginHook::PostResponse);
-        
+        self.execute_post_response_with(body, PluginContext::new(PluginHook::PostResponse))
+            .await
+    }
+
+    /// Execute plugins for post-response hook with request metadata
+    pub async fn execute_post_response_with(
+        &self,
+        body: &str,
+        context: PluginContext,
+    ) -> Result<()> {
         for plugin in &self.plugins {
             if plugin.handles_hook(&PluginHook::PostResponse) {
                 plugin.handle_post_response(body, &context).await?;
@@ -226,11 +364,11 @@ impl PluginManager {
         }
         Ok(())
     }
-    
+
     /// Execute plugins for stream hook
     pub async fn execute_stream(&self, chunk: &[u8]) -> Result<()> {
         let context = PluginContext::new(PluginHook::OnStream);
-        
+
         for plugin in &self.plugins {
             if plugin.handles_hook(&PluginHook::OnStream) {
                 plugin.handle_stream(chunk, &context).await?;
@@ -238,6 +376,18 @@ impl PluginManager {
         }
         Ok(())
     }
+
+    /// Finalize every plugin, even if some fail; returns the first failure
+    pub async fn finalize_all(&self) -> Result<()> {
+        let mut first_error = None;
+        for plugin in &self.plugins {
+            if let Err(e) = plugin.finalize().await {
+                tracing::warn!("Plugin {} failed to finalize: {}", plugin.name(), e);
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
 }
 
 impl Default for PluginManager {
@@ -259,16 +409,17 @@ impl RateLimitPlugin {
             last_request_time: std::sync::Mutex::new(None),
         }
     }
-    
+
     async fn enforce_rate_limit(&self) -> Result<()> {
         let now = std::time::Instant::now();
         let sleep_duration = {
             let last_time = self.last_request_time.lock().unwrap();
-            
+
             if let Some(last) = *last_time {
-                let min_interval = std::time::Duration::from_secs_f64(1.0 / self.requests_per_second as f64);
+                let min_interval =
+                    std::time::Duration::from_secs_f64(1.0 / self.requests_per_second as f64);
                 let elapsed = now.duration_since(last);
-                
+
                 if elapsed < min_interval {
                     Some(min_interval - elapsed)
                 } else {
@@ -278,17 +429,17 @@ impl RateLimitPlugin {
                 None
             }
         };
-        
+
         if let Some(duration) = sleep_duration {
             tokio::time::sleep(duration).await;
         }
-        
+
         // Update the last request time
         {
             let mut last_time = self.last_request_time.lock().unwrap();
             *last_time = Some(std::time::Instant::now());
         }
-        
+
         Ok(())
     }
 }
@@ -298,30 +449,81 @@ impl Plugin for RateLimitPlugin {
     fn name(&self) -> &str {
         "rate_limit"
     }
-    
+
     fn version(&self) -> &str {
         "1.0.0"
     }
-    
+
     fn description(&self) -> &str {
         "Rate limits API requests"
     }
-    
+
     fn handles_hook(&self, hook: &PluginHook) -> bool {
         matches!(hook, PluginHook::PreRequest)
     }
-    
+
     async fn handle_pre_request(&self, _url: &str, _context: &PluginContext) -> Result<()> {
         self.enforce_rate_limit().await
     }
 }
 
+/// Default number of body bytes kept when a body is logged
+pub const DEFAULT_BODY_LOG_LIMIT: usize = 2048;
+
+/// Shorten `body` to at most `limit` bytes for logging.
+///
+/// Longer bodies are cut at a character boundary and annotated with
+/// `... (N bytes total)` so the original size is still visible.
+pub fn truncate_for_log(body: &str, limit: usize) -> std::borrow::Cow<'_, str> {
+    if body.len() <= limit {
+        return std::borrow::Cow::Borrowed(body);
+    }
+    let mut end = limit;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    std::borrow::Cow::Owned(format!("{}... ({} bytes total)", &body[..end], body.len()))
+}
+
 /// Basic logging plugin implementation
-pub struct LoggingPlugin;
+///
+/// Response bodies are logged truncated to `body_limit` bytes
+/// (`{"max_body_bytes": N}` in the plugin settings). Retries are logged only
+/// when enabled (`{"log_retries": true}`).
+pub struct LoggingPlugin {
+    body_limit: usize,
+    log_retries: bool,
+}
 
 impl LoggingPlugin {
     pub fn new() -> Self {
-        Self
+        Self {
+            body_limit: DEFAULT_BODY_LOG_LIMIT,
+            log_retries: false,
+        }
+    }
+
+    /// Log at most `limit` bytes of each body
+    pub fn with_body_limit(mut self, limit: usize) -> Self {
+        self.body_limit = limit;
+        self
+    }
+
+    /// Also log each retry with its wait and the failure being retried
+    pub fn with_retry_logging(mut self) -> Self {
+        self.log_retries = true;
+        self
+    }
+
+    /// Body as it appears in the log
+    pub fn format_body<'a>(&self, body: &'a str) -> std::borrow::Cow<'a, str> {
+        truncate_for_log(body, self.body_limit)
+    }
+}
+
+impl Default for LoggingPlugin {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -330,57 +532,274 @@ impl Plugin for LoggingPlugin {
     fn name(&self) -> &str {
         "logging"
     }
-    
+
     fn version(&self) -> &str {
         "1.0.0"
     }
-    
+
     fn description(&self) -> &str {
         "Logs HTTP requests and responses"
     }
-    
+
+    async fn initialize(&mut self, config: &serde_json::Value) -> Result<()> {
+        if let Some(limit) = config.get("max_body_bytes") {
+            let limit = limit.as_u64().ok_or_else(|| {
+                ApiError::plugin("logging max_body_bytes must be a non-negative integer")
+            })?;
+            self.body_limit = limit as usize;
+        }
+        if let Some(enabled) = config.get("log_retries") {
+            self.log_retries = enabled
+                .as_bool()
+                .ok_or_else(|| ApiError::plugin("logging log_retries must be true or false"))?;
+        }
+        Ok(())
+    }
+
+    fn handles_hook(&self, hook: &PluginHook) -> bool {
+        match hook {
+            PluginHook::PreRequest | PluginHook::PostRequest | PluginHook::PostResponse => true,
+            PluginHook::OnRetry => self.log_retries,
+            _ => false,
+        }
+    }
+
     async fn handle_pre_request(&self, url: &str, _context: &PluginContext) -> Result<()> {
         println!("[PLUGIN-LOG] Making request to: {}", url);
         Ok(())
     }
-    
-    async fn handle_post_request(&self, url: &str, status: u16, _context: &PluginContext) -> Result<()> {
+
+    async fn handle_post_request(
+        &self,
+        url: &str,
+        status: u16,
+        _context: &PluginContext,
+    ) -> Result<()> {
         println!("[PLUGIN-LOG] Response from {}: {}", url, status);
         Ok(())
     }
-    
+
+    async fn handle_post_response(&self, body: &str, _context: &PluginContext) -> Result<()> {
+        println!("[PLUGIN-LOG] Response body: {}", self.format_body(body));
+        Ok(())
+    }
+
     async fn handle_error(&self, error: &ApiError, _context: &PluginContext) -> Result<()> {
         println!("[PLUGIN-LOG] Error occurred: {}", error);
         Ok(())
     }
+
+    async fn handle_retry(&self, attempt: u32, context: &PluginContext) -> Result<()> {
+        if !self.log_retries {
+            return Ok(());
+        }
+        println!(
+            "[PLUGIN-LOG] Retry {} in {}ms: {}",
+            attempt,
+            context
+                .metadata
+                .get("delay_ms")
+                .unwrap_or(&serde_json::Value::Null),
+            context
+                .metadata
+                .get("reason")
+                .and_then(|reason| reason.as_str())
+                .unwrap_or("unknown")
+        );
+        Ok(())
+    }
+}
+
+/// Rejects successful responses whose Content-Type is not in the expected set
+///
+/// Entries match the media type ignoring parameters and case; `type/*` matches a
+/// whole family. Catches HTML error pages served with 200 before they reach a
+/// JSON decoder.
+pub struct ContentTypeGuardPlugin {
+    expected: Vec<String>,
+}
+
+impl ContentTypeGuardPlugin {
+    pub fn new<I, T>(expected: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self {
+            expected: expected
+                .into_iter()
+                .map(|t| t.into().to_ascii_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Whether a Content-Type header value matches the expected set
+    pub fn accepts(&self, content_type: &str) -> bool {
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .to_ascii_lowercase();
+        self.expected
+            .iter()
+            .any(|expected| match expected.strip_suffix("/*") {
+                Some(family) => essence.split('/').next() == Some(family),
+                None => *expected == essence,
+            })
+    }
+}
+
+#[async_trait]
+impl Plugin for ContentTypeGuardPlugin {
+    fn name(&self) -> &str {
+        "content_type_guard"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Rejects responses with an unexpected Content-Type"
+    }
+
+    /// Settings: `{"expected": ["application/json", ...]}`
+    async fn initialize(&mut self, config: &serde_json::Value) -> Result<()> {
+        if let Some(expected) = config.get("expected") {
+            let expected = expected.as_array().ok_or_else(|| {
+                ApiError::plugin("content_type_guard: `expected` must be an array")
+            })?;
+            self.expected = expected
+                .iter()
+                .map(|v| {
+                    v.as_str().map(str::to_ascii_lowercase).ok_or_else(|| {
+                        ApiError::plugin("content_type_guard: `expected` entries must be strings")
+                    })
+                })
+                .collect::<Result<_>>()?;
+        }
+        Ok(())
+    }
+
+    fn handles_hook(&self, hook: &PluginHook) -> bool {
+        matches!(hook, PluginHook::PreResponse)
+    }
+
+    async fn handle_pre_response(&self, status: u16, context: &PluginContext) -> Result<()> {
+        // Only successful responses with content are checked; error statuses keep their own error
+        if !(200..300).contains(&status) || status == 204 {
+            return Ok(());
+        }
+        let content_type = context
+            .metadata
+            .get("content_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        if self.accepts(content_type) {
+            Ok(())
+        } else {
+            tracing::warn!(
+                "Rejecting response with unexpected content type {:?}",
+                content_type
+            );
+            Err(ApiError::InvalidResponse)
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[tokio::test]
     async fn test_plugin_manager_creation() {
         let manager = PluginManager::new();
         assert_eq!(manager.plugins.len(), 0);
     }
-    
+
     #[tokio::test]
     async fn test_plugin_registration() {
         let mut manager = PluginManager::new();
         let plugin = Arc::new(LoggingPlugin::new());
-        
+
         assert!(manager.register_plugin(plugin).is_ok());
         assert_eq!(manager.plugins.len(), 1);
     }
-    
+
     #[tokio::test]
     async fn test_plugin_execution() {
         let mut manager = PluginManager::new();
-        manager.register_plugin(Arc::new(LoggingPlugin::new())).unwrap();
-        
+        manager
+            .register_plugin(Arc::new(LoggingPlugin::new()))
+            .unwrap();
+
         // Test plugin execution doesn't panic
-        assert!(manager.execute_pre_request("https://test.com").await.is_ok());
-        assert!(manager.execute_post_request("https://test.com", 200).await.is_ok());
+        assert!(manager
+            .execute_pre_request("https://test.com")
+            .await
+            .is_ok());
+        assert!(manager
+            .execute_post_request("https://test.com", 200)
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn test_large_logged_body_is_truncated_with_length() {
+        let body = "x".repeat(10_000);
+        let logged = LoggingPlugin::new().format_body(&body);
+        assert_eq!(
+            logged.len(),
+            DEFAULT_BODY_LOG_LIMIT + "... (10000 bytes total)".len()
+        );
+        assert!(logged.ends_with("... (10000 bytes total)"));
+
+        assert_eq!(LoggingPlugin::new().format_body("short"), "short");
+        // Never splits a multi-byte character
+        assert_eq!(truncate_for_log("ééé", 3), "é... (6 bytes total)");
+    }
+
+    #[tokio::test]
+    async fn test_logging_body_limit_from_settings() {
+        let mut plugin = LoggingPlugin::new();
+        plugin
+            .initialize(&serde_json::json!({"max_body_bytes": 4}))
+            .await
+            .unwrap();
+        assert_eq!(plugin.format_body("abcdefgh"), "abcd... (8 bytes total)");
+        assert!(plugin
+            .initialize(&serde_json::json!({"max_body_bytes": "big"}))
+            .await
+            .is_err());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_retry_logging_is_opt_in() {
+        assert!(!LoggingPlugin::new().handles_hook(&PluginHook::OnRetry));
+        assert!(LoggingPlugin::new()
+            .with_retry_logging()
+            .handles_hook(&PluginHook::OnRetry));
+
+        let mut plugin = LoggingPlugin::new();
+        plugin
+            .initialize(&serde_json::json!({"log_retries": true}))
+            .await
+            .unwrap();
+        assert!(plugin.handles_hook(&PluginHook::OnRetry));
+        assert!(plugin
+            .initialize(&serde_json::json!({"log_retries": "yes"}))
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_content_type_guard_matching() {
+        let guard = ContentTypeGuardPlugin::new(["application/json", "text/*"]);
+        assert!(guard.accepts("application/json"));
+        assert!(guard.accepts("Application/JSON; charset=utf-8"));
+        assert!(guard.accepts("text/plain"));
+        assert!(!guard.accepts("application/xml"));
+        assert!(!guard.accepts(""));
+    }
+}
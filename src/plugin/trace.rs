@@ -0,0 +1,249 @@
+//! W3C trace context propagation
+//!
+//! `TraceContextPlugin` gives every request its own span. The parent comes from
+//! a `traceparent` in the request metadata when there is one, so a trace started
+//! upstream continues through this client; otherwise the request starts a new trace.
+
+use super::{Plugin, PluginContext, PluginHook, PLUGIN_HEADERS_KEY};
+use crate::error::Result;
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Metadata key (and header name) carrying the parent trace context
+pub const TRACEPARENT: &str = "traceparent";
+/// Metadata key (and header name) carrying vendor trace state, forwarded as is
+pub const TRACESTATE: &str = "tracestate";
+
+/// One `traceparent` value: `00-{trace_id}-{span_id}-{flags}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// 32 lowercase hex digits
+    pub trace_id: String,
+    /// 16 lowercase hex digits
+    pub span_id: String,
+    pub flags: u8,
+}
+
+impl TraceContext {
+    /// Start a new sampled trace
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: Uuid::new_v4().simple().to_string(),
+            span_id: new_span_id(),
+            flags: 0x01,
+        }
+    }
+
+    /// Parse a version `00` `traceparent`, `None` if it is malformed
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let (version, trace_id, span_id, flags) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() || version != "00" {
+            return None;
+        }
+        if !is_hex_id(trace_id, 32) || !is_hex_id(span_id, 16) || flags.len() != 2 {
+            return None;
+        }
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+            flags: u8::from_str_radix(flags, 16).ok()?,
+        })
+    }
+
+    /// Same trace and flags with a fresh span id
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            span_id: new_span_id(),
+            flags: self.flags,
+        }
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            self.trace_id, self.span_id, self.flags
+        )
+    }
+}
+
+fn new_span_id() -> String {
+    format!("{:016x}", Uuid::new_v4().as_u64_pair().0.max(1))
+}
+
+/// Lowercase hex of `len` digits, not all zero
+fn is_hex_id(id: &str, len: usize) -> bool {
+    id.len() == len
+        && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        && id.bytes().any(|b| b != b'0')
+}
+
+/// A request span and the status its response came back with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceSpan {
+    pub trace_id: String,
+    pub span_id: String,
+    /// Span id of the `traceparent` the request was made under, if any
+    pub parent_span_id: Option<String>,
+    pub url: String,
+    pub status: u16,
+}
+
+/// Adds `traceparent`/`tracestate` to every request and, with
+/// `with_retained_spans`, keeps the most recent finished spans.
+///
+/// Pass the caller's context through the request metadata to continue its trace:
+///
+/// ```ignore
+/// let trace = TraceContextPlugin::new().with_retained_spans(1000);
+/// let options = RequestOptions::new().metadata("traceparent", incoming_traceparent);
+/// client.get_with("https://example.com", &options).await?;
+/// for span in trace.take_finished_spans() { /* export */ }
+/// ```
+#[derive(Debug, Default)]
+pub struct TraceContextPlugin {
+    spans: Mutex<VecDeque<TraceSpan>>,
+    /// Finished spans kept for export; 0 keeps none
+    retained: usize,
+}
+
+impl TraceContextPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep up to `limit` finished spans for `finished_spans`, dropping the
+    /// oldest once full. By default spans are only propagated, not kept.
+    pub fn with_retained_spans(mut self, limit: usize) -> Self {
+        self.retained = limit;
+        self
+    }
+
+    /// Retained spans of the requests that got a response, oldest first
+    pub fn finished_spans(&self) -> Vec<TraceSpan> {
+        self.spans.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Remove and return the retained spans, e.g. after exporting them
+    pub fn take_finished_spans(&self) -> Vec<TraceSpan> {
+        self.spans.lock().unwrap().drain(..).collect()
+    }
+}
+
+fn metadata_str<'a>(context: &'a PluginContext, key: &str) -> Option<&'a str> {
+    context.metadata.get(key).and_then(|v| v.as_str())
+}
+
+#[async_trait]
+impl Plugin for TraceContextPlugin {
+    fn name(&self) -> &str {
+        "trace_context"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Propagates W3C trace context headers"
+    }
+
+    fn handles_hook(&self, hook: &PluginHook) -> bool {
+        match hook {
+            PluginHook::PreRequest => true,
+            PluginHook::PostRequest => self.retained > 0,
+            _ => false,
+        }
+    }
+
+    async fn request_headers(
+        &self,
+        _url: &str,
+        context: &PluginContext,
+    ) -> Result<Vec<(String, String)>> {
+        let parent = metadata_str(context, TRACEPARENT).and_then(TraceContext::parse);
+        let span = match &parent {
+            Some(parent) => parent.child(),
+            None => TraceContext::new_root(),
+        };
+        let mut headers = vec![(TRACEPARENT.to_string(), span.to_string())];
+        // Trace state only means something alongside the trace it came with
+        if let (Some(_), Some(state)) = (&parent, metadata_str(context, TRACESTATE)) {
+            headers.push((TRACESTATE.to_string(), state.to_string()));
+        }
+        Ok(headers)
+    }
+
+    async fn handle_post_request(
+        &self,
+        url: &str,
+        status: u16,
+        context: &PluginContext,
+    ) -> Result<()> {
+        if self.retained == 0 {
+            return Ok(());
+        }
+        let sent = context
+            .metadata
+            .get(PLUGIN_HEADERS_KEY)
+            .and_then(|headers| headers.get(TRACEPARENT))
+            .and_then(|v| v.as_str())
+            .and_then(TraceContext::parse);
+        let Some(span) = sent else {
+            return Ok(());
+        };
+        let parent_span_id = metadata_str(context, TRACEPARENT)
+            .and_then(TraceContext::parse)
+            .map(|parent| parent.span_id);
+        let mut spans = self.spans.lock().unwrap();
+        if spans.len() == self.retained {
+            spans.pop_front();
+        }
+        spans.push_back(TraceSpan {
+            trace_id: span.trace_id,
+            span_id: span.span_id,
+            parent_span_id,
+            url: url.to_string(),
+            status,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traceparent_round_trip_and_validation() {
+        let value = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context = TraceContext::parse(value).unwrap();
+        assert_eq!(context.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(context.to_string(), value);
+
+        let child = context.child();
+        assert_eq!(child.trace_id, context.trace_id);
+        assert_ne!(child.span_id, context.span_id);
+        assert!(TraceContext::parse(&TraceContext::new_root().to_string()).is_some());
+
+        for invalid in [
+            "",
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-zz",
+        ] {
+            assert_eq!(TraceContext::parse(invalid), None, "{}", invalid);
+        }
+    }
+}
@@ -2,11 +2,26 @@ use crate::config::Config;
 use crate::error::{ApiError, Result};
 use bytes::Bytes;
 use futures::Stream;
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use uuid::Uuid;
 
+#[cfg(feature = "storage-watch")]
+mod watch;
+#[cfg(feature = "storage-watch")]
+pub use watch::{StorageEvent, StorageWatch};
+
+/// Subdirectory of the cache dir holding cached responses
+const RESPONSE_CACHE_DIR: &str = "responses";
+
+/// Callback receiving `(bytes_written, total_bytes)` progress updates
+pub type ProgressCallback = Box<dyn Fn(u64, Option<u64>) + Send + Sync>;
+
 pub struct StorageManager {
     config: Config,
 }
@@ -15,12 +30,12 @@ impl StorageManager {
     pub fn new(config: Config) -> Self {
         Self { config }
     }
-    
+
     /// Save bytes to a file
     pub async fn save_bytes(&self, data: &[u8], filename: &str) -> Result<PathBuf> {
         let path = self.config.data_dir().join(filename);
         self.ensure_parent_dir(&path).await?;
-        
+
         // Check file size limit
         if data.len() as u64 > self.config.storage.max_file_size {
             return Err(ApiError::storage(format!(
@@ -29,44 +44,86 @@ impl StorageManager {
                 self.config.storage.max_file_size
             )));
         }
-        
+
         fs::write(&path, data).await?;
         Ok(path)
     }
-    
+
     /// Save string content to a file
     pub async fn save_string(&self, content: &str, filename: &str) -> Result<PathBuf> {
         self.save_bytes(content.as_bytes(), filename).await
     }
-    
+
     /// Save JSON data to a file
-    pub async fn save_json<T: serde::Serialize>(&self, data: &T, filename: &str) -> Result<PathBuf> {
+    pub async fn save_json<T: serde::Serialize>(
+        &self,
+        data: &T,
+        filename: &str,
+    ) -> Result<PathBuf> {
         let json = serde_json::to_string_pretty(data)?;
         self.save_string(&json, filename).await
     }
-    
+
     /// Save a stream to a file with progress tracking
     pub async fn save_stream<S, E>(
         &self,
         stream: S,
         filename: &str,
-        progress_callback: Option<Box<dyn Fn(u64, Option<u64>) + Send + Sync>>,
+        progress_callback: Option<ProgressCallback>,
     ) -> Result<PathBuf>
     where
         S: Stream<Item = std::result::Result<Bytes, E>> + Unpin,
         E: std::error::Error + Send + Sync + 'static,
+    {
+        self.write_stream(stream, filename, progress_callback, |_| {})
+            .await
+    }
+
+    /// Save a stream to a file while hashing it in the same pass.
+    ///
+    /// Returns the file path and the lowercase hex digest of the written bytes,
+    /// so integrity can be checked without re-reading the file.
+    pub async fn save_stream_with_digest<S, E>(
+        &self,
+        stream: S,
+        filename: &str,
+        algorithm: DigestAlgorithm,
+    ) -> Result<(PathBuf, String)>
+    where
+        S: Stream<Item = std::result::Result<Bytes, E>> + Unpin,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut hasher = StreamDigest::new(algorithm);
+        let path = self
+            .write_stream(stream, filename, None, |chunk| hasher.update(chunk))
+            .await?;
+        Ok((path, hasher.finalize_hex()))
+    }
+
+    /// Write a stream to the data directory, handing each chunk to `on_chunk`
+    async fn write_stream<S, E, F>(
+        &self,
+        stream: S,
+        filename: &str,
+        progress_callback: Option<ProgressCallback>,
+        mut on_chunk: F,
+    ) -> Result<PathBuf>
+    where
+        S: Stream<Item = std::result::Result<Bytes, E>> + Unpin,
+        E: std::error::Error + Send + Sync + 'static,
+        F: FnMut(&[u8]),
     {
         let path = self.config.data_dir().join(filename);
         self.ensure_parent_dir(&path).await?;
-        
+
         let mut file = fs::File::create(&path).await?;
         let mut total_bytes = 0u64;
         let mut stream = Box::pin(stream);
-        
+
         while let Some(chunk_result) = futures::StreamExt::next(&mut stream).await {
-            let chunk = chunk_result
-                .map_err(|e| ApiError::stream(format!("Stream error: {}", e)))?;
-            
+            let chunk =
+                chunk_result.map_err(|e| ApiError::stream(format!("Stream error: {}", e)))?;
+
             // Check file size limit
             if total_bytes + chunk.len() as u64 > self.config.storage.max_file_size {
                 return Err(ApiError::storage(format!(
@@ -74,19 +131,37 @@ impl StorageManager {
                     self.config.storage.max_file_size
                 )));
             }
-            
+
             file.write_all(&chunk).await?;
+            on_chunk(&chunk);
             total_bytes += chunk.len() as u64;
-            
+
             if let Some(ref callback) = progress_callback {
                 callback(total_bytes, None);
             }
         }
-        
+
         file.flush().await?;
         Ok(path)
     }
-    
+
+    /// Open a JSON-lines writer for incrementally persisting records.
+    ///
+    /// The file is created (or truncated) in the data directory. Writes are buffered
+    /// through `StreamingFileWriter`, and `max_file_size` is enforced across appends.
+    pub async fn open_jsonl_writer(&self, filename: &str) -> Result<JsonlWriter> {
+        let path = self.config.data_dir().join(filename);
+        self.ensure_parent_dir(&path).await?;
+
+        let writer = StreamingFileWriter::new(&path, self.config.streaming.buffer_size).await?;
+        Ok(JsonlWriter {
+            writer,
+            path,
+            bytes_written: 0,
+            max_file_size: self.config.storage.max_file_size,
+        })
+    }
+
     /// Create a temporary file with unique name
     pub async fn create_temp_file(&self, extension: Option<&str>) -> Result<PathBuf> {
         let filename = if let Some(ext) = extension {
@@ -94,36 +169,36 @@ impl StorageManager {
         } else {
             Uuid::new_v4().to_string()
         };
-        
+
         let path = self.config.cache_dir().join(filename);
         self.ensure_parent_dir(&path).await?;
-        
+
         // Create empty file
         fs::File::create(&path).await?;
         Ok(path)
     }
-    
+
     /// Load file as bytes
     pub async fn load_bytes(&self, filename: &str) -> Result<Vec<u8>> {
         let path = self.config.data_dir().join(filename);
         let data = fs::read(&path).await?;
         Ok(data)
     }
-    
+
     /// Load file as string
     pub async fn load_string(&self, filename: &str) -> Result<String> {
         let path = self.config.data_dir().join(filename);
         let content = fs::read_to_string(&path).await?;
         Ok(content)
     }
-    
+
     /// Load JSON file
     pub async fn load_json<T: serde::de::DeserializeOwned>(&self, filename: &str) -> Result<T> {
         let content = self.load_string(filename).await?;
         let data = serde_json::from_str(&content)?;
         Ok(data)
     }
-    
+
     /// Delete a file
     pub async fn delete_file(&self, filename: &str) -> Result<()> {
         let path = self.config.data_dir().join(filename);
@@ -132,12 +207,12 @@ impl StorageManager {
         }
         Ok(())
     }
-    
+
     /// List files in storage directory
     pub async fn list_files(&self, pattern: Option<&str>) -> Result<Vec<PathBuf>> {
         let mut entries = fs::read_dir(self.config.data_dir()).await?;
         let mut files = Vec::new();
-        
+
         while let Some(entry) = entries.next_entry().await? {
             let path = entry.path();
             if path.is_file() {
@@ -152,15 +227,42 @@ impl StorageManager {
                 }
             }
         }
-        
+
         Ok(files)
     }
-    
+
+    /// List files modified after `since`, skipping any whose mtime can't be read
+    pub async fn list_files_modified_since(
+        &self,
+        since: std::time::SystemTime,
+    ) -> Result<Vec<PathBuf>> {
+        let mut entries = fs::read_dir(self.config.data_dir()).await?;
+        let mut files = Vec::new();
+
+        while let Some(entry) = entries.next_entry().await? {
+            let Ok(metadata) = entry.metadata().await else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            match metadata.modified() {
+                Ok(modified) if modified > since => files.push(entry.path()),
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::debug!("Skipping {:?} with unreadable mtime: {}", entry.path(), e)
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
     /// Get file metadata
     pub async fn file_metadata(&self, filename: &str) -> Result<FileMetadata> {
         let path = self.config.data_dir().join(filename);
         let metadata = fs::metadata(&path).await?;
-        
+
         Ok(FileMetadata {
             size: metadata.len(),
             created: metadata.created().ok(),
@@ -168,11 +270,11 @@ impl StorageManager {
             is_file: metadata.is_file(),
         })
     }
-    
+
     /// Clean up temporary files
     pub async fn cleanup_temp_files(&self) -> Result<()> {
         let mut entries = fs::read_dir(self.config.cache_dir()).await?;
-        
+
         while let Some(entry) = entries.next_entry().await? {
             let path = entry.path();
             if path.is_file() {
@@ -181,10 +283,66 @@ impl StorageManager {
                 }
             }
         }
-        
+
         Ok(())
     }
-    
+
+    /// Store `data` as the cache entry for `key` under the cache directory.
+    ///
+    /// `key` must be a plain file name, e.g. a hex digest.
+    pub async fn save_cached(&self, key: &str, data: &[u8]) -> Result<PathBuf> {
+        let path = self.cached_path(key)?;
+        self.ensure_parent_dir(&path).await?;
+        fs::write(&path, data).await?;
+        Ok(path)
+    }
+
+    /// Cache entry for `key`, if one was stored less than `ttl` ago
+    pub async fn load_cached(
+        &self,
+        key: &str,
+        ttl: std::time::Duration,
+    ) -> Result<Option<Vec<u8>>> {
+        let path = self.cached_path(key)?;
+        let modified = match fs::metadata(&path).await {
+            Ok(metadata) => metadata.modified()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        // A clock set backwards makes the entry look fresh; treat it as expired instead
+        let fresh = modified.elapsed().is_ok_and(|age| age < ttl);
+        if !fresh {
+            return Ok(None);
+        }
+        Ok(Some(fs::read(&path).await?))
+    }
+
+    /// Remove every cache entry, returning how many were removed
+    pub async fn clear_cache(&self) -> Result<usize> {
+        let dir = self.config.cache_dir().join(RESPONSE_CACHE_DIR);
+        let mut entries = match fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut removed = 0;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_file() {
+                fs::remove_file(entry.path()).await?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn cached_path(&self, key: &str) -> Result<PathBuf> {
+        if key.is_empty() || key.contains(['/', '\\']) || key.starts_with('.') {
+            return Err(ApiError::storage(format!("Invalid cache key: {:?}", key)));
+        }
+        Ok(self.config.cache_dir().join(RESPONSE_CACHE_DIR).join(key))
+    }
+
     /// Ensure parent directory exists
     async fn ensure_parent_dir(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
@@ -194,24 +352,38 @@ impl StorageManager {
         }
         Ok(())
     }
-    
+
+    /// Watch the data directory for files being created, modified or removed.
+    ///
+    /// Rapid changes to the same path are debounced into one event, directories
+    /// are skipped, and in-progress files under the temp dir are ignored.
+    /// Watching stops when the returned stream is dropped.
+    #[cfg(feature = "storage-watch")]
+    pub async fn watch(&self) -> Result<StorageWatch> {
+        let data_dir = self.config.data_dir();
+        fs::create_dir_all(data_dir).await?;
+        let data_dir = fs::canonicalize(data_dir).await?;
+        let temp_dir = fs::canonicalize(self.config.cache_dir()).await.ok();
+        watch::watch_dir(&data_dir, temp_dir)
+    }
+
     /// Get storage statistics
     pub async fn storage_stats(&self) -> Result<StorageStats> {
         let data_size = self.directory_size(self.config.data_dir()).await?;
         let cache_size = self.directory_size(self.config.cache_dir()).await?;
-        
+
         Ok(StorageStats {
             data_size,
             cache_size,
             total_size: data_size + cache_size,
         })
     }
-    
+
     /// Calculate directory size
     async fn directory_size(&self, dir: &Path) -> Result<u64> {
         let mut total_size = 0u64;
         let mut entries = fs::read_dir(dir).await?;
-        
+
         while let Some(entry) = entries.next_entry().await? {
             let metadata = entry.metadata().await?;
             if metadata.is_file() {
@@ -220,31 +392,153 @@ impl StorageManager {
                 total_size += Box::pin(self.directory_size(&entry.path())).await?;
             }
         }
-        
+
         Ok(total_size)
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FileMetadata {
     pub size: u64,
+    /// Serialized as unix milliseconds
+    #[serde(with = "unix_millis")]
     pub created: Option<std::time::SystemTime>,
+    /// Serialized as unix milliseconds
+    #[serde(with = "unix_millis")]
     pub modified: Option<std::time::SystemTime>,
     pub is_file: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StorageStats {
     pub data_size: u64,
     pub cache_size: u64,
     pub total_size: u64,
 }
 
+/// Hash algorithms supported for streaming digests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+/// Incremental hasher for the selected algorithm
+enum StreamDigest {
+    Sha256(sha2::Sha256),
+    Sha512(sha2::Sha512),
+}
+
+impl StreamDigest {
+    fn new(algorithm: DigestAlgorithm) -> Self {
+        match algorithm {
+            DigestAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            DigestAlgorithm::Sha512 => Self::Sha512(sha2::Sha512::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Sha512(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        let digest = match self {
+            Self::Sha256(hasher) => hasher.finalize().to_vec(),
+            Self::Sha512(hasher) => hasher.finalize().to_vec(),
+        };
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// Appends JSON records one per line, see `StorageManager::open_jsonl_writer`
+pub struct JsonlWriter {
+    writer: StreamingFileWriter,
+    path: PathBuf,
+    bytes_written: u64,
+    max_file_size: u64,
+}
+
+impl JsonlWriter {
+    /// Serialize a record and append it followed by a newline.
+    ///
+    /// A record that would push the file past `max_file_size` is rejected
+    /// without being written.
+    pub async fn append<T: Serialize>(&mut self, record: &T) -> Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        if self.bytes_written + line.len() as u64 > self.max_file_size {
+            return Err(ApiError::storage(format!(
+                "File size would exceed limit {}",
+                self.max_file_size
+            )));
+        }
+
+        self.writer.write_chunk(&line).await?;
+        self.bytes_written += line.len() as u64;
+        Ok(())
+    }
+
+    /// Path of the file being written
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Flush buffered records and return the file path
+    pub async fn finish(self) -> Result<PathBuf> {
+        self.writer.finish().await?;
+        Ok(self.path)
+    }
+}
+
+/// Portable `Option<SystemTime>` serialization as milliseconds since the unix epoch
+mod unix_millis {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    pub fn serialize<S: Serializer>(
+        time: &Option<SystemTime>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        time.map(|t| match t.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_millis() as i64,
+            Err(before) => -(before.duration().as_millis() as i64),
+        })
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Option<SystemTime>, D::Error> {
+        Ok(Option::<i64>::deserialize(deserializer)?.map(|millis| {
+            if millis >= 0 {
+                UNIX_EPOCH + Duration::from_millis(millis as u64)
+            } else {
+                UNIX_EPOCH - Duration::from_millis(millis.unsigned_abs())
+            }
+        }))
+    }
+}
+
 /// Utility for streaming file writer with automatic chunking
+///
+/// Also implements `AsyncWrite`, so it can be the target of `tokio::io::copy`;
+/// call `finish` (or `shutdown`) afterwards to flush the last buffered bytes.
+///
+/// A failed `write_chunk` or `finish` deletes the file, so an error never leaves
+/// a file holding an arbitrary part of the buffered data; the writer should be
+/// dropped afterwards. `AsyncWrite` errors leave the file in place for the caller
+/// to `abort`. Dropping the writer without `finish` may lose buffered bytes.
 pub struct StreamingFileWriter {
     file: tokio::fs::File,
+    path: PathBuf,
     buffer: Vec<u8>,
     buffer_size: usize,
+    /// Bytes at the front of `buffer` already handed to the file
+    drained: usize,
     total_written: u64,
 }
 
@@ -253,40 +547,325 @@ impl StreamingFileWriter {
         let file = fs::File::create(path).await?;
         Ok(Self {
             file,
+            path: path.to_path_buf(),
             buffer: Vec::with_capacity(buffer_size),
             buffer_size,
+            drained: 0,
             total_written: 0,
         })
     }
-    
+
     pub async fn write_chunk(&mut self, data: &[u8]) -> Result<()> {
         self.buffer.extend_from_slice(data);
-        
+
         if self.buffer.len() >= self.buffer_size {
-            self.flush_buffer().await?;
+            if let Err(e) = self.flush_buffer().await {
+                self.remove_file().await;
+                return Err(e);
+            }
         }
-        
+
         Ok(())
     }
-    
+
     pub async fn finish(mut self) -> Result<u64> {
-        if !self.buffer.is_empty() {
-            self.flush_buffer().await?;
+        let result = match self.flush_buffer().await {
+            Ok(()) => self.file.flush().await.map_err(ApiError::from),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => Ok(self.total_written),
+            Err(e) => {
+                self.remove_file().await;
+                Err(e)
+            }
         }
-        self.file.flush().await?;
-        Ok(self.total_written)
     }
-    
-    async fn flush_buffer(&mut self) -> Result<()> {
-        if !self.buffer.is_empty() {
-            self.file.write_all(&self.buffer).await?;
-            self.total_written += self.buffer.len() as u64;
-            self.buffer.clear();
+
+    /// Discard buffered data and delete the file
+    pub async fn abort(self) -> Result<()> {
+        let Self { file, path, .. } = self;
+        drop(file);
+        fs::remove_file(&path).await?;
+        Ok(())
+    }
+
+    async fn remove_file(&self) {
+        if let Err(e) = fs::remove_file(&self.path).await {
+            tracing::warn!(
+                "Failed to remove {:?} after a write error: {}",
+                self.path,
+                e
+            );
         }
+    }
+
+    async fn flush_buffer(&mut self) -> Result<()> {
+        std::future::poll_fn(|cx| self.poll_drain(cx)).await?;
         Ok(())
     }
-    
+
+    /// Write out the buffer, counting bytes in `total_written` as the file accepts them
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        while self.drained < self.buffer.len() {
+            let n = ready!(Pin::new(&mut self.file).poll_write(cx, &self.buffer[self.drained..]))?;
+            if n == 0 {
+                return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into()));
+            }
+            self.drained += n;
+            self.total_written += n as u64;
+        }
+        self.buffer.clear();
+        self.drained = 0;
+        Poll::Ready(Ok(()))
+    }
+
     pub fn total_written(&self) -> u64 {
         self.total_written
     }
-}
\ No newline at end of file
+}
+
+impl AsyncWrite for StreamingFileWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        data: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        if self.buffer.len() >= self.buffer_size {
+            ready!(self.poll_drain(cx))?;
+        }
+        self.buffer.extend_from_slice(data);
+        Poll::Ready(Ok(data.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        ready!(self.poll_drain(cx))?;
+        Pin::new(&mut self.file).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        ready!(self.poll_drain(cx))?;
+        Pin::new(&mut self.file).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[tokio::test]
+    async fn test_jsonl_writer_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(Config::new(temp_dir.path().to_path_buf()));
+
+        let mut writer = storage.open_jsonl_writer("records.jsonl").await.unwrap();
+        for id in 0..3 {
+            writer
+                .append(&serde_json::json!({"id": id, "name": format!("record-{}", id)}))
+                .await
+                .unwrap();
+        }
+        writer.finish().await.unwrap();
+
+        let content = storage.load_string("records.jsonl").await.unwrap();
+        let records: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2]["name"], "record-2");
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_ttl_and_clear() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(Config::new(temp_dir.path().to_path_buf()));
+
+        assert_eq!(
+            storage
+                .load_cached("abc", Duration::from_secs(60))
+                .await
+                .unwrap(),
+            None
+        );
+        storage.save_cached("abc", b"cached").await.unwrap();
+        assert_eq!(
+            storage
+                .load_cached("abc", Duration::from_secs(60))
+                .await
+                .unwrap(),
+            Some(b"cached".to_vec())
+        );
+        assert_eq!(
+            storage.load_cached("abc", Duration::ZERO).await.unwrap(),
+            None
+        );
+        assert!(matches!(
+            storage.save_cached("../escape", b"x").await,
+            Err(ApiError::Storage(_))
+        ));
+
+        assert_eq!(storage.clear_cache().await.unwrap(), 1);
+        assert_eq!(
+            storage
+                .load_cached("abc", Duration::from_secs(60))
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_jsonl_writer_enforces_max_file_size() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::new(temp_dir.path().to_path_buf());
+        config.storage.max_file_size = 20;
+        let storage = StorageManager::new(config);
+
+        let mut writer = storage.open_jsonl_writer("small.jsonl").await.unwrap();
+        writer.append(&serde_json::json!({"n": 1})).await.unwrap();
+        writer.append(&serde_json::json!({"n": 2})).await.unwrap();
+        let result = writer.append(&serde_json::json!({"n": 3})).await;
+        assert!(matches!(result, Err(ApiError::Storage(_))));
+
+        writer.finish().await.unwrap();
+        let content = storage.load_string("small.jsonl").await.unwrap();
+        assert_eq!(content.lines().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_writer_as_async_write() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("copied.bin");
+        let content: Vec<u8> = (0..10_000u32).map(|i| (i % 256) as u8).collect();
+
+        let mut writer = StreamingFileWriter::new(&path, 1024).await.unwrap();
+        let copied = tokio::io::copy(&mut content.as_slice(), &mut writer)
+            .await
+            .unwrap();
+        assert_eq!(copied, content.len() as u64);
+
+        assert_eq!(writer.finish().await.unwrap(), content.len() as u64);
+        assert_eq!(std::fs::read(&path).unwrap(), content);
+    }
+
+    #[tokio::test]
+    async fn test_list_files_modified_since() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(Config::new(temp_dir.path().to_path_buf()));
+        let now = std::time::SystemTime::now();
+
+        for (name, age_secs) in [
+            ("old.txt", 7200),
+            ("stale.txt", 3600),
+            ("fresh.txt", 60),
+            ("new.txt", 0),
+        ] {
+            let path = storage.save_string(name, name).await.unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - Duration::from_secs(age_secs))
+                .unwrap();
+        }
+
+        let mut names: Vec<String> = storage
+            .list_files_modified_since(now - Duration::from_secs(1800))
+            .await
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["fresh.txt", "new.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_writer_error_removes_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("broken.bin");
+        std::fs::write(&path, b"").unwrap();
+
+        // A read-only handle makes every write to the file fail
+        let mut writer = StreamingFileWriter {
+            file: fs::File::open(&path).await.unwrap(),
+            path: path.clone(),
+            buffer: Vec::new(),
+            buffer_size: 4,
+            drained: 0,
+            total_written: 0,
+        };
+        // The failure surfaces on the chunk that fills the buffer or, once the
+        // file has accepted it, on `finish`
+        let result = match writer.write_chunk(b"abcdef").await {
+            Ok(()) => writer.finish().await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_streaming_writer_abort_deletes_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("aborted.bin");
+
+        let mut writer = StreamingFileWriter::new(&path, 4).await.unwrap();
+        writer.write_chunk(b"abcdef").await.unwrap();
+        writer.abort().await.unwrap();
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_save_stream_with_digest() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(Config::new(temp_dir.path().to_path_buf()));
+
+        let chunks: Vec<std::result::Result<Bytes, std::io::Error>> =
+            vec![Ok(Bytes::from("hello ")), Ok(Bytes::from("world"))];
+        let (path, digest) = storage
+            .save_stream_with_digest(
+                futures::stream::iter(chunks),
+                "hello.txt",
+                DigestAlgorithm::Sha256,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            digest,
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "hello world");
+    }
+
+    #[test]
+    fn test_storage_stats_json_round_trip() {
+        let stats = StorageStats {
+            data_size: 1024,
+            cache_size: 512,
+            total_size: 1536,
+        };
+        let json = serde_json::to_string(&stats).unwrap();
+        let parsed: StorageStats = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, stats);
+    }
+
+    #[test]
+    fn test_file_metadata_times_serialize_as_unix_millis() {
+        let metadata = FileMetadata {
+            size: 10,
+            created: None,
+            modified: Some(UNIX_EPOCH + Duration::from_millis(1_700_000_000_123)),
+            is_file: true,
+        };
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(json["modified"], 1_700_000_000_123u64);
+        assert!(json["created"].is_null());
+
+        let parsed: FileMetadata = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, metadata);
+    }
+}
@@ -0,0 +1,205 @@
+//! Change notifications for the storage data directory (`storage-watch` feature)
+//!
+//! Raw filesystem events are debounced per path so an editor save or a chunked
+//! write shows up as a single event, and directories are filtered out.
+
+use crate::error::{ApiError, Result};
+use futures::Stream;
+use notify::event::{CreateKind, ModifyKind, RemoveKind, RenameMode};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// Quiet period a path must have before its change is reported
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Debounced events buffered for a slow consumer
+const EVENT_BUFFER: usize = 64;
+
+/// A file change in the watched data directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageEvent {
+    Created(PathBuf),
+    Modified(PathBuf),
+    Removed(PathBuf),
+}
+
+impl StorageEvent {
+    /// Path of the file that changed
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Created(path) | Self::Modified(path) | Self::Removed(path) => path,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Created,
+    Modified,
+    Removed,
+}
+
+impl Change {
+    /// Combine a pending change with a newer one; `None` means nothing happened overall
+    fn merge(pending: Option<Change>, next: Change) -> Option<Change> {
+        match (pending, next) {
+            (Some(Change::Created), Change::Modified) => Some(Change::Created),
+            (Some(Change::Created), Change::Removed) => None,
+            (Some(Change::Removed), Change::Created | Change::Modified) => Some(Change::Modified),
+            (_, next) => Some(next),
+        }
+    }
+
+    fn into_event(self, path: PathBuf) -> StorageEvent {
+        match self {
+            Change::Created => StorageEvent::Created(path),
+            Change::Modified => StorageEvent::Modified(path),
+            Change::Removed => StorageEvent::Removed(path),
+        }
+    }
+}
+
+/// Stream of debounced `StorageEvent`s; watching stops when it is dropped
+pub struct StorageWatch {
+    events: mpsc::Receiver<StorageEvent>,
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl Stream for StorageWatch {
+    type Item = StorageEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_recv(cx)
+    }
+}
+
+/// Watch `dir` recursively, ignoring anything under `ignored` (in-progress temp files)
+pub(crate) fn watch_dir(dir: &Path, ignored: Option<PathBuf>) -> Result<StorageWatch> {
+    let (raw_tx, raw_rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::warn!("Storage watch error: {}", e);
+                return;
+            }
+        };
+        for change in classify(&event) {
+            if ignored
+                .as_ref()
+                .is_some_and(|ignored| change.0.starts_with(ignored))
+            {
+                continue;
+            }
+            // The receiver is gone once the stream is dropped
+            let _ = raw_tx.send(change);
+        }
+    })
+    .map_err(|e| ApiError::storage(format!("Failed to start storage watcher: {}", e)))?;
+
+    watcher
+        .watch(dir, RecursiveMode::Recursive)
+        .map_err(|e| ApiError::storage(format!("Failed to watch {}: {}", dir.display(), e)))?;
+
+    let (tx, events) = mpsc::channel(EVENT_BUFFER);
+    tokio::spawn(debounce(raw_rx, tx));
+
+    Ok(StorageWatch {
+        events,
+        _watcher: watcher,
+    })
+}
+
+/// Map a raw notify event to per-path changes, dropping directory and metadata-only events
+fn classify(event: &notify::Event) -> Vec<(PathBuf, Change)> {
+    let paths = event.paths.iter().cloned();
+    match event.kind {
+        EventKind::Create(CreateKind::Folder) | EventKind::Remove(RemoveKind::Folder) => Vec::new(),
+        EventKind::Create(_) => paths.map(|p| (p, Change::Created)).collect(),
+        EventKind::Remove(_) => paths.map(|p| (p, Change::Removed)).collect(),
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+            paths.map(|p| (p, Change::Removed)).collect()
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+            paths.map(|p| (p, Change::Created)).collect()
+        }
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            let mut paths = paths;
+            let mut changes = Vec::new();
+            if let Some(from) = paths.next() {
+                changes.push((from, Change::Removed));
+            }
+            changes.extend(paths.map(|p| (p, Change::Created)));
+            changes
+        }
+        EventKind::Modify(ModifyKind::Metadata(_)) => Vec::new(),
+        EventKind::Modify(_) => paths.map(|p| (p, Change::Modified)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Hold each path's change until it has been quiet for `DEBOUNCE`, then report it
+async fn debounce(
+    mut raw: mpsc::UnboundedReceiver<(PathBuf, Change)>,
+    events: mpsc::Sender<StorageEvent>,
+) {
+    let mut pending: HashMap<PathBuf, (Change, Instant)> = HashMap::new();
+    loop {
+        let deadline = pending.values().map(|(_, at)| *at + DEBOUNCE).min();
+        tokio::select! {
+            change = raw.recv() => {
+                let Some((path, change)) = change else { return };
+                let previous = pending.remove(&path).map(|(change, _)| change);
+                if let Some(merged) = Change::merge(previous, change) {
+                    pending.insert(path, (merged, Instant::now()));
+                }
+            }
+            _ = sleep_until(deadline) => {
+                let now = Instant::now();
+                let due: Vec<PathBuf> = pending
+                    .iter()
+                    .filter(|(_, (_, at))| *at + DEBOUNCE <= now)
+                    .map(|(path, _)| path.clone())
+                    .collect();
+                for path in due {
+                    let (change, _) = pending.remove(&path).expect("due path is pending");
+                    // Some platforms report directories with a generic kind; keep files only
+                    if change != Change::Removed && !path.is_file() {
+                        continue;
+                    }
+                    if events.send(change.into_event(path)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_merging() {
+        use Change::*;
+        assert_eq!(Change::merge(Some(Created), Modified), Some(Created));
+        assert_eq!(Change::merge(Some(Created), Removed), None);
+        assert_eq!(Change::merge(Some(Modified), Removed), Some(Removed));
+        assert_eq!(Change::merge(Some(Removed), Created), Some(Modified));
+        assert_eq!(Change::merge(None, Modified), Some(Modified));
+    }
+}
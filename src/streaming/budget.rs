@@ -0,0 +1,55 @@
+//! Shared byte budget for stream buffers
+//!
+//! Each `BufferedStream` reserves its `buffer_size` from the pool before it
+//! starts filling a buffer and returns it once the buffer has been yielded, so
+//! the bytes held by every stream of a `StreamHandler` together stay within the
+//! budget. A stream that finds the pool exhausted waits, which backpressures
+//! its upstream, until another stream hands its reservation back.
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Pool of buffer bytes shared by streams; clones share the same pool
+#[derive(Debug, Clone)]
+pub struct BufferBudget {
+    bytes: Arc<Semaphore>,
+    capacity: usize,
+}
+
+impl BufferBudget {
+    /// A pool of `capacity` bytes
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.clamp(1, Semaphore::MAX_PERMITS);
+        Self {
+            bytes: Arc::new(Semaphore::new(capacity)),
+            capacity,
+        }
+    }
+
+    /// A pool too large to ever make a stream wait, still tracking usage
+    pub fn unlimited() -> Self {
+        Self::new(Semaphore::MAX_PERMITS)
+    }
+
+    /// Total bytes in the pool
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Bytes currently reserved by stream buffers
+    pub fn usage(&self) -> usize {
+        self.capacity - self.bytes.available_permits()
+    }
+
+    /// Reserve `bytes`, capped at the capacity so an oversized buffer can still proceed alone
+    pub(crate) fn reserve(&self, bytes: usize) -> BoxFuture<'static, OwnedSemaphorePermit> {
+        let bytes = bytes.clamp(1, self.capacity).min(u32::MAX as usize) as u32;
+        self.bytes
+            .clone()
+            .acquire_many_owned(bytes)
+            .map(|permit| permit.expect("buffer budget semaphore is never closed"))
+            .boxed()
+    }
+}
@@ -0,0 +1,116 @@
+//! Line splitting for newline-delimited bodies (NDJSON, logs, CSV)
+//!
+//! Lines are yielded as soon as their `\n` arrives, however they are split
+//! across chunks; only the unfinished tail is buffered.
+
+use crate::error::{ApiError, Result};
+use bytes::{Bytes, BytesMut};
+use futures::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Stream adapter yielding each line of a byte stream, without its `\n` or `\r\n`.
+///
+/// A final line without a trailing newline is yielded when the stream ends.
+pub struct LineStream<S> {
+    inner: S,
+    buffer: BytesMut,
+    /// Buffer offset already known to hold no `\n`
+    scanned: usize,
+    finished: bool,
+}
+
+impl<S> LineStream<S> {
+    pub fn new(stream: S) -> Self {
+        Self {
+            inner: stream,
+            buffer: BytesMut::new(),
+            scanned: 0,
+            finished: false,
+        }
+    }
+
+    fn next_line(&mut self) -> Option<Bytes> {
+        let at = self.buffer[self.scanned..].iter().position(|&b| b == b'\n');
+        let Some(at) = at.map(|at| self.scanned + at) else {
+            self.scanned = self.buffer.len();
+            return None;
+        };
+        let mut line = self.buffer.split_to(at + 1).freeze();
+        self.scanned = 0;
+        line.truncate(at);
+        Some(trim_cr(line))
+    }
+}
+
+fn trim_cr(mut line: Bytes) -> Bytes {
+    if line.ends_with(b"\r") {
+        line.truncate(line.len() - 1);
+    }
+    line
+}
+
+/// Pass `ApiError`s from the inner stream (e.g. read timeouts) through unchanged
+fn stream_error(error: Box<dyn std::error::Error + Send + Sync>) -> ApiError {
+    match error.downcast::<ApiError>() {
+        Ok(error) => *error,
+        Err(error) => ApiError::stream(format!("Stream error: {}", error)),
+    }
+}
+
+impl<S, E> Stream for LineStream<S>
+where
+    S: Stream<Item = std::result::Result<Bytes, E>> + Unpin,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(line) = self.next_line() {
+                return Poll::Ready(Some(Ok(line)));
+            }
+            if self.finished {
+                return Poll::Ready(None);
+            }
+
+            match Pin::new(&mut self.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => self.buffer.extend_from_slice(&chunk),
+                Poll::Ready(Some(Err(e))) => {
+                    self.finished = true;
+                    self.buffer.clear();
+                    self.scanned = 0;
+                    return Poll::Ready(Some(Err(stream_error(Box::new(e)))));
+                }
+                Poll::Ready(None) => {
+                    self.finished = true;
+                    if !self.buffer.is_empty() {
+                        self.scanned = 0;
+                        let rest = self.buffer.split().freeze();
+                        return Poll::Ready(Some(Ok(trim_cr(rest))));
+                    }
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_lines_split_across_chunks() {
+        let chunks = ["fir", "st\r\nsec", "ond\n\nthi", "rd"]
+            .into_iter()
+            .map(|chunk| Ok::<_, std::io::Error>(Bytes::from(chunk)));
+        let lines: Vec<Bytes> = LineStream::new(stream::iter(chunks))
+            .map(|line| line.unwrap())
+            .collect()
+            .await;
+        assert_eq!(lines, ["first", "second", "", "third"]);
+    }
+}
@@ -1,72 +1,89 @@
 use crate::config::Config;
-use crate::error::{ApiError, Result};
+use crate::error::{ApiError, Result, TimeoutDetail, TimeoutPhase};
 use bytes::Bytes;
+use futures::future::BoxFuture;
 use futures::{Stream, StreamExt};
-use pin_project::pin_project;
+use http_body_util::BodyExt;
+use hyper::body::Body;
 use hyper::Response;
-use http_body_util::{combinators::BoxBody, BodyExt};
+use pin_project::pin_project;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use tokio::sync::OwnedSemaphorePermit;
 use tokio::time::{interval, timeout, Duration, Interval};
 
+mod budget;
+mod lines;
+mod multipart;
+mod ndjson;
+pub use budget::BufferBudget;
+pub use lines::LineStream;
+pub use multipart::{boundary_from_content_type, MultipartStream, Part};
+pub use ndjson::NdjsonStream;
+
 pub struct StreamHandler {
     config: Config,
+    buffer_budget: BufferBudget,
 }
 
 impl StreamHandler {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        let buffer_budget = match config.streaming.buffer_budget {
+            Some(bytes) => BufferBudget::new(bytes),
+            None => BufferBudget::unlimited(),
+        };
+        Self {
+            config,
+            buffer_budget,
+        }
     }
-    
-    /// Convert HTTP response body to byte stream with timeout
-    pub fn response_to_stream(
-        &self,
-        response: Response<BoxBody<Bytes, hyper::Error>>,
-    ) -> impl Stream<Item = Result<Bytes>> + '_ {
-        let timeout_duration = self.config.stream_timeout();
-        let (_parts, body) = response.into_parts();
-        
-        // Convert body to stream using BodyExt::into_data_stream
-        let stream = body.into_data_stream();
-        
-        // Map errors and apply timeout to each chunk
-        stream
-            .map(move |result| {
-                let chunk_result = result.map_err(|e| ApiError::Http(e));
-                async move {
-                    timeout(timeout_duration, async move { chunk_result })
-                        .await
-                        .map_err(|_| ApiError::Timeout)
-                        .and_then(|r| r)
-                }
-            })
-            .buffered(1)  // Process one future at a time
+
+    /// Bytes currently reserved by buffered streams created by this handler
+    pub fn stream_buffer_usage(&self) -> usize {
+        self.buffer_budget.usage()
     }
-    
-    /// Create a buffered stream that collects chunks until buffer is full
-    pub fn create_buffered_stream<S, E>(
+
+    /// Convert an HTTP response body to a byte stream.
+    ///
+    /// Takes the client's body types as well as hyper's. Waiting longer than
+    /// `streaming.stream_timeout` for a chunk ends the stream with a read timeout.
+    pub fn response_to_stream<B>(
         &self,
-        stream: S,
-    ) -> BufferedStream<S>
+        response: Response<B>,
+    ) -> impl Stream<Item = Result<Bytes>> + Send + 'static
+    where
+        B: Body<Data = Bytes> + Send + 'static,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let chunks = response
+            .into_body()
+            .into_data_stream()
+            .map(|chunk| chunk.map_err(|e| body_error(e.into())));
+        with_read_timeout(chunks, self.config.stream_timeout())
+    }
+
+    /// Create a buffered stream that collects chunks until buffer is full.
+    ///
+    /// Its buffer is reserved from `streaming.buffer_budget`, shared by every
+    /// buffered stream of this handler.
+    pub fn create_buffered_stream<S, E>(&self, stream: S) -> BufferedStream<S>
     where
         S: Stream<Item = std::result::Result<Bytes, E>>,
         E: std::error::Error + Send + Sync + 'static,
     {
         BufferedStream::new(stream, self.config.streaming.buffer_size)
+            .with_budget(self.buffer_budget.clone())
     }
-    
+
     /// Create a chunked stream that yields fixed-size chunks
-    pub fn create_chunked_stream<S, E>(
-        &self,
-        stream: S,
-    ) -> ChunkedStream<S>
+    pub fn create_chunked_stream<S, E>(&self, stream: S) -> ChunkedStream<S>
     where
         S: Stream<Item = std::result::Result<Bytes, E>>,
         E: std::error::Error + Send + Sync + 'static,
     {
         ChunkedStream::new(stream, self.config.streaming.chunk_size)
     }
-    
+
     /// Create a rate-limited stream
     pub fn create_rate_limited_stream<S, E>(
         &self,
@@ -79,13 +96,9 @@ impl StreamHandler {
     {
         RateLimitedStream::new(stream, bytes_per_second)
     }
-    
+
     /// Collect entire stream into bytes with size limit
-    pub async fn collect_stream<S, E>(
-        &self,
-        mut stream: S,
-        max_size: Option<u64>,
-    ) -> Result<Bytes>
+    pub async fn collect_stream<S, E>(&self, mut stream: S, max_size: Option<u64>) -> Result<Bytes>
     where
         S: Stream<Item = std::result::Result<Bytes, E>> + Unpin,
         E: std::error::Error + Send + Sync + 'static,
@@ -93,11 +106,11 @@ impl StreamHandler {
         let mut collected = Vec::new();
         let mut total_size = 0u64;
         let limit = max_size.unwrap_or(self.config.storage.max_file_size);
-        
+
         while let Some(chunk_result) = stream.next().await {
-            let chunk = chunk_result
-                .map_err(|e| ApiError::stream(format!("Stream error: {}", e)))?;
-            
+            let chunk =
+                chunk_result.map_err(|e| ApiError::stream(format!("Stream error: {}", e)))?;
+
             total_size += chunk.len() as u64;
             if total_size > limit {
                 return Err(ApiError::storage(format!(
@@ -105,19 +118,15 @@ impl StreamHandler {
                     total_size, limit
                 )));
             }
-            
+
             collected.extend_from_slice(&chunk);
         }
-        
+
         Ok(Bytes::from(collected))
     }
-    
+
     /// Create a stream progress tracker
-    pub fn track_progress<S, E, F>(
-        &self,
-        stream: S,
-        callback: F,
-    ) -> ProgressStream<S, F>
+    pub fn track_progress<S, E, F>(&self, stream: S, callback: F) -> ProgressStream<S, F>
     where
         S: Stream<Item = std::result::Result<Bytes, E>>,
         E: std::error::Error + Send + Sync + 'static,
@@ -127,20 +136,92 @@ impl StreamHandler {
     }
 }
 
+/// `ApiError` for a failed body read, keeping hyper errors as `ApiError::Http`
+fn body_error(error: Box<dyn std::error::Error + Send + Sync>) -> ApiError {
+    match error.downcast::<hyper::Error>() {
+        Ok(error) => ApiError::Http(*error),
+        Err(error) => ApiError::stream(format!("Failed to read response body: {}", error)),
+    }
+}
+
+/// End `stream` with a read timeout once a chunk takes longer than `limit` to arrive
+pub(crate) fn with_read_timeout<S>(
+    stream: S,
+    limit: Duration,
+) -> impl Stream<Item = Result<Bytes>> + Send + 'static
+where
+    S: Stream<Item = Result<Bytes>> + Send + 'static,
+{
+    futures::stream::unfold(Some(Box::pin(stream)), move |stream| async move {
+        let mut stream = stream?;
+        let started = std::time::Instant::now();
+        match timeout(limit, stream.next()).await {
+            Ok(Some(chunk)) => Some((chunk, Some(stream))),
+            Ok(None) => None,
+            Err(_) => Some((
+                Err(ApiError::Timeout(TimeoutDetail::new(
+                    TimeoutPhase::Read,
+                    started.elapsed(),
+                    limit,
+                ))),
+                None,
+            )),
+        }
+    })
+}
+
 /// Buffered stream that accumulates data until buffer size is reached
 pub struct BufferedStream<S> {
     inner: S,
     buffer: Vec<u8>,
     buffer_size: usize,
+    budget: Option<BufferBudget>,
+    /// Pending reservation of `buffer_size` bytes from `budget`
+    reserving: Option<BoxFuture<'static, OwnedSemaphorePermit>>,
+    /// Held while a buffer is being filled
+    reservation: Option<OwnedSemaphorePermit>,
 }
 
 impl<S> BufferedStream<S> {
-    fn new(stream: S, buffer_size: usize) -> Self {
+    pub fn new(stream: S, buffer_size: usize) -> Self {
         Self {
             inner: stream,
-            buffer: Vec::with_capacity(buffer_size),
+            buffer: Vec::new(),
             buffer_size,
+            budget: None,
+            reserving: None,
+            reservation: None,
+        }
+    }
+
+    /// Reserve each buffer from `budget`, waiting while it is exhausted
+    pub fn with_budget(mut self, budget: BufferBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Wait until this stream holds a reservation for the buffer it is filling
+    fn poll_reserve(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let Some(budget) = &self.budget else {
+            return Poll::Ready(());
+        };
+        if self.reservation.is_some() {
+            return Poll::Ready(());
         }
+        let reserving = self
+            .reserving
+            .get_or_insert_with(|| budget.reserve(self.buffer_size));
+        let permit = std::task::ready!(reserving.as_mut().poll(cx));
+        self.reserving = None;
+        self.reservation = Some(permit);
+        self.buffer.reserve(self.buffer_size);
+        Poll::Ready(())
+    }
+
+    /// Hand back the buffer contents and its reservation
+    fn take_buffer(&mut self) -> Bytes {
+        self.reservation = None;
+        Bytes::from(std::mem::take(&mut self.buffer))
     }
 }
 
@@ -150,26 +231,32 @@ where
     E: std::error::Error + Send + Sync + 'static,
 {
     type Item = Result<Bytes>;
-    
+
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
+            std::task::ready!(self.poll_reserve(cx));
             match Pin::new(&mut self.inner).poll_next(cx) {
                 Poll::Ready(Some(Ok(chunk))) => {
                     self.buffer.extend_from_slice(&chunk);
-                    
+
                     if self.buffer.len() >= self.buffer_size {
-                        let data = std::mem::take(&mut self.buffer);
-                        return Poll::Ready(Some(Ok(Bytes::from(data))));
+                        return Poll::Ready(Some(Ok(self.take_buffer())));
                     }
                 }
                 Poll::Ready(Some(Err(e))) => {
-                    return Poll::Ready(Some(Err(ApiError::stream(format!("Stream error: {}", e)))));
+                    if self.buffer.is_empty() {
+                        self.reservation = None;
+                    }
+                    return Poll::Ready(Some(Err(ApiError::stream(format!(
+                        "Stream error: {}",
+                        e
+                    )))));
                 }
                 Poll::Ready(None) => {
                     if !self.buffer.is_empty() {
-                        let data = std::mem::take(&mut self.buffer);
-                        return Poll::Ready(Some(Ok(Bytes::from(data))));
+                        return Poll::Ready(Some(Ok(self.take_buffer())));
                     }
+                    self.reservation = None;
                     return Poll::Ready(None);
                 }
                 Poll::Pending => return Poll::Pending,
@@ -186,7 +273,7 @@ pub struct ChunkedStream<S> {
 }
 
 impl<S> ChunkedStream<S> {
-    fn new(stream: S, chunk_size: usize) -> Self {
+    pub fn new(stream: S, chunk_size: usize) -> Self {
         Self {
             inner: stream,
             buffer: Vec::new(),
@@ -201,7 +288,7 @@ where
     E: std::error::Error + Send + Sync + 'static,
 {
     type Item = Result<Bytes>;
-    
+
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
             // If we have enough data for a chunk, return it
@@ -210,14 +297,17 @@ where
                 let chunk_data = self.buffer.drain(..chunk_size).collect::<Vec<u8>>();
                 return Poll::Ready(Some(Ok(Bytes::from(chunk_data))));
             }
-            
+
             // Otherwise, try to get more data
             match Pin::new(&mut self.inner).poll_next(cx) {
                 Poll::Ready(Some(Ok(data))) => {
                     self.buffer.extend_from_slice(&data);
                 }
                 Poll::Ready(Some(Err(e))) => {
-                    return Poll::Ready(Some(Err(ApiError::stream(format!("Stream error: {}", e)))));
+                    return Poll::Ready(Some(Err(ApiError::stream(format!(
+                        "Stream error: {}",
+                        e
+                    )))));
                 }
                 Poll::Ready(None) => {
                     if !self.buffer.is_empty() {
@@ -246,7 +336,7 @@ impl<S> RateLimitedStream<S> {
         let ticks_per_second = 10;
         let interval = interval(Duration::from_millis(100));
         let bytes_per_tick = bytes_per_second / ticks_per_second;
-        
+
         Self {
             inner: stream,
             interval,
@@ -262,7 +352,7 @@ where
     E: std::error::Error + Send + Sync + 'static,
 {
     type Item = Result<Bytes>;
-    
+
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         // Check if we can send more data
         if self.pending_bytes > self.bytes_per_tick {
@@ -274,7 +364,7 @@ where
                 Poll::Pending => return Poll::Pending,
             }
         }
-        
+
         // Try to get next item
         match Pin::new(&mut self.inner).poll_next(cx) {
             Poll::Ready(Some(Ok(data))) => {
@@ -316,7 +406,7 @@ where
     F: Fn(u64, Option<u64>) + Send + Sync,
 {
     type Item = Result<Bytes>;
-    
+
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.project();
         match this.inner.poll_next(cx) {
@@ -332,4 +422,4 @@ where
             Poll::Pending => Poll::Pending,
         }
     }
-}
\ No newline at end of file
+}
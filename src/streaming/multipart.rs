@@ -0,0 +1,294 @@
+//! Incremental parser for multipart server-push responses
+//!
+//! Long-poll endpoints such as MJPEG cameras send `multipart/x-mixed-replace`
+//! bodies that never really end; each part is a complete message. The parser
+//! keeps only the unfinished tail in its buffer and yields a part as soon as
+//! the next boundary arrives, however the boundary is split across chunks.
+
+use crate::error::{ApiError, Result};
+use bytes::{Buf, Bytes, BytesMut};
+use futures::Stream;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// One part of a multipart body
+#[derive(Debug, Clone)]
+pub struct Part {
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl Part {
+    /// Value of the part's `Content-Type` header, if present and valid UTF-8
+    pub fn content_type(&self) -> Option<&str> {
+        self.headers
+            .get(hyper::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+    }
+}
+
+/// Extract the `boundary` parameter from a `multipart/*` content type
+pub fn boundary_from_content_type(content_type: &str) -> Option<String> {
+    let mime: mime::Mime = content_type.parse().ok()?;
+    if mime.type_() != mime::MULTIPART {
+        return None;
+    }
+    mime.get_param(mime::BOUNDARY)
+        .map(|boundary| boundary.as_str().to_string())
+        .filter(|boundary| !boundary.is_empty())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Skipping everything before the first delimiter
+    Preamble,
+    /// Just consumed a delimiter; either `--` (close) or the rest of its line follows
+    AfterDelimiter,
+    /// Accumulating a part until the next delimiter
+    Part,
+    /// Close delimiter seen; anything after it is epilogue
+    Done,
+}
+
+/// Stream adapter yielding each complete `Part` of a multipart byte stream
+pub struct MultipartStream<S> {
+    inner: S,
+    buffer: BytesMut,
+    /// `\n--boundary`; the preceding `\r` is trimmed from the part body
+    delimiter: Vec<u8>,
+    /// Buffer offset already known not to start a delimiter
+    scanned: usize,
+    state: State,
+    finished: bool,
+}
+
+impl<S> MultipartStream<S> {
+    pub fn new(stream: S, boundary: &str) -> Self {
+        let mut delimiter = b"\n--".to_vec();
+        delimiter.extend_from_slice(boundary.as_bytes());
+        Self {
+            inner: stream,
+            // Lets a body that opens with the delimiter match like any later one
+            buffer: BytesMut::from(&b"\n"[..]),
+            delimiter,
+            scanned: 0,
+            state: State::Preamble,
+            finished: false,
+        }
+    }
+
+    /// Advance the state machine over buffered bytes; `Ok(None)` means more input is needed
+    fn next_part(&mut self) -> Result<Option<Part>> {
+        loop {
+            match self.state {
+                State::Preamble => {
+                    match self.find_delimiter() {
+                        Some(at) => {
+                            self.buffer.advance(at + self.delimiter.len());
+                            self.state = State::AfterDelimiter;
+                        }
+                        None => {
+                            // Preamble is discarded, keeping only a possible partial delimiter
+                            let keep = self.delimiter.len() - 1;
+                            if self.buffer.len() > keep {
+                                self.buffer.advance(self.buffer.len() - keep);
+                            }
+                            self.scanned = 0;
+                            return Ok(None);
+                        }
+                    }
+                }
+                State::AfterDelimiter => {
+                    if self.buffer.len() < 2 {
+                        return Ok(None);
+                    }
+                    if self.buffer.starts_with(b"--") {
+                        self.state = State::Done;
+                        self.buffer.clear();
+                        return Ok(None);
+                    }
+                    // Skip transport padding up to the end of the delimiter line
+                    let Some(eol) = self.buffer.iter().position(|&b| b == b'\n') else {
+                        return Ok(None);
+                    };
+                    self.buffer.advance(eol + 1);
+                    self.scanned = 0;
+                    self.state = State::Part;
+                }
+                State::Part => {
+                    let Some(at) = self.find_delimiter() else {
+                        return Ok(None);
+                    };
+                    let mut raw = self.buffer.split_to(at).freeze();
+                    if raw.ends_with(b"\r") {
+                        raw.truncate(raw.len() - 1);
+                    }
+                    self.buffer.advance(self.delimiter.len());
+                    self.scanned = 0;
+                    self.state = State::AfterDelimiter;
+                    return parse_part(raw).map(Some);
+                }
+                State::Done => {
+                    self.buffer.clear();
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    /// Find the delimiter, resuming where the previous scan left off
+    fn find_delimiter(&mut self) -> Option<usize> {
+        let delimiter = &self.delimiter;
+        let found = self.buffer[self.scanned..]
+            .windows(delimiter.len())
+            .position(|window| window == delimiter.as_slice())
+            .map(|at| self.scanned + at);
+        if found.is_none() {
+            // A delimiter split across chunks can only start in the last `len - 1` bytes
+            self.scanned = self.buffer.len().saturating_sub(delimiter.len() - 1);
+        }
+        found
+    }
+}
+
+impl<S, E> Stream for MultipartStream<S>
+where
+    S: Stream<Item = std::result::Result<Bytes, E>> + Unpin,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    type Item = Result<Part>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if self.finished {
+                return Poll::Ready(None);
+            }
+            match self.next_part() {
+                Ok(Some(part)) => return Poll::Ready(Some(Ok(part))),
+                Ok(None) if self.state == State::Done => {
+                    self.finished = true;
+                    return Poll::Ready(None);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    self.finished = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+
+            match Pin::new(&mut self.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    if self.state != State::Done {
+                        self.buffer.extend_from_slice(&chunk);
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    self.finished = true;
+                    return Poll::Ready(Some(Err(ApiError::stream(format!(
+                        "Stream error: {}",
+                        e
+                    )))));
+                }
+                Poll::Ready(None) => {
+                    self.finished = true;
+                    // Push streams are often cut after a delimiter without a close marker
+                    let truncated = match self.state {
+                        State::Part => true,
+                        State::AfterDelimiter => {
+                            !self.buffer.iter().all(|b| b.is_ascii_whitespace())
+                        }
+                        State::Preamble | State::Done => false,
+                    };
+                    if truncated {
+                        return Poll::Ready(Some(Err(ApiError::stream(
+                            "Multipart stream ended in the middle of a part",
+                        ))));
+                    }
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Split a raw part into its header block and body
+fn parse_part(raw: Bytes) -> Result<Part> {
+    // A part with no headers starts directly with the blank line
+    let (header_len, body_start) = if raw.starts_with(b"\r\n") {
+        (0, 2)
+    } else if raw.starts_with(b"\n") {
+        (0, 1)
+    } else if let Some(at) = find(&raw, b"\r\n\r\n") {
+        (at, at + 4)
+    } else if let Some(at) = find(&raw, b"\n\n") {
+        (at, at + 2)
+    } else {
+        return Err(ApiError::stream(
+            "Multipart part is missing the blank line after its headers",
+        ));
+    };
+
+    let mut headers = HeaderMap::new();
+    for line in raw[..header_len].split(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.is_empty() {
+            continue;
+        }
+        let colon = line
+            .iter()
+            .position(|&b| b == b':')
+            .ok_or_else(|| ApiError::stream("Malformed multipart part header"))?;
+        let name = HeaderName::from_bytes(line[..colon].trim_ascii())
+            .map_err(|e| ApiError::stream(format!("Invalid multipart header name: {}", e)))?;
+        let value = HeaderValue::from_bytes(line[colon + 1..].trim_ascii())
+            .map_err(|e| ApiError::stream(format!("Invalid multipart header value: {}", e)))?;
+        headers.append(name, value);
+    }
+
+    Ok(Part {
+        headers,
+        body: raw.slice(body_start..),
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundary_from_content_type() {
+        assert_eq!(
+            boundary_from_content_type("multipart/x-mixed-replace; boundary=frame").as_deref(),
+            Some("frame")
+        );
+        assert_eq!(
+            boundary_from_content_type("multipart/mixed; boundary=\"a b\"").as_deref(),
+            Some("a b")
+        );
+        assert_eq!(boundary_from_content_type("multipart/mixed"), None);
+        assert_eq!(boundary_from_content_type("text/plain; boundary=x"), None);
+    }
+
+    #[test]
+    fn test_parse_part_headers_and_body() {
+        let part = parse_part(Bytes::from_static(
+            b"Content-Type: image/jpeg\r\nX-Frame: 7\r\n\r\n\xff\xd8\r\n\xff\xd9",
+        ))
+        .unwrap();
+        assert_eq!(part.content_type(), Some("image/jpeg"));
+        assert_eq!(part.headers["x-frame"], "7");
+        assert_eq!(&part.body[..], b"\xff\xd8\r\n\xff\xd9");
+
+        let bare = parse_part(Bytes::from_static(b"\r\nno headers")).unwrap();
+        assert!(bare.headers.is_empty());
+        assert_eq!(&bare.body[..], b"no headers");
+    }
+}
@@ -0,0 +1,88 @@
+//! Typed records from newline-delimited JSON (NDJSON, JSON lines) bodies
+//!
+//! Each record is decoded as soon as its line is complete. A line that is not
+//! valid JSON fails on its own; the records after it are still read.
+
+use super::LineStream;
+use crate::error::{ApiError, Result};
+use bytes::Bytes;
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Stream adapter deserializing each line of a byte stream into a `T`.
+///
+/// Blank lines are skipped. An invalid line yields `ApiError::Serialization`
+/// and the stream carries on; an error from the byte stream ends it.
+pub struct NdjsonStream<S, T> {
+    lines: LineStream<S>,
+    _record: PhantomData<fn() -> T>,
+}
+
+impl<S, T> NdjsonStream<S, T> {
+    pub fn new(stream: S) -> Self {
+        Self {
+            lines: LineStream::new(stream),
+            _record: PhantomData,
+        }
+    }
+}
+
+impl<S, E, T> Stream for NdjsonStream<S, T>
+where
+    S: Stream<Item = std::result::Result<Bytes, E>> + Unpin,
+    E: std::error::Error + Send + Sync + 'static,
+    T: DeserializeOwned,
+{
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match Pin::new(&mut self.lines).poll_next(cx) {
+                Poll::Ready(Some(Ok(line))) => {
+                    if line.iter().all(u8::is_ascii_whitespace) {
+                        continue;
+                    }
+                    return Poll::Ready(Some(
+                        serde_json::from_slice(&line).map_err(ApiError::Serialization),
+                    ));
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{stream, StreamExt};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Event {
+        id: u32,
+    }
+
+    #[tokio::test]
+    async fn test_records_split_across_chunks_skip_blank_and_survive_bad_lines() {
+        let chunks = [
+            "{\"id\":",
+            "1}\n\n  \r\n{\"id\"",
+            ":2}\nnot json\n{\"id\":3}",
+        ]
+        .into_iter()
+        .map(|chunk| Ok::<_, std::io::Error>(Bytes::from(chunk)));
+        let records: Vec<Result<Event>> = NdjsonStream::new(stream::iter(chunks)).collect().await;
+
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].as_ref().unwrap(), &Event { id: 1 });
+        assert_eq!(records[1].as_ref().unwrap(), &Event { id: 2 });
+        assert!(matches!(records[2], Err(ApiError::Serialization(_))));
+        assert_eq!(records[3].as_ref().unwrap(), &Event { id: 3 });
+    }
+}
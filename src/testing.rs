@@ -0,0 +1,38 @@
+//! Helpers for fuzzing and property-testing the stream adapters
+//!
+//! Adapters are driven synchronously over caller-chosen chunk boundaries, so the
+//! same entry point works from `cargo fuzz` targets and proptest cases. Adapters
+//! that rely on tokio timers (`RateLimitedStream`) need a runtime and are not
+//! suitable here.
+
+use crate::error::Result;
+use bytes::Bytes;
+use futures::executor::block_on;
+use futures::stream::{self, Iter, StreamExt};
+use futures::Stream;
+
+/// Upstream stream yielding the raw chunks handed to `feed_bytes_through`
+pub type ByteSource = Iter<std::vec::IntoIter<std::result::Result<Bytes, std::io::Error>>>;
+
+/// Feed `chunks` (with exactly these boundaries) through an adapter and collect its output.
+///
+/// ```
+/// use kick::streaming::ChunkedStream;
+/// use kick::testing::feed_bytes_through;
+///
+/// let out = feed_bytes_through(|source| ChunkedStream::new(source, 4), vec![b"abcde".to_vec()]);
+/// assert_eq!(out.len(), 2);
+/// ```
+pub fn feed_bytes_through<A, F>(adapter: F, chunks: Vec<Vec<u8>>) -> Vec<Result<Bytes>>
+where
+    F: FnOnce(ByteSource) -> A,
+    A: Stream<Item = Result<Bytes>> + Unpin,
+{
+    let source = stream::iter(
+        chunks
+            .into_iter()
+            .map(|chunk| Ok(Bytes::from(chunk)))
+            .collect::<Vec<_>>(),
+    );
+    block_on(adapter(source).collect())
+}
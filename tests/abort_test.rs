@@ -0,0 +1,60 @@
+use kick::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn slow_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/slow"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(10)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/fast"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_abort_all_cancels_in_flight_requests() {
+    let server = slow_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = Arc::new(ApiClient::new(Config::new(temp_dir.path().to_path_buf())));
+
+    let url = format!("{}/slow", server.uri());
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let client = client.clone();
+            let url = url.clone();
+            tokio::spawn(async move { client.get(&url).await })
+        })
+        .collect();
+
+    // Let the requests reach the server before aborting
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while server.received_requests().await.unwrap().len() < 4 {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "requests never arrived"
+        );
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    client.abort_all();
+
+    for task in tasks {
+        let result = tokio::time::timeout(Duration::from_secs(2), task)
+            .await
+            .expect("request was not cancelled")
+            .unwrap();
+        assert!(matches!(result, Err(ApiError::Cancelled)));
+    }
+
+    // Requests started after the abort are unaffected
+    let body = client.get(&format!("{}/fast", server.uri())).await.unwrap();
+    assert_eq!(body, "ok");
+}
@@ -0,0 +1,246 @@
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use kick::prelude::*;
+use kick::BuildError;
+use std::io::Write;
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEXT: &str = "hello hello hello compressed world";
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn client(temp_dir: &TempDir, encodings: &[&str]) -> ApiClient {
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.accept_encodings = encodings.iter().map(|e| e.to_string()).collect();
+    ApiClient::new(config)
+}
+
+#[tokio::test]
+async fn test_advertised_encoding_is_decoded() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/gzip"))
+        .and(header("accept-encoding", "gzip"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "gzip")
+                .set_body_bytes(gzip(TEXT.as_bytes())),
+        )
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, &["gzip"]);
+
+    let (_, headers, body) = client
+        .get_with_headers(&format!("{}/gzip", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(body, TEXT);
+    assert!(!headers.contains_key("Content-Encoding"));
+}
+
+#[tokio::test]
+async fn test_unadvertised_encoding_passes_through() {
+    let server = MockServer::start().await;
+    let encoded = zlib(TEXT.as_bytes());
+    Mock::given(method("GET"))
+        .and(path("/deflate"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "deflate")
+                .set_body_bytes(encoded.clone()),
+        )
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, &["gzip"]);
+
+    let saved = client
+        .download_file(&format!("{}/deflate", server.uri()), "deflate.bin")
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(saved).unwrap(), encoded);
+}
+
+#[tokio::test]
+async fn test_empty_list_disables_negotiation() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+
+    client(&temp_dir, &[]).get(&server.uri()).await.unwrap();
+    client(&temp_dir, &["gzip", "deflate"])
+        .get(&server.uri())
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests[0].headers.get("accept-encoding").is_none());
+    assert_eq!(requests[1].headers["accept-encoding"], "gzip, deflate");
+}
+
+#[tokio::test]
+async fn test_unsupported_encoding_fails_build() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.accept_encodings = vec!["zstd".to_string()];
+    let error = ApiClientBuilder::new()
+        .with_config(config)
+        .with_plugin_manager(PluginManager::new())
+        .build()
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(
+        error,
+        ApiError::Build(BuildError::UnsupportedEncoding { encoding }) if encoding == "zstd"
+    ));
+}
+
+#[tokio::test]
+async fn test_range_requests_ask_for_identity_encoding() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/blob"))
+        .and(header("accept-encoding", "identity"))
+        .respond_with(
+            ResponseTemplate::new(206)
+                .insert_header("content-range", "bytes 0-4/34")
+                .set_body_string("hello"),
+        )
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, &["gzip", "deflate"]);
+
+    let options = kick::RequestOptions::new().range("0-4".parse().unwrap());
+    let saved = client
+        .download_file_with(&format!("{}/blob", server.uri()), "blob", false, &options)
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(saved).unwrap(), b"hello");
+}
+
+/// Serves `content` gzip-encoded to clients that accept it and as-is otherwise,
+/// honouring `Range` against whichever form is sent and cutting the first
+/// response off part way. Returns the base URL and every request's
+/// `(range, accept-encoding)`.
+async fn flaky_gzip_server(
+    content: Vec<u8>,
+) -> (
+    String,
+    std::sync::Arc<std::sync::Mutex<Vec<(Option<String>, String)>>>,
+) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = requests.clone();
+
+    tokio::spawn(async move {
+        let encoded = gzip(&content);
+        let mut served = 0;
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut head = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                head.extend_from_slice(&buf[..n]);
+            }
+            let head = String::from_utf8_lossy(&head).to_lowercase();
+            let field = |name: &str| {
+                head.lines()
+                    .find_map(|line| line.strip_prefix(name))
+                    .map(|value| value.trim().to_string())
+            };
+            let range = field("range: bytes=");
+            let accept = field("accept-encoding:").unwrap_or_default();
+            seen.lock().unwrap().push((range.clone(), accept.clone()));
+
+            let (body, encoding) = if accept.contains("gzip") {
+                (&encoded, "content-encoding: gzip\r\n")
+            } else {
+                (&content, "")
+            };
+            let start = range.map_or(0, |range| range.trim_end_matches('-').parse().unwrap());
+            let status = if start > 0 {
+                format!(
+                    "HTTP/1.1 206 Partial Content\r\ncontent-range: bytes {}-{}/{}\r\n",
+                    start,
+                    body.len() - 1,
+                    body.len()
+                )
+            } else {
+                "HTTP/1.1 200 OK\r\n".to_string()
+            };
+            let response_head = format!(
+                "{}{}accept-ranges: bytes\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                status,
+                encoding,
+                body.len() - start
+            );
+            socket.write_all(response_head.as_bytes()).await.unwrap();
+            let end = if served == 0 {
+                body.len() * 8 / 10
+            } else {
+                body.len()
+            };
+            served += 1;
+            socket
+                .write_all(&body[start..end.max(start)])
+                .await
+                .unwrap();
+        }
+    });
+
+    (url, requests)
+}
+
+#[tokio::test]
+async fn test_resumed_download_from_gzip_server_is_intact() {
+    let content: Vec<u8> = (0..200_000u32)
+        .flat_map(|i| format!("line {} of the log\n", i).into_bytes())
+        .take(200_000)
+        .collect();
+    let (url, requests) = flaky_gzip_server(content.clone()).await;
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.retry_delay = 10;
+    let client = ApiClient::new(config);
+
+    let saved = tokio::time::timeout(
+        std::time::Duration::from_secs(10),
+        client.download_file(&format!("{}/app.log", url), "app.log"),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(std::fs::read(saved).unwrap(), content);
+    let requests = requests.lock().unwrap();
+    assert!(requests.len() >= 2, "{:?}", requests);
+    assert!(requests[0].1.contains("gzip"));
+    for (range, accept) in &requests[1..] {
+        assert!(range.is_some());
+        assert_eq!(accept, "identity");
+    }
+}
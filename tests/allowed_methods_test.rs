@@ -0,0 +1,98 @@
+use hyper::Method;
+use kick::plugin::{PluginContext, PluginHook};
+use kick::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Counts pre-request and post-request hook calls
+#[derive(Default)]
+struct HookCounter {
+    pre: AtomicU32,
+    post: AtomicU32,
+}
+
+#[async_trait]
+impl Plugin for HookCounter {
+    fn name(&self) -> &str {
+        "hook_counter"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Counts request hook calls"
+    }
+
+    fn handles_hook(&self, hook: &PluginHook) -> bool {
+        matches!(hook, PluginHook::PreRequest | PluginHook::PostRequest)
+    }
+
+    async fn handle_pre_request(&self, _url: &str, _context: &PluginContext) -> Result<()> {
+        self.pre.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn handle_post_request(
+        &self,
+        _url: &str,
+        _status: u16,
+        _context: &PluginContext,
+    ) -> Result<()> {
+        self.post.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_options_parses_allow_header() {
+    let server = MockServer::start().await;
+    Mock::given(method("OPTIONS"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(204).insert_header("allow", "GET, POST, OPTIONS"))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let counter = Arc::new(HookCounter::default());
+    let mut plugins = PluginManager::new();
+    plugins.register_plugin(counter.clone()).unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_plugin_manager(plugins)
+        .build()
+        .await
+        .unwrap();
+
+    let methods = client
+        .options(&format!("{}/items", server.uri()))
+        .await
+        .unwrap();
+
+    assert_eq!(methods, [Method::GET, Method::POST, Method::OPTIONS]);
+    assert_eq!(counter.pre.load(Ordering::SeqCst), 1);
+    assert_eq!(counter.post.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_options_without_allow_header_is_empty() {
+    let server = MockServer::start().await;
+    Mock::given(method("OPTIONS"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let methods = client
+        .options(&format!("{}/items", server.uri()))
+        .await
+        .unwrap();
+    assert!(methods.is_empty());
+}
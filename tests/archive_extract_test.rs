@@ -0,0 +1,97 @@
+#![cfg(feature = "archive")]
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use kick::prelude::*;
+use kick::ArchiveFormat;
+use std::io::Write;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Tar entry written with a raw header, so names the `tar` builder refuses can be used
+fn append_raw(builder: &mut tar::Builder<Vec<u8>>, name: &str, data: &[u8]) {
+    let mut header = tar::Header::new_old();
+    header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_cksum();
+    builder.append(&header, data).unwrap();
+}
+
+fn tar_gz(entries: &[(&str, &str)]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (name, data) in entries {
+        append_raw(&mut builder, name, data.as_bytes());
+    }
+    let tar = builder.into_inner().unwrap();
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&tar).unwrap();
+    encoder.finish().unwrap()
+}
+
+async fn serve(server: &MockServer, route: &str, body: Vec<u8>) {
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(body))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_extracts_tar_gz() {
+    let server = MockServer::start().await;
+    serve(
+        &server,
+        "/bundle.tgz",
+        tar_gz(&[("a.txt", "alpha"), ("docs/b.txt", "beta")]),
+    )
+    .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let dest = temp_dir.path().join("out");
+
+    let written = client
+        .download_and_extract(
+            &format!("{}/bundle.tgz", server.uri()),
+            &dest,
+            ArchiveFormat::TarGz,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(written, [dest.join("a.txt"), dest.join("docs/b.txt")]);
+    assert_eq!(
+        std::fs::read_to_string(dest.join("docs/b.txt")).unwrap(),
+        "beta"
+    );
+}
+
+#[tokio::test]
+async fn test_rejects_parent_directory_entry() {
+    let server = MockServer::start().await;
+    serve(
+        &server,
+        "/evil.tgz",
+        tar_gz(&[("ok.txt", "fine"), ("../evil.txt", "pwned")]),
+    )
+    .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let dest = temp_dir.path().join("out");
+
+    let result = client
+        .download_and_extract(
+            &format!("{}/evil.tgz", server.uri()),
+            &dest,
+            ArchiveFormat::TarGz,
+        )
+        .await;
+
+    match result {
+        Err(ApiError::Validation(reason)) => assert!(reason.contains("../evil.txt"), "{}", reason),
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+    assert!(!temp_dir.path().join("evil.txt").exists());
+}
@@ -0,0 +1,58 @@
+use kick::prelude::*;
+use kick::ErrorKind;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_unauthorized_carries_parsed_bearer_challenge() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/me"))
+        .respond_with(ResponseTemplate::new(401).insert_header(
+            "WWW-Authenticate",
+            r#"Bearer realm="example", error="invalid_token", scope="read write""#,
+        ))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let error = client
+        .get(&format!("{}/me", server.uri()))
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ClientStatus);
+    assert_eq!(error.code(), "authentication");
+
+    let ApiError::Authentication(auth) = &error else {
+        panic!("expected Authentication, got {:?}", error);
+    };
+    assert_eq!(auth.status.as_u16(), 401);
+    assert_eq!(auth.challenges.len(), 1);
+    let bearer = auth.challenge("bearer").expect("Bearer challenge");
+    assert_eq!(bearer.scheme, "Bearer");
+    assert_eq!(bearer.realm.as_deref(), Some("example"));
+    assert_eq!(bearer.param("error"), Some("invalid_token"));
+    assert_eq!(bearer.param("scope"), Some("read write"));
+    assert_eq!(
+        error.to_string(),
+        r#"Authentication error: HTTP 401, server expects Bearer realm="example""#
+    );
+}
+
+#[tokio::test]
+async fn test_unauthorized_without_challenge() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    match client.get(&server.uri()).await {
+        Err(ApiError::Authentication(auth)) => assert!(auth.challenges.is_empty()),
+        other => panic!("expected Authentication, got {:?}", other),
+    }
+}
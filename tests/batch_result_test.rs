@@ -0,0 +1,51 @@
+use kick::prelude::*;
+use kick::BatchResult;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_batch_result_partitions_mixed_outcomes() {
+    let server = MockServer::start().await;
+    for name in ["a", "b"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/{}", name)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(name))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/missing"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let (a, missing, b) = (
+        format!("{}/a", server.uri()),
+        format!("{}/missing", server.uri()),
+        format!("{}/b", server.uri()),
+    );
+
+    let results: BatchResult<String> = client.get_many(&[&a, &missing, &b]).await;
+
+    assert_eq!(results.len(), 3);
+    assert!(!results.all_succeeded());
+    assert_eq!(
+        results.successes().collect::<Vec<_>>(),
+        [
+            (a.as_str(), &"a".to_string()),
+            (b.as_str(), &"b".to_string())
+        ]
+    );
+    let failures: Vec<_> = results.failures().collect();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, missing);
+    assert!(matches!(failures[0].1, ApiError::HttpStatus { status, .. } if status.as_u16() == 404));
+    assert!(matches!(results.get(&b), Some(Ok(body)) if body == "b"));
+    assert!(results.get("https://elsewhere.example").is_none());
+
+    let keys: Vec<String> = results.into_iter().map(|(key, _)| key).collect();
+    assert_eq!(keys, [a, missing, b]);
+}
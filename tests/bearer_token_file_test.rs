@@ -0,0 +1,43 @@
+use hyper::Method;
+use kick::prelude::*;
+use kick::BuildError;
+use std::path::Path;
+use tempfile::TempDir;
+
+async fn build_with_token_file(temp_dir: &TempDir, path: &Path) -> Result<ApiClient> {
+    ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_plugin_manager(PluginManager::new())
+        .with_bearer_token_file(path)
+        .build()
+        .await
+}
+
+#[tokio::test]
+async fn test_token_file_sets_authorization_header() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("token");
+    std::fs::write(&path, "  s3cr3t-token\n").unwrap();
+
+    let client = build_with_token_file(&temp_dir, &path).await.unwrap();
+
+    let headers = client.effective_headers(&Method::GET);
+    assert_eq!(headers["authorization"], "Bearer s3cr3t-token");
+}
+
+#[tokio::test]
+async fn test_missing_or_empty_token_file_fails_build() {
+    let temp_dir = TempDir::new().unwrap();
+    let empty = temp_dir.path().join("empty");
+    std::fs::write(&empty, "\n").unwrap();
+
+    for path in [temp_dir.path().join("missing"), empty] {
+        match build_with_token_file(&temp_dir, &path).await {
+            Err(ApiError::Build(BuildError::TokenFile { path: reported, .. })) => {
+                assert_eq!(reported, path)
+            }
+            Err(other) => panic!("expected TokenFile, got {:?}", other),
+            Ok(_) => panic!("expected TokenFile for {}", path.display()),
+        }
+    }
+}
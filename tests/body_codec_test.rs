@@ -0,0 +1,95 @@
+use bytes::Bytes;
+use kick::prelude::*;
+use kick::BodyCodec;
+use serde_json::json;
+use std::sync::Arc;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+/// XORs every byte with a fixed key; applying it twice gives back the input
+struct XorCodec(u8);
+
+impl XorCodec {
+    fn apply(&self, body: &[u8]) -> Bytes {
+        body.iter().map(|byte| byte ^ self.0).collect()
+    }
+}
+
+impl BodyCodec for XorCodec {
+    fn encode(&self, body: Bytes) -> Result<Bytes> {
+        Ok(self.apply(&body))
+    }
+
+    fn decode(&self, body: Bytes) -> Result<Bytes> {
+        Ok(self.apply(&body))
+    }
+}
+
+async fn client_with_codec(temp_dir: &TempDir) -> ApiClient {
+    ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_body_codec(Arc::new(XorCodec(0x5a)))
+        .build()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_codec_round_trips_post_body_through_echo() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/echo"))
+        .respond_with(|request: &Request| {
+            ResponseTemplate::new(200).set_body_bytes(request.body.clone())
+        })
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = client_with_codec(&temp_dir).await;
+    let data = json!({"account": "acme", "amount": 42});
+
+    let echoed = client
+        .post_json(&format!("{}/echo", server.uri()), &data)
+        .await
+        .unwrap();
+
+    // The server only ever saw the encoded bytes
+    let sent = &server.received_requests().await.unwrap()[0];
+    assert_eq!(sent.body, XorCodec(0x5a).apply(data.to_string().as_bytes()));
+    assert_eq!(
+        sent.headers["content-length"],
+        sent.body.len().to_string().as_str()
+    );
+    // The caller gets the decoded echo back
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&echoed).unwrap(),
+        data
+    );
+}
+
+#[tokio::test]
+async fn test_codec_decodes_responses_and_skips_empty_bodies() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/secret"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_bytes(XorCodec(0x5a).apply(b"plain text").to_vec()),
+        )
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = client_with_codec(&temp_dir).await;
+
+    let body = client
+        .get(&format!("{}/secret", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(body, "plain text");
+    // A GET has no body to encode
+    let sent = &server.received_requests().await.unwrap()[0];
+    assert!(sent.body.is_empty());
+    assert!(!sent.headers.contains_key("content-length"));
+}
@@ -0,0 +1,127 @@
+use hyper::Method;
+use kick::prelude::*;
+use kick::{BodyExpectation, RequestOptions, ResponseContent};
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const BINARY: &[u8] = &[0x89, b'P', b'N', b'G', 0x00, 0xff, 0xfe];
+
+async fn server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/image"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(BINARY))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/text"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("héllo"))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/items"))
+        .and(body_json(json!({"name": "a"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": 7, "name": "a"})))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/items/7"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn client(temp_dir: &TempDir) -> ApiClient {
+    ApiClient::new(Config::new(temp_dir.path().to_path_buf()))
+}
+
+#[tokio::test]
+async fn test_bytes_expectation_returns_binary_body() {
+    let server = server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir);
+    let url = format!("{}/image", server.uri());
+
+    let options = RequestOptions::new().expect(BodyExpectation::Bytes);
+    let body = client
+        .request(Method::GET, &url, None, &options)
+        .await
+        .unwrap();
+    assert_eq!(body, ResponseContent::Bytes(BINARY.to_vec().into()));
+
+    // The default expectation still insists on text
+    let error = client
+        .request(Method::GET, &url, None, &RequestOptions::new())
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("Invalid UTF-8"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_utf8_expectation_returns_text() {
+    let server = server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let options = RequestOptions::new().expect(BodyExpectation::Utf8);
+    let body = client(&temp_dir)
+        .request(
+            Method::GET,
+            &format!("{}/text", server.uri()),
+            None,
+            &options,
+        )
+        .await
+        .unwrap();
+    assert_eq!(body, ResponseContent::Text("héllo".to_string()));
+    assert_eq!(body.into_bytes(), "héllo".as_bytes());
+}
+
+#[tokio::test]
+async fn test_json_expectation_parses_body_and_maps_no_content_to_null() {
+    let server = server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir);
+    let options = RequestOptions::new().expect(BodyExpectation::Json);
+
+    let created = client
+        .request(
+            Method::POST,
+            &format!("{}/items", server.uri()),
+            Some(&json!({"name": "a"})),
+            &options,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        created,
+        ResponseContent::Json(json!({"id": 7, "name": "a"}))
+    );
+
+    let deleted = client
+        .request(
+            Method::DELETE,
+            &format!("{}/items/7", server.uri()),
+            None,
+            &options,
+        )
+        .await
+        .unwrap();
+    assert_eq!(deleted, ResponseContent::Json(serde_json::Value::Null));
+
+    let error = client
+        .request(
+            Method::GET,
+            &format!("{}/text", server.uri()),
+            None,
+            &options,
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("Failed to deserialize JSON"),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,207 @@
+use kick::config::TlsConfig;
+use kick::prelude::*;
+use kick::{BuildError, ErrorKind};
+use tempfile::TempDir;
+
+async fn build(config: Config) -> Result<ApiClient> {
+    ApiClientBuilder::new()
+        .with_config(config)
+        .with_plugin_manager(PluginManager::new())
+        .build()
+        .await
+}
+
+fn test_config(temp_dir: &TempDir) -> Config {
+    Config::new(temp_dir.path().to_path_buf())
+}
+
+fn invalid_header_name(error: ApiError) -> String {
+    match error {
+        ApiError::Build(BuildError::InvalidHeader { name, .. }) => name,
+        other => panic!("expected InvalidHeader, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_custom_header() {
+    let error = ApiClientBuilder::new()
+        .with_header("X-Trace".to_string(), "a\r\nInjected: 1".to_string())
+        .err()
+        .unwrap();
+    assert_eq!(error.kind(), ErrorKind::Validation);
+    assert_eq!(invalid_header_name(error), "X-Trace");
+
+    // Not a control-character problem, but still not a legal header name
+    let error = ApiClientBuilder::new()
+        .with_header("Bad Header".to_string(), "value".to_string())
+        .err()
+        .unwrap();
+    assert_eq!(invalid_header_name(error), "Bad Header");
+}
+
+#[tokio::test]
+async fn test_invalid_default_header_and_user_agent() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = test_config(&temp_dir);
+    config
+        .client
+        .default_headers
+        .insert("x-team".to_string(), "ops\nX-Admin: 1".to_string());
+    let error = build(config).await.err().unwrap();
+    assert_eq!(invalid_header_name(error), "x-team");
+
+    let error = ApiClientBuilder::new()
+        .with_config(test_config(&temp_dir))
+        .with_user_agent("kick/1.0\r\n".to_string())
+        .build()
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(invalid_header_name(error), "user-agent");
+}
+
+#[tokio::test]
+async fn test_invalid_base_url() {
+    let temp_dir = TempDir::new().unwrap();
+    for base_url in ["api.example.com/v1", "ftp://files.example.com/"] {
+        let mut config = test_config(&temp_dir);
+        config.client.base_url = Some(base_url.to_string());
+        match build(config).await.err().unwrap() {
+            ApiError::Build(BuildError::InvalidBaseUrl { url, .. }) => assert_eq!(url, base_url),
+            other => panic!("expected InvalidBaseUrl, got {:?}", other),
+        }
+    }
+}
+
+#[cfg(feature = "rustls")]
+#[tokio::test]
+async fn test_tls_file_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let missing = temp_dir.path().join("missing-ca.pem");
+    let mut config = test_config(&temp_dir);
+    config.client.tls = TlsConfig {
+        ca_bundle: Some(missing.clone()),
+        ..TlsConfig::default()
+    };
+    let error = build(config).await.err().unwrap();
+    assert!(
+        error.to_string().contains(&missing.display().to_string()),
+        "{}",
+        error
+    );
+    assert!(matches!(
+        error,
+        ApiError::Build(BuildError::MissingCertFile {
+            what: "CA bundle",
+            ..
+        })
+    ));
+
+    let garbage = temp_dir.path().join("garbage.pem");
+    std::fs::write(&garbage, "not a certificate").unwrap();
+    let mut config = test_config(&temp_dir);
+    config.client.tls = TlsConfig {
+        ca_bundle: Some(garbage),
+        ..TlsConfig::default()
+    };
+    assert!(matches!(
+        build(config).await,
+        Err(ApiError::Build(BuildError::InvalidCertificate {
+            what: "CA bundle",
+            ..
+        }))
+    ));
+}
+
+#[cfg(feature = "rustls")]
+#[tokio::test]
+async fn test_client_cert_without_key() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = test_config(&temp_dir);
+    config.client.tls = TlsConfig {
+        client_cert: Some(temp_dir.path().join("client.pem")),
+        ..TlsConfig::default()
+    };
+    assert!(matches!(
+        build(config).await,
+        Err(ApiError::Build(BuildError::IncompleteClientIdentity))
+    ));
+}
+
+#[cfg(not(feature = "rustls"))]
+#[tokio::test]
+async fn test_custom_tls_needs_rustls_backend() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = test_config(&temp_dir);
+    config.client.tls = TlsConfig {
+        ca_bundle: Some(temp_dir.path().join("ca.pem")),
+        ..TlsConfig::default()
+    };
+    match build(config).await.err().unwrap() {
+        ApiError::Build(BuildError::UnsupportedTlsBackend { setting, backend }) => {
+            assert_eq!(setting, "TLS ca_bundle");
+            assert_eq!(backend, "native-tls");
+        }
+        other => panic!("expected UnsupportedTlsBackend, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_api_client_try_new_reports_build_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = test_config(&temp_dir);
+    config.client.https_proxy = Some("socks5://proxy.internal:1080".to_string());
+    assert!(matches!(
+        ApiClient::try_new(config),
+        Err(ApiError::Build(BuildError::InvalidProxy { .. }))
+    ));
+
+    let mut config = test_config(&temp_dir);
+    config.client.accept_encodings = vec!["br".to_string()];
+    assert!(matches!(
+        ApiClient::try_new(config),
+        Err(ApiError::Build(BuildError::UnsupportedEncoding { .. }))
+    ));
+
+    // An unreadable CA bundle is a missing file with rustls and unsupported with native-tls
+    let mut config = test_config(&temp_dir);
+    config.client.tls = TlsConfig {
+        ca_bundle: Some(temp_dir.path().join("missing-ca.pem")),
+        ..TlsConfig::default()
+    };
+    assert!(matches!(
+        ApiClient::try_new(config),
+        Err(ApiError::Build(
+            BuildError::MissingCertFile { .. } | BuildError::UnsupportedTlsBackend { .. }
+        ))
+    ));
+
+    // Checks shared with `build`
+    let mut config = test_config(&temp_dir);
+    config.client.base_url = Some("api.example.com/v1".to_string());
+    assert!(matches!(
+        ApiClient::try_new(config),
+        Err(ApiError::Build(BuildError::InvalidBaseUrl { .. }))
+    ));
+
+    let mut config = test_config(&temp_dir);
+    config
+        .client
+        .default_headers
+        .insert("X-Trace".to_string(), "a\r\nInjected: 1".to_string());
+    assert_eq!(
+        invalid_header_name(ApiClient::try_new(config).err().unwrap()),
+        "X-Trace"
+    );
+
+    assert!(ApiClient::try_new(test_config(&temp_dir)).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "base_url")]
+fn test_api_client_new_panics_on_build_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = test_config(&temp_dir);
+    config.client.base_url = Some("api.example.com/v1".to_string());
+    ApiClient::new(config);
+}
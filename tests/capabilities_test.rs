@@ -0,0 +1,85 @@
+mod common;
+
+use common::run_kick;
+use kick::prelude::*;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_capabilities_reflect_builder_choices() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut plugins = PluginManager::new();
+    plugins
+        .register_plugin(std::sync::Arc::new(LoggingPlugin::new()))
+        .unwrap();
+
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_plugin_manager(plugins)
+        .with_max_concurrent_requests(3)
+        .with_json_cache(16, Duration::from_secs(60))
+        .with_host_header("internal.example".to_string())
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+
+    let capabilities = client.capabilities();
+    assert_eq!(capabilities.tls_backend, kick::client::tls_backend());
+    assert_eq!(capabilities.max_concurrent_requests, 3);
+    assert_eq!(capabilities.json_cache_capacity, Some(16));
+    assert_eq!(
+        capabilities.host_header.as_deref(),
+        Some("internal.example")
+    );
+    assert_eq!(capabilities.plugins, ["logging"]);
+    assert_eq!(capabilities.timeout_ms, 30_000);
+    assert_eq!(capabilities.compression, ["gzip", "deflate"]);
+    assert!(capabilities.follows_redirects);
+    assert!(!capabilities.cookies);
+    assert!(!capabilities.config_reload);
+}
+
+#[tokio::test]
+async fn test_default_client_capabilities() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    let max_streams = config.streaming.max_concurrent_streams;
+    let capabilities = ApiClient::new(config).capabilities();
+
+    assert_eq!(capabilities.max_concurrent_requests, max_streams);
+    assert_eq!(capabilities.json_cache_capacity, None);
+    assert!(capabilities.plugins.is_empty());
+    assert!(capabilities.follows_redirects);
+
+    let json = serde_json::to_value(&capabilities).unwrap();
+    assert_eq!(json["max_retries"], 3);
+}
+
+#[tokio::test]
+async fn test_zero_max_redirects_reports_no_redirect_following() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_redirects = 0;
+
+    assert!(!ApiClient::new(config).capabilities().follows_redirects);
+}
+
+#[tokio::test]
+async fn test_doctor_prints_capabilities_and_checks_url() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let config_home = TempDir::new().unwrap();
+
+    let output = run_kick(&config_home, &["doctor", &server.uri()]).await;
+    assert_eq!(output.status, 0);
+    let stdout = output.stdout;
+    assert!(stdout.contains("\"tls_backend\""), "{}", stdout);
+    assert!(stdout.contains("Reachable"), "{}", stdout);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::run_kick;
+use tempfile::TempDir;
+
+/// Run `kick check-url <url>`, returning the exit code and stdout
+async fn check_url(url: &str) -> (i32, String) {
+    let output = run_kick(&TempDir::new().unwrap(), &["check-url", url]).await;
+    (output.status, output.stdout)
+}
+
+#[tokio::test]
+async fn test_public_url_is_allowed() {
+    let (code, stdout) = check_url("https://api.example.com/users").await;
+    assert_eq!(code, 0);
+    assert!(
+        stdout.starts_with("ALLOW https://api.example.com/users"),
+        "{}",
+        stdout
+    );
+}
+
+#[tokio::test]
+async fn test_non_http_scheme_is_denied() {
+    let (code, stdout) = check_url("file:///etc/passwd").await;
+    assert_eq!(code, 2);
+    assert!(
+        stdout.starts_with("DENY file:///etc/passwd: Only HTTP/HTTPS URLs allowed"),
+        "{}",
+        stdout
+    );
+}
+
+#[cfg(feature = "strict-security")]
+#[tokio::test]
+async fn test_private_ip_is_denied_under_strict_security() {
+    let (code, stdout) = check_url("http://192.168.1.10/admin").await;
+    assert_eq!(code, 2);
+    assert!(
+        stdout.contains("Private IP addresses not allowed"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("(policy: strict)"), "{}", stdout);
+
+    let (code, _) = check_url("http://localhost:8080").await;
+    assert_eq!(code, 2);
+}
+
+#[cfg(not(feature = "strict-security"))]
+#[tokio::test]
+async fn test_private_ip_is_allowed_without_strict_security() {
+    let (code, stdout) = check_url("http://192.168.1.10/admin").await;
+    assert_eq!(code, 0);
+    assert!(stdout.contains("(policy: permissive)"), "{}", stdout);
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{config_home, run_kick};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/report"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("report body"))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn request_count(server: &MockServer) -> usize {
+    server.received_requests().await.unwrap().len()
+}
+
+#[tokio::test]
+async fn test_cached_get_hits_network_once() {
+    let server = server().await;
+    let home = config_home();
+    let url = format!("{}/report", server.uri());
+
+    for _ in 0..2 {
+        let output = run_kick(&home, &["get", "--cache", "60", &url]).await;
+        assert_eq!(output.status, 0);
+        assert!(output.stdout.contains("report body"), "{}", output.stdout);
+    }
+    assert_eq!(request_count(&server).await, 1);
+
+    // Different headers are a different cache entry
+    run_kick(&home, &["get", "--cache", "60", "-H", "X-Team: ops", &url]).await;
+    assert_eq!(request_count(&server).await, 2);
+}
+
+#[tokio::test]
+async fn test_no_cache_and_cache_clear_refetch() {
+    let server = server().await;
+    let home = config_home();
+    let url = format!("{}/report", server.uri());
+
+    run_kick(&home, &["get", "--cache", "60", &url]).await;
+    run_kick(&home, &["get", "--cache", "60", "--no-cache", &url]).await;
+    assert_eq!(request_count(&server).await, 2);
+
+    assert_eq!(run_kick(&home, &["cache", "clear"]).await.status, 0);
+    let output = run_kick(&home, &["get", "--cache", "60", &url]).await;
+    assert!(
+        !output.stdout.contains("Served from cache"),
+        "{}",
+        output.stdout
+    );
+    assert_eq!(request_count(&server).await, 3);
+}
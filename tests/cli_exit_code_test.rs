@@ -0,0 +1,142 @@
+mod common;
+
+use common::{config_home_with, run_kick};
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Config home whose config.toml uses a one second request timeout
+fn config_home() -> TempDir {
+    config_home_with_timeout(1)
+}
+
+fn config_home_with_timeout(seconds: u64) -> TempDir {
+    config_home_with(|config| config.client.timeout = seconds)
+}
+
+async fn mock(server: &MockServer, route: &str, response: ResponseTemplate) {
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_timeout_exits_3() {
+    let server = MockServer::start().await;
+    mock(
+        &server,
+        "/slow",
+        ResponseTemplate::new(200).set_delay(Duration::from_secs(5)),
+    )
+    .await;
+    let home = config_home();
+
+    let url = format!("{}/slow", server.uri());
+    assert_eq!(run_kick(&home, &["get", &url]).await.status, 3);
+}
+
+#[tokio::test]
+async fn test_max_time_bounds_whole_command() {
+    let server = MockServer::start().await;
+    mock(
+        &server,
+        "/slow",
+        ResponseTemplate::new(200).set_delay(Duration::from_secs(10)),
+    )
+    .await;
+    // Per-request timeout alone would let this run for 30s
+    let home = config_home_with_timeout(30);
+
+    let url = format!("{}/slow", server.uri());
+    let started = std::time::Instant::now();
+    assert_eq!(
+        run_kick(&home, &["get", "--max-time", "0.5", &url])
+            .await
+            .status,
+        3
+    );
+    assert!(
+        started.elapsed() < Duration::from_secs(5),
+        "{:?}",
+        started.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn test_max_time_rejects_non_positive() {
+    let home = config_home();
+    assert_eq!(
+        run_kick(&home, &["--max-time", "0", "get", "http://127.0.0.1:9/"])
+            .await
+            .status,
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_connection_refused_exits_3() {
+    let home = config_home();
+    // Bind and drop a listener so the port is known to be closed
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let url = format!("http://127.0.0.1:{}/", port);
+    assert_eq!(run_kick(&home, &["get", &url]).await.status, 3);
+}
+
+#[tokio::test]
+async fn test_not_found_exits_4() {
+    let server = MockServer::start().await;
+    mock(&server, "/missing", ResponseTemplate::new(404)).await;
+    let home = config_home();
+
+    let url = format!("{}/missing", server.uri());
+    assert_eq!(run_kick(&home, &["get", &url]).await.status, 4);
+}
+
+#[tokio::test]
+async fn test_server_error_exits_5() {
+    let server = MockServer::start().await;
+    mock(&server, "/broken", ResponseTemplate::new(503)).await;
+    let home = config_home();
+
+    let url = format!("{}/broken", server.uri());
+    assert_eq!(run_kick(&home, &["get", &url]).await.status, 5);
+}
+
+#[tokio::test]
+async fn test_invalid_input_exits_2() {
+    let home = config_home();
+    assert_eq!(
+        run_kick(&home, &["get", "ftp://example.com/file"])
+            .await
+            .status,
+        2
+    );
+    assert_eq!(
+        run_kick(&home, &["post", "-d", "{not json", "http://127.0.0.1:9/"])
+            .await
+            .status,
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_success_exits_0() {
+    let server = MockServer::start().await;
+    mock(
+        &server,
+        "/ok",
+        ResponseTemplate::new(200).set_body_string("fine"),
+    )
+    .await;
+    let home = config_home();
+
+    let url = format!("{}/ok", server.uri());
+    assert_eq!(run_kick(&home, &["get", &url]).await.status, 0);
+}
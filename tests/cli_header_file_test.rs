@@ -0,0 +1,63 @@
+mod common;
+
+use common::{config_home, run_kick};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_header_file_combines_with_inline_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .mount(&server)
+        .await;
+    let home = config_home();
+    let header_file = home.path().join("headers.txt");
+    std::fs::write(
+        &header_file,
+        "# API credentials\nAuthorization: Bearer from-file\n\n  X-Team: ops\nx-env: staging\n",
+    )
+    .unwrap();
+
+    let output = run_kick(
+        &home,
+        &[
+            "get",
+            "--header-file",
+            header_file.to_str().unwrap(),
+            "-H",
+            "X-Env: production",
+            &format!("{}/items", server.uri()),
+        ],
+    )
+    .await;
+    assert_eq!(output.status, 0, "{}", output.stderr);
+
+    let requests = server.received_requests().await.unwrap();
+    let headers = &requests[0].headers;
+    assert_eq!(headers["authorization"], "Bearer from-file");
+    assert_eq!(headers["x-team"], "ops");
+    let envs: Vec<_> = headers.get_all("x-env").iter().collect();
+    assert_eq!(envs, ["production"]);
+}
+
+#[tokio::test]
+async fn test_invalid_header_file_line_is_rejected() {
+    let home = config_home();
+    let header_file = home.path().join("headers.txt");
+    std::fs::write(&header_file, "X-Team: ops\nnot a header\n").unwrap();
+
+    let output = run_kick(
+        &home,
+        &[
+            "get",
+            "--header-file",
+            header_file.to_str().unwrap(),
+            "http://127.0.0.1:9/",
+        ],
+    )
+    .await;
+    assert_eq!(output.status, 2);
+    assert!(output.stderr.contains("line 2"), "{}", output.stderr);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::{config_home, run_kick};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Send `data` with `kick request PUT` and return the content type the server saw
+async fn sent_content_type(extra: &[&str], data: &str) -> String {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/flags/beta"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("stored"))
+        .mount(&server)
+        .await;
+    let home = config_home();
+    let url = format!("{}/flags/beta", server.uri());
+    let mut args = vec!["request", "PUT", "-d", data];
+    args.extend_from_slice(extra);
+    args.push(&url);
+
+    let output = run_kick(&home, &args).await;
+    assert_eq!(output.status, 0, "{}", output.stderr);
+    assert!(output.stdout.contains("stored"), "{}", output.stdout);
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body, data.as_bytes());
+    requests[0].headers["content-type"]
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_json_data_is_sent_as_json() {
+    let content_type = sent_content_type(&[], r#"{"enabled": true}"#).await;
+    assert_eq!(content_type, "application/json");
+}
+
+#[tokio::test]
+async fn test_other_data_is_sent_as_text() {
+    let content_type = sent_content_type(&[], "enabled=true").await;
+    assert_eq!(content_type, "text/plain");
+}
+
+#[tokio::test]
+async fn test_explicit_content_type_wins() {
+    let content_type = sent_content_type(
+        &["--content-type", "application/merge-patch+json"],
+        "[1, 2]",
+    )
+    .await;
+    assert_eq!(content_type, "application/merge-patch+json");
+}
+
+#[tokio::test]
+async fn test_request_without_data_sends_no_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("PURGE"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("purged"))
+        .mount(&server)
+        .await;
+    let home = config_home();
+
+    let output = run_kick(&home, &["request", "purge", &server.uri()]).await;
+    assert_eq!(output.status, 0, "{}", output.stderr);
+    assert!(output.stdout.contains("purged"), "{}", output.stdout);
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests[0].body.is_empty());
+    assert!(!requests[0].headers.contains_key("content-type"));
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use common::{config_home, run_kick};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_output_labels_status_group() {
+    let server = MockServer::start().await;
+    for (route, status) in [
+        ("/ok", 200),
+        ("/moved", 302),
+        ("/missing", 404),
+        ("/broken", 503),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(ResponseTemplate::new(status))
+            .mount(&server)
+            .await;
+    }
+    let home = config_home();
+
+    for (route, label) in [
+        ("/ok", "200 OK (2xx Success)"),
+        ("/moved", "302 Found (3xx Redirect)"),
+        ("/missing", "404 Not Found (4xx Client Error)"),
+        ("/broken", "503 Service Unavailable (5xx Server Error)"),
+    ] {
+        let output = run_kick(&home, &["get", &format!("{}{}", server.uri(), route)]).await;
+        let printed = format!("{}{}", output.stdout, output.stderr);
+        assert!(printed.contains(label), "{}: {}", route, printed);
+    }
+}
@@ -0,0 +1,46 @@
+//! Helpers for tests that run the `kick` binary
+
+// Each test crate uses only some of these
+#![allow(dead_code)]
+
+use kick::config::Config;
+use tempfile::TempDir;
+
+/// Exit code and output of one `kick` run
+pub struct KickOutput {
+    pub status: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Run the `kick` binary in `home`, with its config, and so its cache dir, under `home`
+pub async fn run_kick(home: &TempDir, args: &[&str]) -> KickOutput {
+    let output = tokio::process::Command::new(env!("CARGO_BIN_EXE_kick"))
+        .args(args)
+        .current_dir(home.path())
+        .env("XDG_CONFIG_HOME", home.path())
+        .output()
+        .await
+        .expect("failed to run kick");
+    KickOutput {
+        status: output.status.code().expect("kick terminated by signal"),
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    }
+}
+
+/// Config home whose config.toml holds the default config
+pub fn config_home() -> TempDir {
+    config_home_with(|_| {})
+}
+
+/// Config home whose config.toml holds the default config as changed by `configure`
+pub fn config_home_with(configure: impl FnOnce(&mut Config)) -> TempDir {
+    let home = TempDir::new().unwrap();
+    let mut config = Config::new(home.path().join("data"));
+    configure(&mut config);
+    let dir = home.path().join("kick");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("config.toml"), toml::to_string(&config).unwrap()).unwrap();
+    home
+}
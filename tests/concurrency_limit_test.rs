@@ -0,0 +1,53 @@
+use kick::prelude::*;
+use kick::ClientEvent;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_in_flight_requests_bounded_by_max_concurrent_streams() {
+    const LIMIT: usize = 3;
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/gated"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(200)))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.streaming.max_concurrent_streams = LIMIT;
+    let client = Arc::new(ApiClient::new(config));
+    let mut rx = client.subscribe();
+
+    let url = format!("{}/gated", server.uri());
+    let tasks: Vec<_> = (0..LIMIT * 3)
+        .map(|_| {
+            let client = client.clone();
+            let url = url.clone();
+            tokio::spawn(async move { client.get(&url).await })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+
+    // A request holds its slot from RequestStarted until at least ResponseReceived
+    let mut in_flight = 0usize;
+    let mut peak = 0usize;
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            ClientEvent::RequestStarted { .. } => {
+                in_flight += 1;
+                peak = peak.max(in_flight);
+            }
+            ClientEvent::ResponseReceived { .. } => in_flight -= 1,
+            _ => {}
+        }
+    }
+    assert_eq!(peak, LIMIT);
+    assert_eq!(server.received_requests().await.unwrap().len(), LIMIT * 3);
+}
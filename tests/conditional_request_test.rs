@@ -0,0 +1,109 @@
+use kick::prelude::*;
+use kick::RequestOptions;
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn client_and_server() -> (ApiClient, MockServer, TempDir) {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/widgets/1"))
+        .and(header("if-match", "\"v2\""))
+        .respond_with(ResponseTemplate::new(200).set_body_string("updated"))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/widgets/1"))
+        .respond_with(ResponseTemplate::new(412))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    (client, server, temp_dir)
+}
+
+#[tokio::test]
+async fn test_stale_if_match_is_precondition_failed() {
+    let (client, server, _dir) = client_and_server().await;
+    let url = format!("{}/widgets/1", server.uri());
+    let options = RequestOptions::new().if_match("\"v1\"");
+
+    let result = client
+        .put_json_with(&url, &serde_json::json!({"name": "sprocket"}), &options)
+        .await;
+    assert!(
+        matches!(result, Err(ApiError::PreconditionFailed { .. })),
+        "{:?}",
+        result
+    );
+}
+
+#[tokio::test]
+async fn test_current_if_match_is_applied() {
+    let (client, server, _dir) = client_and_server().await;
+    let url = format!("{}/widgets/1", server.uri());
+    let options = RequestOptions::new().if_match("\"v2\"");
+
+    let body = client
+        .put_json_with(&url, &serde_json::json!({"name": "sprocket"}), &options)
+        .await
+        .unwrap();
+    assert_eq!(body, "updated");
+}
+
+#[tokio::test]
+async fn test_if_none_match_header_is_sent() {
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/widgets/2"))
+        .and(header("if-none-match", "*"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("created"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let url = format!("{}/widgets/2", server.uri());
+    let options = RequestOptions::new().if_none_match("*");
+
+    let body = client
+        .patch_json_with(&url, &serde_json::json!({"name": "gear"}), &options)
+        .await
+        .unwrap();
+    assert_eq!(body, "created");
+}
+
+#[tokio::test]
+async fn test_invalid_etag_is_rejected_before_sending() {
+    let (client, server, _dir) = client_and_server().await;
+    let url = format!("{}/widgets/1", server.uri());
+    let options = RequestOptions::new().if_match("\"v1\"\r\nx-evil: 1");
+
+    let result = client.delete_with(&url, &options).await;
+    assert!(result.is_err());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_if_match_replaces_configured_header() {
+    let (_, server, temp_dir) = client_and_server().await;
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config
+        .client
+        .default_headers
+        .insert("If-Match".to_string(), "\"v1\"".to_string());
+    let client = ApiClient::new(config);
+    let url = format!("{}/widgets/1", server.uri());
+    let options = RequestOptions::new().if_match("\"v2\"");
+
+    let body = client
+        .put_json_with(&url, &serde_json::json!({"name": "sprocket"}), &options)
+        .await
+        .unwrap();
+    assert_eq!(body, "updated");
+    let requests = server.received_requests().await.unwrap();
+    let sent: Vec<_> = requests[0].headers.get_all("if-match").iter().collect();
+    assert_eq!(sent, ["\"v2\""]);
+}
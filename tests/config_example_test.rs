@@ -0,0 +1,75 @@
+use kick::config::Config;
+
+#[test]
+fn test_example_toml_parses_into_config() {
+    let example = Config::example_toml();
+    let config: Config = toml::from_str(&example).expect("example config must parse");
+
+    let defaults = Config::default();
+    assert_eq!(config.version, kick::config::CONFIG_VERSION);
+    assert_eq!(config.client.user_agent, defaults.client.user_agent);
+    assert_eq!(config.client.timeout, defaults.client.timeout);
+    assert_eq!(
+        config.client.max_request_size,
+        defaults.client.max_request_size
+    );
+    assert_eq!(config.storage.base_path, defaults.storage.base_path);
+    assert_eq!(
+        config.streaming.max_concurrent_streams,
+        defaults.streaming.max_concurrent_streams
+    );
+    // Optional limits are only shown as commented-out examples
+    assert_eq!(config.streaming.keepalive_interval, None);
+    assert_eq!(config.streaming.buffer_budget, None);
+    assert_eq!(config.client.max_response_size, None);
+    assert_eq!(config.client.max_error_body, defaults.client.max_error_body);
+    assert_eq!(
+        config.client.accept_encodings,
+        defaults.client.accept_encodings
+    );
+    assert_eq!(config.client.max_redirects, defaults.client.max_redirects);
+    assert_eq!(config.client.max_total_hops, None);
+    assert!(config.client.https_proxy.is_none());
+    assert!(config.client.no_proxy.is_empty());
+    assert_eq!(
+        config.client.max_cookies_per_domain,
+        defaults.client.max_cookies_per_domain
+    );
+    assert_eq!(
+        config.client.max_cookie_size,
+        defaults.client.max_cookie_size
+    );
+    assert!(!config.client.tls.is_custom());
+    assert!(config.plugins.enabled_plugins.is_empty());
+    assert_eq!(
+        config.plugins.plugin_settings["rate_limiter"]["requests_per_minute"],
+        60
+    );
+}
+
+#[test]
+fn test_example_toml_documents_every_section() {
+    let example = Config::example_toml();
+    for section in [
+        "[client]",
+        "[client.tls]",
+        "[storage]",
+        "[plugins]",
+        "[streaming]",
+    ] {
+        assert!(example.contains(section), "missing {}", section);
+    }
+    assert!(example.lines().filter(|line| line.starts_with('#')).count() > 10);
+    for example_setting in [
+        "# keepalive_interval = 60",
+        "# max_response_size = 104857600",
+        "# max_total_hops = 20",
+        "# buffer_budget = 16777216",
+    ] {
+        assert!(
+            example.contains(example_setting),
+            "missing {}",
+            example_setting
+        );
+    }
+}
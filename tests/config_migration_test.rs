@@ -0,0 +1,104 @@
+mod common;
+
+use common::run_kick;
+use kick::config::{Config, CONFIG_VERSION};
+use kick::prelude::*;
+
+/// Config as written before files carried a `version`
+const V1_CONFIG: &str = r#"
+[client]
+user_agent = "legacy-agent/1.0"
+timeout = 45
+max_retries = 2
+retry_delay = 250
+base_url = "https://api.example.com/"
+
+[client.default_headers]
+"X-Team" = "ops"
+
+[storage]
+base_path = "/srv/kick"
+temp_path = "/srv/kick/tmp"
+max_file_size = 1024
+cleanup_on_exit = false
+
+[plugins]
+enabled_plugins = ["logging"]
+"#;
+
+#[test]
+fn test_v1_config_migrates_to_current_version() {
+    let config = Config::from_toml(V1_CONFIG).expect("v1 config must migrate");
+    let defaults = Config::default();
+
+    assert_eq!(config.version, CONFIG_VERSION);
+    // Settings from the file are kept
+    assert_eq!(config.client.user_agent, "legacy-agent/1.0");
+    assert_eq!(config.client.timeout, 45);
+    assert_eq!(config.client.retry_delay, 250);
+    assert_eq!(
+        config.client.base_url.as_deref(),
+        Some("https://api.example.com/")
+    );
+    assert_eq!(config.client.default_headers["X-Team"], "ops");
+    assert_eq!(config.storage.base_path.to_str(), Some("/srv/kick"));
+    assert!(!config.storage.cleanup_on_exit);
+    assert_eq!(config.plugins.enabled_plugins, ["logging"]);
+    // Settings and sections the file lacks come from the defaults
+    assert!(config.plugins.plugin_paths.is_empty());
+    assert!(config.plugins.plugin_settings.is_empty());
+    assert_eq!(config.client.max_redirects, defaults.client.max_redirects);
+    assert_eq!(config.streaming.buffer_size, defaults.streaming.buffer_size);
+    assert_eq!(
+        config.streaming.stream_timeout,
+        defaults.streaming.stream_timeout
+    );
+}
+
+#[tokio::test]
+async fn test_save_writes_current_version() {
+    // `kick config init` writes the defaults with `Config::save`
+    let config_home = tempfile::TempDir::new().unwrap();
+    let output = run_kick(&config_home, &["config", "init"]).await;
+    assert_eq!(output.status, 0, "{}", output.stderr);
+
+    let saved =
+        std::fs::read_to_string(config_home.path().join("kick").join("config.toml")).unwrap();
+    let table: toml::Table = toml::from_str(&saved).unwrap();
+    assert_eq!(
+        table["version"].as_integer(),
+        Some(i64::from(CONFIG_VERSION))
+    );
+    assert_eq!(Config::from_toml(&saved).unwrap().version, CONFIG_VERSION);
+}
+
+#[test]
+fn test_newer_config_version_is_rejected() {
+    let future = format!("version = {}\n{}", CONFIG_VERSION + 1, V1_CONFIG);
+
+    match Config::from_toml(&future) {
+        Err(ApiError::Config(message)) => {
+            assert!(
+                message.contains("newer than this kick supports"),
+                "{}",
+                message
+            )
+        }
+        other => panic!(
+            "expected a config error, got {:?}",
+            other.map(|c| c.version)
+        ),
+    }
+}
+
+#[test]
+fn test_invalid_config_version_is_rejected() {
+    for version in ["0", "\"two\""] {
+        let content = format!("version = {}\n{}", version, V1_CONFIG);
+        assert!(
+            matches!(Config::from_toml(&content), Err(ApiError::Config(_))),
+            "version = {} was accepted",
+            version
+        );
+    }
+}
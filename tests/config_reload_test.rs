@@ -0,0 +1,49 @@
+#![cfg(feature = "config-watch")]
+
+use kick::prelude::*;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn write_config(path: &std::path::Path, config: &Config) {
+    std::fs::write(path, toml::to_string_pretty(config).unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn test_modifying_watched_config_updates_timeout() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    write_config(&config_path, &config);
+
+    let client = ApiClient::new(config.clone())
+        .with_config_reload(&config_path)
+        .expect("Failed to watch config");
+    assert_eq!(client.timeout(), Duration::from_secs(30));
+
+    config.client.timeout = 99;
+    config
+        .client
+        .default_headers
+        .insert("X-Reloaded".to_string(), "yes".to_string());
+    config.storage.base_path = temp_dir.path().join("elsewhere");
+    write_config(&config_path, &config);
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while client.timeout() != Duration::from_secs(99) {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "config was not reloaded"
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    assert_eq!(
+        client
+            .default_headers()
+            .get("X-Reloaded")
+            .map(String::as_str),
+        Some("yes")
+    );
+    // Storage paths are not safe to change live
+    assert_eq!(client.config().storage.base_path, temp_dir.path());
+}
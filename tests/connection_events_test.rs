@@ -0,0 +1,240 @@
+use kick::prelude::*;
+use kick::{ClientEvent, ConnectionEvent, StaticResolver};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::sync::mpsc::Receiver;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn drain(rx: &mut Receiver<ClientEvent>) -> Vec<ClientEvent> {
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    events
+}
+
+#[tokio::test]
+async fn test_new_connection_events_are_emitted_in_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let resolver = StaticResolver::new().with_host("api.service.test", server.address().ip());
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_resolver(Arc::new(resolver))
+        .build()
+        .await
+        .unwrap();
+    let mut rx = client.subscribe_with_connections();
+
+    let url = format!("http://api.service.test:{}/", server.address().port());
+    client.get(&url).await.unwrap();
+
+    let events = drain(&mut rx);
+    assert_eq!(events.len(), 4, "{:?}", events);
+    assert!(matches!(events[0], ClientEvent::RequestStarted { .. }));
+    match &events[1] {
+        ClientEvent::Connection(ConnectionEvent::DnsResolved { host, .. }) => {
+            assert_eq!(host, "api.service.test")
+        }
+        other => panic!("expected DnsResolved, got {:?}", other),
+    }
+    match &events[2] {
+        ClientEvent::Connection(ConnectionEvent::Connected { host, .. }) => {
+            assert_eq!(host, "api.service.test")
+        }
+        other => panic!("expected Connected, got {:?}", other),
+    }
+    assert_eq!(events[3], ClientEvent::ResponseReceived { status: 200 });
+
+    // The pooled connection is reused without new connection events
+    client.get(&url).await.unwrap();
+    let events = drain(&mut rx);
+    assert!(
+        !events
+            .iter()
+            .any(|event| matches!(event, ClientEvent::Connection(_))),
+        "{:?}",
+        events
+    );
+}
+
+#[tokio::test]
+async fn test_ip_literal_skips_dns_event() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let mut rx = client.subscribe_with_connections();
+    client.get(&server.uri()).await.unwrap();
+
+    let connection: Vec<ConnectionEvent> = drain(&mut rx)
+        .into_iter()
+        .filter_map(|event| match event {
+            ClientEvent::Connection(event) => Some(event),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(connection.len(), 1, "{:?}", connection);
+    assert!(matches!(connection[0], ConnectionEvent::Connected { .. }));
+}
+
+#[tokio::test]
+async fn test_failed_handshake_still_reports_finished_phases() {
+    // A plain HTTP server answers the TLS handshake with garbage
+    let server = MockServer::start().await;
+    let resolver = StaticResolver::new().with_host("api.service.test", server.address().ip());
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_retries = 0;
+    let client = ApiClientBuilder::new()
+        .with_config(config)
+        .with_resolver(Arc::new(resolver))
+        .build()
+        .await
+        .unwrap();
+    let mut rx = client.subscribe_with_connections();
+
+    let url = format!("https://api.service.test:{}/", server.address().port());
+    assert!(client.get(&url).await.is_err());
+
+    let phases: Vec<&str> = drain(&mut rx)
+        .into_iter()
+        .filter_map(|event| match event {
+            ClientEvent::Connection(ConnectionEvent::DnsResolved { .. }) => Some("dns"),
+            ClientEvent::Connection(ConnectionEvent::Connected { .. }) => Some("tcp"),
+            ClientEvent::Connection(ConnectionEvent::TlsEstablished { .. }) => Some("tls"),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(phases, ["dns", "tcp"]);
+}
+
+#[tokio::test]
+async fn test_lifecycle_subscribers_skip_connection_events() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let mut lifecycle = client.subscribe();
+    let mut everything = client.subscribe_with_connections();
+    client.get(&server.uri()).await.unwrap();
+
+    assert_eq!(drain(&mut lifecycle).len(), 2);
+    assert_eq!(drain(&mut everything).len(), 3);
+}
+
+#[cfg(feature = "rustls")]
+mod tls {
+    use super::*;
+    use http_body_util::Full;
+    use hyper::body::Bytes;
+    use hyper::server::conn::http1;
+    use hyper::service::service_fn;
+    use hyper_util::rt::TokioIo;
+    use kick::config::TlsConfig;
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+    use rustls::pki_types::PrivatePkcs8KeyDer;
+    use tokio::net::TcpListener;
+    use tokio_rustls::TlsAcceptor;
+
+    /// Serve over TLS with a localhost certificate; returns the port and CA bundle path
+    async fn start_tls_server(dir: &TempDir) -> (u16, std::path::PathBuf) {
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+        let server_key = KeyPair::generate().unwrap();
+        let server_cert = CertificateParams::new(vec!["localhost".to_string()])
+            .unwrap()
+            .signed_by(&server_key, &ca_cert, &ca_key)
+            .unwrap();
+        let ca_path = dir.path().join("ca.pem");
+        std::fs::write(&ca_path, ca_cert.pem()).unwrap();
+
+        let server_config = rustls::ServerConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_no_client_auth()
+        .with_single_cert(
+            vec![server_cert.der().clone()],
+            PrivatePkcs8KeyDer::from(server_key.serialize_der()).into(),
+        )
+        .unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(server_config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    if let Ok(tls) = acceptor.accept(stream).await {
+                        let service = service_fn(|_| async {
+                            Ok::<_, hyper::Error>(hyper::Response::new(Full::new(Bytes::from(
+                                "tls-ok",
+                            ))))
+                        });
+                        let _ = http1::Builder::new()
+                            .serve_connection(TokioIo::new(tls), service)
+                            .await;
+                    }
+                });
+            }
+        });
+        (port, ca_path)
+    }
+
+    #[tokio::test]
+    async fn test_tls_connection_reports_every_phase() {
+        let temp_dir = TempDir::new().unwrap();
+        let (port, ca_bundle) = start_tls_server(&temp_dir).await;
+        let mut config = Config::new(temp_dir.path().to_path_buf());
+        config.client.tls = TlsConfig {
+            ca_bundle: Some(ca_bundle),
+            ..TlsConfig::default()
+        };
+        let client = ApiClientBuilder::new()
+            .with_config(config)
+            .build()
+            .await
+            .unwrap();
+        let mut rx = client.subscribe_with_connections();
+
+        let body = client
+            .get(&format!("https://localhost:{}/", port))
+            .await
+            .unwrap();
+        assert_eq!(body, "tls-ok");
+
+        // localhost goes through the resolver, then TCP, then the handshake
+        let phases: Vec<&str> = drain(&mut rx)
+            .into_iter()
+            .filter_map(|event| match event {
+                ClientEvent::Connection(ConnectionEvent::DnsResolved { .. }) => Some("dns"),
+                ClientEvent::Connection(ConnectionEvent::Connected { .. }) => Some("tcp"),
+                ClientEvent::Connection(ConnectionEvent::TlsEstablished { host, .. }) => {
+                    assert_eq!(host, "localhost");
+                    Some("tls")
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(phases, ["dns", "tcp", "tls"]);
+    }
+}
@@ -0,0 +1,73 @@
+use kick::prelude::*;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+async fn items_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(201))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn header<'a>(request: &'a Request, name: &str) -> Option<&'a str> {
+    request
+        .headers
+        .get(name)
+        .map(|value| value.to_str().unwrap())
+}
+
+#[tokio::test]
+async fn test_json_post_sends_content_length() {
+    let server = items_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let payload = json!({"name": "widget", "tags": ["a", "b"]});
+    client
+        .post_json(&format!("{}/items", server.uri()), &payload)
+        .await
+        .unwrap();
+    client
+        .post_form(&format!("{}/items", server.uri()), &[("q", "kick")])
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    for request in &requests {
+        let length: usize = header(request, "content-length")
+            .expect("missing Content-Length")
+            .parse()
+            .expect("Content-Length is not numeric");
+        assert_eq!(length, request.body.len());
+        assert_eq!(header(request, "transfer-encoding"), None);
+    }
+    assert_eq!(requests[0].body, serde_json::to_vec(&payload).unwrap());
+}
+
+#[tokio::test]
+async fn test_chunked_uploads_override() {
+    let server = items_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_plugin_manager(PluginManager::new())
+        .with_chunked_uploads(true)
+        .build()
+        .await
+        .unwrap();
+
+    client
+        .post_json(&format!("{}/items", server.uri()), &json!({"id": 1}))
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(header(&requests[0], "transfer-encoding"), Some("chunked"));
+    assert_eq!(header(&requests[0], "content-length"), None);
+    assert_eq!(requests[0].body, br#"{"id":1}"#);
+}
@@ -0,0 +1,82 @@
+use kick::prelude::*;
+use kick::ContentTypeGuardPlugin;
+use std::sync::Arc;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn guarded_client(temp_dir: &TempDir) -> ApiClient {
+    let mut plugins = PluginManager::new();
+    plugins
+        .register_plugin(Arc::new(ContentTypeGuardPlugin::new(["application/json"])))
+        .unwrap();
+    ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_plugin_manager(plugins)
+        .build()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_html_response_rejected_under_json_expectation() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "<html><body>Maintenance</body></html>",
+            "text/html; charset=utf-8",
+        ))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let result = guarded_client(&temp_dir)
+        .await
+        .get(&format!("{}/data", server.uri()))
+        .await;
+    assert!(matches!(result, Err(ApiError::InvalidResponse)));
+}
+
+#[tokio::test]
+async fn test_json_response_passes_guard() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let body = guarded_client(&temp_dir)
+        .await
+        .get(&format!("{}/data", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(body, r#"{"ok":true}"#);
+}
+
+#[tokio::test]
+async fn test_guard_configured_from_plugin_settings() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.plugins.enabled_plugins = vec!["content_type_guard".to_string()];
+    config.plugins.plugin_settings.insert(
+        "content_type_guard".to_string(),
+        serde_json::json!({"expected": ["text/plain"]}),
+    );
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .mount(&server)
+        .await;
+
+    let client = ApiClientBuilder::new()
+        .with_config(config)
+        .build()
+        .await
+        .unwrap();
+    let result = client.get(&server.uri()).await;
+    assert!(matches!(result, Err(ApiError::InvalidResponse)));
+}
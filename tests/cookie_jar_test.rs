@@ -0,0 +1,156 @@
+use kick::prelude::*;
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn login_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/login"))
+        .respond_with(
+            ResponseTemplate::new(303)
+                .append_header("Set-Cookie", "session=s3cr3t; Path=/; HttpOnly")
+                .append_header("Set-Cookie", "theme=dark; Path=/settings")
+                .append_header("Location", "/dashboard"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(header("cookie", "session=s3cr3t"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("welcome back"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn client(temp_dir: &TempDir, cookie_jar: bool) -> ApiClient {
+    let mut builder =
+        ApiClientBuilder::new().with_config(Config::new(temp_dir.path().to_path_buf()));
+    if cookie_jar {
+        builder = builder.with_cookie_jar();
+    }
+    builder.build().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_session_cookie_from_login_is_sent_afterwards() {
+    let server = login_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, true).await;
+
+    // The redirect after login already carries the new session
+    let body = client
+        .post_form(
+            &format!("{}/login", server.uri()),
+            &[("user", "alice"), ("password", "pw")],
+        )
+        .await
+        .unwrap();
+    assert_eq!(body, "welcome back");
+
+    let body = client
+        .get(&format!("{}/profile", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(body, "welcome back");
+    assert_eq!(client.cookie_jar().unwrap().len(), 2);
+    assert!(client.capabilities().cookies);
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("cookie"));
+}
+
+#[tokio::test]
+async fn test_cookies_are_ignored_without_a_jar() {
+    let server = login_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, false).await;
+
+    let error = client
+        .post_form(&format!("{}/login", server.uri()), &[("user", "alice")])
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ApiError::Authentication(_)), "{:?}", error);
+    assert!(client.cookie_jar().is_none());
+}
+
+#[tokio::test]
+async fn test_expired_cookie_is_not_sent() {
+    let server = MockServer::start().await;
+    Mock::given(path("/logout"))
+        .respond_with(
+            ResponseTemplate::new(200).append_header("Set-Cookie", "session=; Max-Age=0; Path=/"),
+        )
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, true).await;
+    let jar_url = format!("{}/", server.uri());
+
+    Mock::given(path("/login"))
+        .respond_with(ResponseTemplate::new(200).append_header("Set-Cookie", "session=abc; Path=/"))
+        .mount(&server)
+        .await;
+    client
+        .get(&format!("{}/login", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(
+        client
+            .cookie_jar()
+            .unwrap()
+            .cookie_header(&jar_url)
+            .as_deref(),
+        Some("session=abc")
+    );
+
+    client
+        .get(&format!("{}/logout", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(client.cookie_jar().unwrap().cookie_header(&jar_url), None);
+    assert!(client.cookie_jar().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_cookie_limits_reject_oversized_and_evict_oldest() {
+    let server = MockServer::start().await;
+    Mock::given(path("/prefs"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .append_header("Set-Cookie", "a=1")
+                .append_header("Set-Cookie", format!("huge={}", "x".repeat(200)))
+                .append_header("Set-Cookie", "b=2")
+                .append_header("Set-Cookie", "c=3")
+                .append_header("Set-Cookie", "b=22"),
+        )
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_cookies_per_domain = 2;
+    config.client.max_cookie_size = 64;
+    let client = ApiClientBuilder::new()
+        .with_config(config)
+        .with_cookie_jar()
+        .build()
+        .await
+        .unwrap();
+
+    client
+        .get(&format!("{}/prefs", server.uri()))
+        .await
+        .unwrap();
+
+    // "huge" is rejected, "a" is evicted and the second "b" replaces the first
+    let jar = client.cookie_jar().unwrap();
+    assert_eq!(jar.len(), 2);
+    assert_eq!(
+        jar.cookie_header(&format!("{}/", server.uri())).as_deref(),
+        Some("b=22; c=3")
+    );
+}
@@ -0,0 +1,68 @@
+use kick::prelude::*;
+use kick::{ErrorKind, StaticResolver};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn dns_host(error: ApiError) -> String {
+    match error {
+        ApiError::Dns { host, .. } => host,
+        other => panic!("expected a DNS error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_unresolvable_host_is_dns_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    // `.invalid` is reserved and never resolves (RFC 6761)
+    let error = client
+        .get("http://kick-test.nonexistent.invalid/")
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Network);
+    assert_eq!(error.code(), "dns");
+    assert_eq!(dns_host(error), "kick-test.nonexistent.invalid");
+}
+
+#[tokio::test]
+async fn test_custom_resolver_failure_is_dns_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_resolver(Arc::new(StaticResolver::new()))
+        .build()
+        .await
+        .unwrap();
+
+    let error = client
+        .get("http://unknown.service.test/")
+        .await
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("unknown.service.test"),
+        "{}",
+        error
+    );
+    assert_eq!(dns_host(error), "unknown.service.test");
+}
+
+// strict-security rejects loopback URLs before connecting
+#[cfg(not(feature = "strict-security"))]
+#[tokio::test]
+async fn test_refused_connection_is_not_dns_error() {
+    // Bind and drop a listener so the port is known to be closed
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let error = client
+        .get(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ApiError::Connect(_)), "{:?}", error);
+}
@@ -0,0 +1,52 @@
+use kick::prelude::*;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_concurrent_downloads_to_same_path_fetch_once() {
+    let payload = "x".repeat(64 * 1024);
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artifact"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(payload.clone())
+                .set_delay(Duration::from_millis(200)),
+        )
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let url = format!("{}/artifact", server.uri());
+
+    let (first, second) = tokio::join!(
+        client.download_file(&url, "artifact.bin"),
+        client.download_file(&url, "artifact.bin"),
+    );
+    let first = first.unwrap();
+    let second = second.unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    assert_eq!(std::fs::read_to_string(&first).unwrap(), payload);
+}
+
+#[tokio::test]
+async fn test_sequential_downloads_fetch_again() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("fresh"))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let url = format!("{}/artifact", server.uri());
+
+    client.download_file(&url, "artifact.bin").await.unwrap();
+    client.download_file(&url, "artifact.bin").await.unwrap();
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
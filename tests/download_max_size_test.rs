@@ -0,0 +1,83 @@
+mod common;
+
+use common::run_kick;
+use kick::prelude::*;
+use kick::RequestOptions;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Server holding a 64 KiB resource
+async fn large_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/large.bin"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![7u8; 64 * 1024]))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_download_over_max_size_is_aborted_and_removed() {
+    let server = large_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let url = format!("{}/large.bin", server.uri());
+    let options = RequestOptions::new().max_size(1024);
+    let error = client
+        .download_file_with(&url, "large.bin", false, &options)
+        .await
+        .unwrap_err();
+
+    assert!(
+        error.to_string().contains("maximum size of 1024 bytes"),
+        "{}",
+        error
+    );
+    assert!(!temp_dir.path().join("downloads/large.bin").exists());
+}
+
+#[tokio::test]
+async fn test_max_size_takes_precedence_over_config() {
+    let server = large_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_response_size = Some(1024);
+    let client = ApiClient::new(config);
+
+    let url = format!("{}/large.bin", server.uri());
+    let options = RequestOptions::new().max_size(128 * 1024);
+    let saved = client
+        .download_file_with(&url, "large.bin", false, &options)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::metadata(saved).unwrap().len(), 64 * 1024);
+}
+
+#[tokio::test]
+async fn test_cli_max_size() {
+    let server = large_server().await;
+    let work_dir = TempDir::new().unwrap();
+
+    let url = format!("{}/large.bin", server.uri());
+    let output = run_kick(
+        &work_dir,
+        &[
+            "download",
+            "--local",
+            "--max-size",
+            "1024",
+            "-o",
+            "large.bin",
+            &url,
+        ],
+    )
+    .await;
+
+    assert_ne!(output.status, 0);
+    assert!(output.stderr.contains("maximum size of 1024 bytes"));
+    assert!(!work_dir.path().join(".downloads/large.bin").exists());
+}
@@ -1,4 +1,4 @@
-use kick::driver::{DriverClient, DriverApiClient, LoggingPlugin};
+use kick::driver::{DriverApiClient, DriverClient, LoggingPlugin};
 use std::sync::Arc;
 
 #[tokio::test]
@@ -10,8 +10,7 @@ async fn test_driver_client_creation() {
 
 #[tokio::test]
 async fn test_driver_plugin_system() {
-    let _client = DriverApiClient::new()
-        .with_plugin(Arc::new(LoggingPlugin));
+    let _client = DriverApiClient::new().with_plugin(Arc::new(LoggingPlugin));
 
     // Verify we can create client with plugins without panicking
     assert!(true);
@@ -22,7 +21,7 @@ async fn test_driver_plugin_system() {
 #[ignore] // Use `cargo test -- --ignored` to run network tests
 async fn test_driver_network_requests() {
     let client = DriverClient::new();
-    
+
     // Test GET request
     match client.get("https://httpbin.org/status/200").await {
         Ok(_) => assert!(true),
@@ -37,10 +36,10 @@ async fn test_driver_network_requests() {
 #[ignore] // Use `cargo test -- --ignored` to run network tests
 async fn test_driver_error_handling() {
     let client = DriverClient::new();
-    
+
     // Test error response
     match client.get("https://httpbin.org/status/404").await {
         Ok(_) => panic!("Expected 404 error but got success"),
         Err(_) => assert!(true), // Expected error
     }
-}
\ No newline at end of file
+}
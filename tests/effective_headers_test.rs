@@ -0,0 +1,111 @@
+use hyper::Method;
+use kick::prelude::*;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn client_with_headers(temp_dir: &TempDir) -> ApiClient {
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    for (name, value) in [("x-team", "ops"), ("x-env", "prod")] {
+        config
+            .client
+            .default_headers
+            .insert(name.to_string(), value.to_string());
+    }
+    ApiClientBuilder::new()
+        .with_config(config)
+        .with_plugin_manager(PluginManager::new())
+        .with_user_agent("kick-test/1.0".to_string())
+        .with_header("x-env".to_string(), "staging".to_string())
+        .unwrap()
+        .build()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_effective_headers_merge_precedence_for_post() {
+    let temp_dir = TempDir::new().unwrap();
+    let client = client_with_headers(&temp_dir).await;
+
+    let headers = client.effective_headers(&Method::POST);
+    assert_eq!(headers["content-type"], "application/json");
+    assert_eq!(headers["user-agent"], "kick-test/1.0");
+    assert_eq!(headers["x-team"], "ops");
+    // Builder headers override config defaults
+    assert_eq!(headers["x-env"], "staging");
+    assert_eq!(headers["accept-encoding"], "gzip, deflate");
+    assert_eq!(headers.len(), 5);
+
+    assert!(!client
+        .effective_headers(&Method::GET)
+        .contains_key("content-type"));
+}
+
+#[tokio::test]
+async fn test_sent_headers_match_effective_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client_with_headers(&temp_dir).await;
+
+    client
+        .post_json(&server.uri(), &json!({"id": 1}))
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    for (name, value) in client.effective_headers(&Method::POST) {
+        let sent: Vec<_> = requests[0].headers.get_all(name.as_str()).iter().collect();
+        assert_eq!(sent, [value.as_str()], "{}", name);
+    }
+}
+
+#[tokio::test]
+async fn test_differently_cased_headers_are_sent_once() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config
+        .client
+        .default_headers
+        .insert("X-Team".to_string(), "ops".to_string());
+    let client = ApiClientBuilder::new()
+        .with_config(config)
+        .with_plugin_manager(PluginManager::new())
+        .with_header("Content-Type".to_string(), "text/plain".to_string())
+        .unwrap()
+        .with_header("x-team".to_string(), "dev".to_string())
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+
+    client
+        .post_json(&server.uri(), &json!({"id": 1}))
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    // The body's own content type outranks a configured Content-Type header
+    for (name, value) in [("content-type", "application/json"), ("x-team", "dev")] {
+        let sent: Vec<_> = requests[0].headers.get_all(name).iter().collect();
+        assert_eq!(sent, [value], "{}", name);
+    }
+    assert_eq!(
+        client.effective_headers(&Method::POST)["content-type"],
+        "application/json"
+    );
+    assert_eq!(
+        client.effective_headers(&Method::GET)["content-type"],
+        "text/plain"
+    );
+}
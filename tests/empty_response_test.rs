@@ -0,0 +1,80 @@
+use kick::prelude::*;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn setup() -> (ApiClient, MockServer, TempDir) {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/no-content"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/empty-ok"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"n": 1})))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    (client, server, temp_dir)
+}
+
+#[tokio::test]
+async fn test_download_json_on_204_reports_empty_response() {
+    let (client, server, _dir) = setup().await;
+    let result = client
+        .download_json::<serde_json::Value>(&format!("{}/no-content", server.uri()))
+        .await;
+    match result {
+        Err(ApiError::EmptyResponse { status }) => assert_eq!(status.as_u16(), 204),
+        other => panic!("expected EmptyResponse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_download_json_optional_maps_empty_to_none() {
+    let (client, server, _dir) = setup().await;
+    let none: Option<serde_json::Value> = client
+        .download_json_optional(&format!("{}/no-content", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(none, None);
+
+    let empty: Option<serde_json::Value> = client
+        .download_json_optional(&format!("{}/empty-ok", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(empty, None);
+
+    let some: Option<serde_json::Value> = client
+        .download_json_optional(&format!("{}/json", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(some, Some(serde_json::json!({"n": 1})));
+}
+
+#[tokio::test]
+async fn test_get_optional_distinguishes_no_content() {
+    let (client, server, _dir) = setup().await;
+    assert_eq!(
+        client
+            .get_optional(&format!("{}/no-content", server.uri()))
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        client
+            .get_optional(&format!("{}/empty-ok", server.uri()))
+            .await
+            .unwrap(),
+        Some(String::new())
+    );
+}
@@ -0,0 +1,71 @@
+use kick::prelude::*;
+use kick::ClientEvent;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn drain(rx: &mut tokio::sync::mpsc::Receiver<ClientEvent>) -> Vec<ClientEvent> {
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    events
+}
+
+#[tokio::test]
+async fn test_successful_request_event_sequence() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/ok"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("fine"))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let mut rx = client.subscribe();
+
+    let url = format!("{}/ok", server.uri());
+    client.get(&url).await.unwrap();
+
+    assert_eq!(
+        drain(&mut rx),
+        vec![
+            ClientEvent::RequestStarted {
+                method: "GET".to_string(),
+                url,
+            },
+            ClientEvent::ResponseReceived { status: 200 },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_failed_request_event_sequence() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let mut rx = client.subscribe();
+
+    let url = format!("{}/missing", server.uri());
+    assert!(client.delete(&url).await.is_err());
+
+    assert_eq!(
+        drain(&mut rx),
+        vec![
+            ClientEvent::RequestStarted {
+                method: "DELETE".to_string(),
+                url,
+            },
+            ClientEvent::ResponseReceived { status: 404 },
+            ClientEvent::RequestFailed {
+                error_code: "http_status",
+            },
+        ]
+    );
+}
@@ -0,0 +1,104 @@
+use kick::prelude::*;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn server_with_body(body: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/report.csv"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_fetch_lands_in_data_dir() {
+    let body = "id,name\n1,sprocket\n2,gear\n";
+    let server = server_with_body(body).await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let url = format!("{}/report.csv", server.uri());
+    let saved = client
+        .fetch_to_storage(&url, "reports/today.csv")
+        .await
+        .unwrap();
+
+    assert_eq!(saved, temp_dir.path().join("reports/today.csv"));
+    assert_eq!(std::fs::read_to_string(&saved).unwrap(), body);
+}
+
+#[tokio::test]
+async fn test_traversal_filename_rejected_before_request() {
+    let server = server_with_body("data").await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let url = format!("{}/report.csv", server.uri());
+    assert!(client
+        .fetch_to_storage(&url, "../escape.csv")
+        .await
+        .is_err());
+    assert!(client
+        .fetch_to_storage(&url, "/etc/escape.csv")
+        .await
+        .is_err());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_storage_file_size_limit_enforced() {
+    let server = server_with_body(&"x".repeat(4096)).await;
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.storage.max_file_size = 1024;
+    let client = ApiClient::new(config);
+
+    let url = format!("{}/report.csv", server.uri());
+    let result = client.fetch_to_storage(&url, "big.csv").await;
+    assert!(matches!(result, Err(ApiError::Storage(_))), "{:?}", result);
+    assert!(!temp_dir.path().join("big.csv").exists());
+}
+
+#[tokio::test]
+async fn test_response_size_limit_enforced() {
+    let server = server_with_body(&"x".repeat(4096)).await;
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_response_size = Some(1024);
+    let client = ApiClient::new(config);
+
+    let url = format!("{}/report.csv", server.uri());
+    let error = client.fetch_to_storage(&url, "big.csv").await.unwrap_err();
+    assert!(error.to_string().contains("max_response_size"), "{}", error);
+    assert!(!temp_dir.path().join("big.csv").exists());
+
+    // Buffered reads share the same cap
+    let error = client.get(&url).await.unwrap_err();
+    assert!(error.to_string().contains("max_response_size"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_failed_fetch_keeps_existing_file() {
+    let server = server_with_body(&"x".repeat(4096)).await;
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_response_size = Some(1024);
+    let client = ApiClient::new(config);
+    let existing = temp_dir.path().join("reports/today.csv");
+    std::fs::create_dir_all(existing.parent().unwrap()).unwrap();
+    std::fs::write(&existing, "yesterday").unwrap();
+
+    let url = format!("{}/report.csv", server.uri());
+    assert!(client
+        .fetch_to_storage(&url, "reports/today.csv")
+        .await
+        .is_err());
+
+    assert_eq!(std::fs::read_to_string(&existing).unwrap(), "yesterday");
+    // No temp file is left behind
+    let entries = std::fs::read_dir(existing.parent().unwrap()).unwrap();
+    assert_eq!(entries.count(), 1);
+}
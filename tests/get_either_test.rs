@@ -0,0 +1,84 @@
+use kick::prelude::*;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Widget {
+    id: u32,
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct ApiProblem {
+    code: String,
+    message: String,
+}
+
+async fn client_and_server() -> (ApiClient, MockServer, TempDir) {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/widgets/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": 1,
+            "name": "sprocket"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/widgets/2"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "code": "not_found",
+            "message": "no such widget"
+        })))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    (client, server, temp_dir)
+}
+
+#[tokio::test]
+async fn test_get_either_deserializes_success_body() {
+    let (client, server, _dir) = client_and_server().await;
+    let result = client
+        .get_either::<Widget, ApiProblem>(&format!("{}/widgets/1", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(
+        result,
+        Ok(Widget {
+            id: 1,
+            name: "sprocket".to_string()
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_get_either_deserializes_error_body_with_status() {
+    let (client, server, _dir) = client_and_server().await;
+    let result = client
+        .get_either::<Widget, ApiProblem>(&format!("{}/widgets/2", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(
+        result,
+        Err((
+            404,
+            ApiProblem {
+                code: "not_found".to_string(),
+                message: "no such widget".to_string()
+            }
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_get_either_reports_undecodable_error_body() {
+    let (client, server, _dir) = client_and_server().await;
+    let result = client
+        .get_either::<ApiProblem, Widget>(&format!("{}/widgets/2", server.uri()))
+        .await;
+    assert!(matches!(result, Err(ApiError::Serialization(_))));
+}
@@ -0,0 +1,83 @@
+use futures::StreamExt;
+use kick::error::TimeoutPhase;
+use kick::prelude::*;
+use serde::Deserialize;
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct LogRecord {
+    level: String,
+    message: String,
+}
+
+#[tokio::test]
+async fn test_get_ndjson_yields_typed_records() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tail"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "{\"level\":\"info\",\"message\":\"started\"}\n\n\
+             {\"level\":\"warn\"}\n\
+             {\"level\":\"error\",\"message\":\"disk full\"}\r\n",
+            "application/x-ndjson",
+        ))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let records: Vec<Result<LogRecord>> = client
+        .get_ndjson(&format!("{}/tail", server.uri()))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(records.len(), 3);
+    assert_eq!(
+        records[0].as_ref().unwrap(),
+        &LogRecord {
+            level: "info".into(),
+            message: "started".into()
+        }
+    );
+    // A record missing a field fails alone
+    assert!(matches!(records[1], Err(ApiError::Serialization(_))));
+    assert_eq!(records[2].as_ref().unwrap().message, "disk full");
+}
+
+#[tokio::test]
+async fn test_get_ndjson_times_out_on_stalled_body() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/tail", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 1024];
+        let _ = socket.read(&mut buf).await.unwrap();
+        socket
+            .write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Length: 1000\r\n\r\n\
+                  {\"level\":\"info\",\"message\":\"started\"}\n",
+            )
+            .await
+            .unwrap();
+        // Never send the rest
+        tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+    });
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.streaming.stream_timeout = 1;
+    let client = ApiClient::new(config);
+
+    let mut records = client.get_ndjson::<LogRecord>(&url).await.unwrap();
+    assert_eq!(records.next().await.unwrap().unwrap().message, "started");
+    match records.next().await.unwrap() {
+        Err(ApiError::Timeout(detail)) => assert_eq!(detail.phase, TimeoutPhase::Read),
+        other => panic!("expected a read timeout, got {:?}", other),
+    }
+    assert!(records.next().await.is_none());
+}
@@ -0,0 +1,102 @@
+use bytes::Bytes;
+use futures::StreamExt;
+use http_body_util::{combinators::BoxBody, BodyExt, Full, StreamBody};
+use hyper::body::Frame;
+use kick::error::TimeoutPhase;
+use kick::prelude::*;
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+fn config(temp_dir: &TempDir) -> Config {
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.streaming.stream_timeout = 1;
+    config
+}
+
+#[tokio::test]
+async fn test_get_stream_yields_whole_body_in_chunks() {
+    let data: Vec<u8> = (0..512 * 1024).map(|i| (i % 251) as u8).collect();
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/large.bin"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(data.clone()))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(config(&temp_dir));
+
+    let mut stream = client
+        .get_stream(&format!("{}/large.bin", server.uri()))
+        .await
+        .unwrap();
+    let (mut received, mut chunks) = (Vec::new(), 0);
+    while let Some(chunk) = stream.next().await {
+        received.extend_from_slice(&chunk.unwrap());
+        chunks += 1;
+    }
+
+    assert_eq!(received, data);
+    assert!(chunks > 1, "expected several chunks, got {}", chunks);
+}
+
+#[tokio::test]
+async fn test_get_stream_times_out_on_stalled_body() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/stalled", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 1024];
+        let _ = socket.read(&mut buf).await.unwrap();
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\nfirst")
+            .await
+            .unwrap();
+        // Never send the rest
+        tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+    });
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(config(&temp_dir));
+
+    let mut stream = client.get_stream(&url).await.unwrap();
+    assert_eq!(stream.next().await.unwrap().unwrap(), "first");
+    match stream.next().await.unwrap() {
+        Err(ApiError::Timeout(detail)) => assert_eq!(detail.phase, TimeoutPhase::Read),
+        other => panic!("expected a read timeout, got {:?}", other),
+    }
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_response_to_stream_accepts_boxed_bodies() {
+    let temp_dir = TempDir::new().unwrap();
+    let handler = StreamHandler::new(config(&temp_dir));
+
+    let body: BoxBody<Bytes, BoxError> = Full::new(Bytes::from("hello"))
+        .map_err(|e| match e {})
+        .boxed();
+    let chunks: Vec<_> = handler
+        .response_to_stream(hyper::Response::new(body))
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].as_ref().unwrap(), "hello");
+
+    let frames = futures::stream::iter([
+        Ok(Frame::data(Bytes::from("partial"))),
+        Err::<Frame<Bytes>, BoxError>("connection reset".into()),
+    ]);
+    let body: BoxBody<Bytes, BoxError> = BodyExt::boxed(StreamBody::new(frames));
+    let chunks: Vec<_> = handler
+        .response_to_stream(hyper::Response::new(body))
+        .collect()
+        .await;
+    assert_eq!(chunks[0].as_ref().unwrap(), "partial");
+    let error = chunks[1].as_ref().unwrap_err();
+    assert!(matches!(error, ApiError::Stream(_)), "{:?}", error);
+    assert!(error.to_string().contains("connection reset"), "{}", error);
+}
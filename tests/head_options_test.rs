@@ -0,0 +1,163 @@
+use hyper::StatusCode;
+use kick::plugin::{PluginContext, PluginHook};
+use kick::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Counts pre-request and post-request hook calls
+#[derive(Default)]
+struct HookCounter {
+    pre: AtomicU32,
+    post: AtomicU32,
+}
+
+#[async_trait]
+impl Plugin for HookCounter {
+    fn name(&self) -> &str {
+        "hook_counter"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Counts request hook calls"
+    }
+
+    fn handles_hook(&self, hook: &PluginHook) -> bool {
+        matches!(hook, PluginHook::PreRequest | PluginHook::PostRequest)
+    }
+
+    async fn handle_pre_request(&self, _url: &str, _context: &PluginContext) -> Result<()> {
+        self.pre.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn handle_post_request(
+        &self,
+        _url: &str,
+        _status: u16,
+        _context: &PluginContext,
+    ) -> Result<()> {
+        self.post.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_head_returns_headers_and_runs_hooks() {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/files/big.iso"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "application/octet-stream")
+                .set_body_bytes(vec![0u8; 4096]),
+        )
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let counter = Arc::new(HookCounter::default());
+    let mut plugins = PluginManager::new();
+    plugins.register_plugin(counter.clone()).unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_plugin_manager(plugins)
+        .build()
+        .await
+        .unwrap();
+
+    let response = client
+        .head(&format!("{}/files/big.iso", server.uri()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.headers["Content-Length"], "4096");
+    assert_eq!(response.headers["Content-Type"], "application/octet-stream");
+    assert!(response.body.is_empty());
+    assert_eq!(counter.pre.load(Ordering::SeqCst), 1);
+    assert_eq!(counter.post.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_head_ignores_body_sent_by_server() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/status", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 1024];
+        let _ = socket.read(&mut buf).await.unwrap();
+        socket
+            .write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nContent-Type: text/plain\r\n\r\nhello",
+            )
+            .await
+            .unwrap();
+    });
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let response = client.head(&url).await.unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.headers["Content-Length"], "5");
+    assert!(response.body.is_empty());
+}
+
+#[tokio::test]
+async fn test_head_missing_resource_is_status_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let error = client
+        .head(&format!("{}/gone", server.uri()))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ApiError::HttpStatus { status, .. } if status == StatusCode::NOT_FOUND)
+    );
+}
+
+#[tokio::test]
+async fn test_options_response_keeps_cors_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("OPTIONS"))
+        .and(path("/items"))
+        .respond_with(
+            ResponseTemplate::new(204)
+                .insert_header("allow", "GET, POST, OPTIONS")
+                .insert_header("access-control-allow-origin", "*")
+                .insert_header("access-control-allow-methods", "GET, POST"),
+        )
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let response = client
+        .options_response(&format!("{}/items", server.uri()))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+    assert_eq!(response.headers["Allow"], "GET, POST, OPTIONS");
+    assert_eq!(response.headers["Access-Control-Allow-Origin"], "*");
+    assert_eq!(
+        response.headers["Access-Control-Allow-Methods"],
+        "GET, POST"
+    );
+    assert!(response.body.is_empty());
+}
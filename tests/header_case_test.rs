@@ -0,0 +1,75 @@
+mod common;
+
+use common::run_kick;
+use kick::prelude::*;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn header_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/headers"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw(r#"{"ok":true}"#, "application/json")
+                .insert_header("etag", "\"v1\"")
+                .insert_header("x-request-id", "abc123")
+                .append_header("vary", "Accept")
+                .append_header("vary", "Origin"),
+        )
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_response_headers_use_canonical_casing() {
+    let server = header_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let (status, headers, body) = client
+        .get_with_headers(&format!("{}/headers", server.uri()))
+        .await
+        .unwrap();
+
+    assert_eq!(status, 200);
+    assert_eq!(body, r#"{"ok":true}"#);
+    assert_eq!(
+        headers.get("Content-Type").map(String::as_str),
+        Some("application/json")
+    );
+    assert!(!headers.contains_key("content-type"));
+    assert_eq!(headers.get("ETag").map(String::as_str), Some("\"v1\""));
+    assert_eq!(
+        headers.get("X-Request-Id").map(String::as_str),
+        Some("abc123")
+    );
+    assert_eq!(
+        headers.get("Vary").map(String::as_str),
+        Some("Accept, Origin")
+    );
+}
+
+#[tokio::test]
+async fn test_cli_include_prints_canonical_headers() {
+    let server = header_server().await;
+    let config_home = TempDir::new().unwrap();
+
+    let output = run_kick(
+        &config_home,
+        &["get", "-i", &format!("{}/headers", server.uri())],
+    )
+    .await;
+    let stdout = output.stdout;
+
+    assert_eq!(output.status, 0, "{}", stdout);
+    assert!(stdout.contains("HTTP/1.1 200 OK\n"), "{}", stdout);
+    assert!(
+        stdout.contains("Content-Type: application/json\n"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("ETag: \"v1\"\n"), "{}", stdout);
+}
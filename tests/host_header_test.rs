@@ -0,0 +1,41 @@
+use kick::prelude::*;
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_host_header_override_keeps_url_authority() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/vhost"))
+        .and(header("host", "api.internal.example"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("virtual host"))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_host_header("api.internal.example".to_string())
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+
+    // Connects to the mock's 127.0.0.1 address while presenting the other host
+    let body = client
+        .get(&format!("{}/vhost", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(body, "virtual host");
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].headers["host"], "api.internal.example");
+}
+
+#[test]
+fn test_host_header_is_validated() {
+    let result =
+        ApiClientBuilder::new().with_host_header("evil.example\r\nX-Injected: 1".to_string());
+    assert!(result.is_err());
+}
@@ -0,0 +1,121 @@
+use kick::prelude::*;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(temp_dir: &TempDir, max_error_body: usize) -> ApiClient {
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_retries = 0;
+    config.client.max_error_body = max_error_body;
+    ApiClient::new(config)
+}
+
+#[tokio::test]
+async fn test_every_verb_keeps_error_body_and_headers() {
+    let server = MockServer::start().await;
+    Mock::given(path("/users"))
+        .respond_with(
+            ResponseTemplate::new(422)
+                .insert_header("x-request-id", "req-42")
+                .set_body_string("{\"error\":\"name is required\"}\n{\"detail\":\"more\"}"),
+        )
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, 64 * 1024);
+    let url = format!("{}/users", server.uri());
+
+    for error in [
+        client.get(&url).await.unwrap_err(),
+        client.post_json(&url, &json!({})).await.unwrap_err(),
+        client.delete(&url).await.unwrap_err(),
+    ] {
+        match &error {
+            ApiError::HttpStatus {
+                status,
+                body,
+                headers,
+            } => {
+                assert_eq!(status.as_u16(), 422);
+                assert!(
+                    body.starts_with("{\"error\":\"name is required\"}\n"),
+                    "{}",
+                    body
+                );
+                assert_eq!(headers["X-Request-Id"], "req-42");
+            }
+            other => panic!("expected HttpStatus, got {:?}", other),
+        }
+        // Display stays on one line
+        assert_eq!(
+            error.to_string(),
+            "HTTP status error: 422 Unprocessable Entity: {\"error\":\"name is required\"}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_large_error_body_is_truncated() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("x".repeat(100_000)))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+
+    let error = client(&temp_dir, 16).get(&server.uri()).await.unwrap_err();
+    match error {
+        ApiError::HttpStatus { body, .. } => assert_eq!(body, "x".repeat(16)),
+        other => panic!("expected HttpStatus, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_empty_error_body_displays_status_only() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+
+    let error = client(&temp_dir, 1024)
+        .get(&server.uri())
+        .await
+        .unwrap_err();
+    assert_eq!(error.to_string(), "HTTP status error: 404 Not Found");
+}
+
+#[tokio::test]
+async fn test_auth_precondition_and_rate_limit_errors_keep_body() {
+    let server = MockServer::start().await;
+    for (status, route) in [(401, "/me"), (412, "/widgets/1"), (429, "/busy")] {
+        Mock::given(path(route))
+            .respond_with(ResponseTemplate::new(status).set_body_string(format!(
+                "{} {}",
+                status,
+                "x".repeat(100)
+            )))
+            .mount(&server)
+            .await;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, 8);
+
+    match client.get(&format!("{}/me", server.uri())).await {
+        Err(ApiError::Authentication(auth)) => assert_eq!(auth.body, "401 xxxx"),
+        other => panic!("expected Authentication, got {:?}", other),
+    }
+    match client.delete(&format!("{}/widgets/1", server.uri())).await {
+        Err(ApiError::PreconditionFailed { body }) => assert_eq!(body, "412 xxxx"),
+        other => panic!("expected PreconditionFailed, got {:?}", other),
+    }
+    match client.get(&format!("{}/busy", server.uri())).await {
+        Err(ApiError::HttpStatus { status, body, .. }) => {
+            assert_eq!(status.as_u16(), 429);
+            assert_eq!(body, "429 xxxx");
+        }
+        other => panic!("expected HttpStatus, got {:?}", other),
+    }
+}
@@ -0,0 +1,93 @@
+use kick::prelude::*;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Settings {
+    version: u32,
+}
+
+async fn mount_settings(server: &MockServer, route: &str, version: u32, expected_calls: u64) {
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "version": version })),
+        )
+        .expect(expected_calls)
+        .mount(server)
+        .await;
+}
+
+async fn cached_client(capacity: usize, ttl: Duration) -> (ApiClient, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_json_cache(capacity, ttl)
+        .build()
+        .await
+        .unwrap();
+    (client, temp_dir)
+}
+
+#[tokio::test]
+async fn test_hit_within_ttl_skips_request() {
+    let server = MockServer::start().await;
+    mount_settings(&server, "/settings", 1, 1).await;
+    let (client, _dir) = cached_client(4, Duration::from_secs(60)).await;
+    let url = format!("{}/settings", server.uri());
+
+    let first: Settings = client.download_json(&url).await.unwrap();
+    let second: Settings = client.download_json(&url).await.unwrap();
+    assert_eq!(first, Settings { version: 1 });
+    assert_eq!(second, first);
+
+    // A different target type re-deserializes the same cached JSON
+    let raw: serde_json::Value = client.download_json(&url).await.unwrap();
+    assert_eq!(raw["version"], 1);
+}
+
+#[tokio::test]
+async fn test_miss_after_ttl_refetches() {
+    let server = MockServer::start().await;
+    mount_settings(&server, "/settings", 1, 2).await;
+    let (client, _dir) = cached_client(4, Duration::from_millis(50)).await;
+    let url = format!("{}/settings", server.uri());
+
+    let _: Settings = client.download_json(&url).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let _: Settings = client.download_json(&url).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_least_recently_used_evicted_at_capacity() {
+    let server = MockServer::start().await;
+    mount_settings(&server, "/a", 1, 2).await;
+    mount_settings(&server, "/b", 2, 1).await;
+    mount_settings(&server, "/c", 3, 1).await;
+    let (client, _dir) = cached_client(2, Duration::from_secs(60)).await;
+    let url = |route: &str| format!("{}{}", server.uri(), route);
+
+    let _: Settings = client.download_json(&url("/a")).await.unwrap();
+    let _: Settings = client.download_json(&url("/b")).await.unwrap();
+    // Capacity 2: caching /c evicts /a
+    let _: Settings = client.download_json(&url("/c")).await.unwrap();
+
+    let b: Settings = client.download_json(&url("/b")).await.unwrap();
+    let a: Settings = client.download_json(&url("/a")).await.unwrap();
+    assert_eq!(b, Settings { version: 2 });
+    assert_eq!(a, Settings { version: 1 });
+}
+
+#[tokio::test]
+async fn test_client_without_cache_always_fetches() {
+    let server = MockServer::start().await;
+    mount_settings(&server, "/settings", 1, 2).await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let url = format!("{}/settings", server.uri());
+
+    let _: Settings = client.download_json(&url).await.unwrap();
+    let _: Settings = client.download_json(&url).await.unwrap();
+}
@@ -0,0 +1,109 @@
+use kick::prelude::*;
+use kick::JsonRpcError;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(temp_dir: &TempDir) -> ApiClient {
+    ApiClient::new(Config::new(temp_dir.path().to_path_buf()))
+}
+
+#[tokio::test]
+async fn test_batch_correlates_results_and_call_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/rpc"))
+        .and(body_json(json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "add", "params": [1, 2]},
+            {"jsonrpc": "2.0", "id": 2, "method": "missing", "params": {}},
+            {"jsonrpc": "2.0", "id": 3, "method": "echo", "params": ["hi"]},
+        ])))
+        // Out of order, as servers are allowed to reply
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"jsonrpc": "2.0", "id": 3, "result": "hi"},
+            {"jsonrpc": "2.0", "id": 2, "error": {"code": -32601, "message": "Method not found", "data": "missing"}},
+            {"jsonrpc": "2.0", "id": 1, "result": 3},
+        ])))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let calls = [
+        ("add", json!([1, 2])),
+        ("missing", json!({})),
+        ("echo", json!(["hi"])),
+    ];
+    let responses = client(&temp_dir)
+        .jsonrpc_batch(&format!("{}/rpc", server.uri()), &calls)
+        .await
+        .unwrap();
+
+    let ids: Vec<u64> = responses.iter().map(|r| r.id).collect();
+    assert_eq!(ids, [1, 2, 3]);
+    assert_eq!(responses[0].method, "add");
+    assert_eq!(responses[0].result, Ok(json!(3)));
+    assert!(!responses[1].is_ok());
+    assert_eq!(
+        responses[1].result,
+        Err(JsonRpcError {
+            code: -32601,
+            message: "Method not found".to_string(),
+            data: Some(json!("missing")),
+        })
+    );
+    assert_eq!(responses[2].result, Ok(json!("hi")));
+}
+
+#[tokio::test]
+async fn test_transport_and_protocol_failures_fail_the_batch() {
+    let server = MockServer::start().await;
+    Mock::given(path("/down"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    Mock::given(path("/short"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"jsonrpc": "2.0", "id": 1, "result": true},
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(path("/rejected"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!(
+            {"jsonrpc": "2.0", "id": null, "error": {"code": -32700, "message": "Parse error"}}
+        )))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir);
+    let calls = [("ping", json!([])), ("ping", json!([]))];
+
+    let error = client
+        .jsonrpc_batch(&format!("{}/down", server.uri()), &calls)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ApiError::HttpStatus { .. }), "{:?}", error);
+
+    let error = client
+        .jsonrpc_batch(&format!("{}/short", server.uri()), &calls)
+        .await
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("no reply for call 2 (ping)"),
+        "{}",
+        error
+    );
+
+    let error = client
+        .jsonrpc_batch(&format!("{}/rejected", server.uri()), &calls)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("Parse error"), "{}", error);
+
+    assert!(client
+        .jsonrpc_batch(&server.uri(), &[])
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,32 @@
+use kick::prelude::*;
+use kick::LatencyPlugin;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_latency_plugin_records_request_durations() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/slow"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(60)))
+        .mount(&server)
+        .await;
+
+    let latency = Arc::new(LatencyPlugin::new());
+    let mut plugins = PluginManager::new();
+    plugins.register_plugin(latency.clone()).unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf())).with_plugins(plugins);
+
+    for _ in 0..3 {
+        client.get(&format!("{}/slow", server.uri())).await.unwrap();
+    }
+
+    assert_eq!(latency.count(), 3);
+    let p50 = latency.p50().unwrap();
+    assert!(p50 >= Duration::from_millis(59), "{:?}", p50);
+    assert!(latency.p99().unwrap() >= p50);
+}
@@ -0,0 +1,151 @@
+use hyper::Method;
+use kick::prelude::*;
+use kick::ErrorKind;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// Answers with a redirect back to the same path, then 503, then a redirect again, ...
+struct RedirectThenUnavailable {
+    calls: AtomicUsize,
+}
+
+impl Respond for RedirectThenUnavailable {
+    fn respond(&self, _request: &Request) -> ResponseTemplate {
+        if self.calls.fetch_add(1, Ordering::SeqCst).is_multiple_of(2) {
+            ResponseTemplate::new(302).insert_header("Location", "/flaky")
+        } else {
+            ResponseTemplate::new(503)
+        }
+    }
+}
+
+async fn flaky_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/flaky"))
+        .respond_with(RedirectThenUnavailable {
+            calls: AtomicUsize::new(0),
+        })
+        .mount(&server)
+        .await;
+    server
+}
+
+fn client(temp_dir: &TempDir, max_retries: usize, max_total_hops: Option<usize>) -> ApiClient {
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_retries = max_retries;
+    config.client.retry_delay = 1;
+    config.client.max_redirects = 10;
+    config.client.max_total_hops = max_total_hops;
+    ApiClient::new(config)
+}
+
+#[tokio::test]
+async fn test_combined_hop_cap_stops_redirect_retry_loop() {
+    let server = flaky_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, 10, Some(3));
+
+    let url = format!("{}/flaky", server.uri());
+    let error = client
+        .execute_request_with_retry(&url, Method::GET, None)
+        .await
+        .unwrap_err();
+    match &error {
+        ApiError::TooManyHops {
+            url: failed_url,
+            limit,
+        } => {
+            assert_eq!(failed_url, &url);
+            assert_eq!(*limit, 3);
+        }
+        other => panic!("expected TooManyHops, got {:?}", other),
+    }
+    assert_eq!(error.code(), "too_many_hops");
+    assert!(error.to_string().contains("max_total_hops"), "{}", error);
+    // Redirect, retry, redirect use up the cap; the 503 that follows needs a fourth hop
+    assert_eq!(server.received_requests().await.unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_individual_limits_apply_without_hop_cap() {
+    let server = flaky_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, 2, None);
+
+    let error = client
+        .execute_request_with_retry(&format!("{}/flaky", server.uri()), Method::GET, None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ServerStatus);
+    assert_eq!(server.received_requests().await.unwrap().len(), 6);
+}
+
+#[tokio::test]
+async fn test_redirect_is_followed_to_the_response_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/submit"))
+        .respond_with(ResponseTemplate::new(303).insert_header("Location", "/result"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/result"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("done"))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, 0, Some(1));
+
+    let body = client
+        .execute_request_with_retry(
+            &format!("{}/submit", server.uri()),
+            Method::POST,
+            Some(&serde_json::json!({"a": 1})),
+        )
+        .await
+        .unwrap();
+    assert_eq!(body, "done");
+}
+
+#[tokio::test]
+async fn test_redirect_to_other_origin_drops_credentials() {
+    let api = MockServer::start().await;
+    let other = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/moved"))
+        .respond_with(
+            ResponseTemplate::new(302)
+                .insert_header("Location", format!("{}/landing", other.uri())),
+        )
+        .mount(&api)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/landing"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("landed"))
+        .mount(&other)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_redirects = 10;
+    config
+        .client
+        .default_headers
+        .insert("Authorization".to_string(), "Bearer secret".to_string());
+    let client = ApiClient::new(config);
+
+    let body = client
+        .execute_request_with_retry(&format!("{}/moved", api.uri()), Method::GET, None)
+        .await
+        .unwrap();
+
+    assert_eq!(body, "landed");
+    assert_eq!(
+        api.received_requests().await.unwrap()[0].headers["authorization"],
+        "Bearer secret"
+    );
+    let landed = other.received_requests().await.unwrap();
+    assert!(!landed[0].headers.contains_key("authorization"));
+}
@@ -0,0 +1,167 @@
+use kick::prelude::*;
+use kick::MetricsPlugin;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// One sample line: metric name, labels and value
+struct Sample {
+    name: String,
+    labels: HashMap<String, String>,
+    value: f64,
+}
+
+/// Parse the text exposition format strictly enough to reject malformed output
+fn parse_exposition(text: &str) -> (HashMap<String, String>, Vec<Sample>) {
+    let is_name = |name: &str| {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+    };
+    let mut types = HashMap::new();
+    let mut samples = Vec::new();
+
+    for line in text.lines() {
+        if let Some(comment) = line.strip_prefix("# ") {
+            let mut parts = comment.splitn(3, ' ');
+            let (keyword, name, rest) = (
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+            );
+            assert!(is_name(name), "bad metric name in {:?}", line);
+            if keyword == "TYPE" {
+                assert!(
+                    ["counter", "gauge", "histogram", "summary", "untyped"].contains(&rest),
+                    "{}",
+                    line
+                );
+                assert!(
+                    types.insert(name.to_string(), rest.to_string()).is_none(),
+                    "duplicate TYPE {}",
+                    name
+                );
+            } else {
+                assert_eq!(keyword, "HELP", "{}", line);
+            }
+            continue;
+        }
+
+        let (series, value) = line
+            .rsplit_once(' ')
+            .unwrap_or_else(|| panic!("no value in {:?}", line));
+        let value: f64 = value
+            .parse()
+            .unwrap_or_else(|_| panic!("bad value in {:?}", line));
+        let (name, labels) = match series.split_once('{') {
+            Some((name, rest)) => {
+                let body = rest
+                    .strip_suffix('}')
+                    .unwrap_or_else(|| panic!("unclosed labels in {:?}", line));
+                let mut labels = HashMap::new();
+                let mut rest = body;
+                while !rest.is_empty() {
+                    let (label, after) = rest.split_once("=\"").unwrap();
+                    assert!(is_name(label), "bad label name in {:?}", line);
+                    let mut value = String::new();
+                    let mut chars = after.char_indices();
+                    let end = loop {
+                        match chars
+                            .next()
+                            .unwrap_or_else(|| panic!("unterminated label in {:?}", line))
+                        {
+                            (_, '\\') => match chars.next().unwrap().1 {
+                                'n' => value.push('\n'),
+                                escaped => value.push(escaped),
+                            },
+                            (i, '"') => break i,
+                            (_, c) => value.push(c),
+                        }
+                    };
+                    labels.insert(label.to_string(), value);
+                    rest = after[end + 1..]
+                        .strip_prefix(',')
+                        .unwrap_or(&after[end + 1..]);
+                }
+                (name, labels)
+            }
+            None => (series, HashMap::new()),
+        };
+        assert!(is_name(name), "bad metric name in {:?}", line);
+        let family = ["_bucket", "_sum", "_count"]
+            .iter()
+            .find_map(|suffix| {
+                name.strip_suffix(suffix)
+                    .filter(|base| types.get(*base).is_some_and(|t| t == "histogram"))
+            })
+            .unwrap_or(name);
+        assert!(
+            types.contains_key(family),
+            "sample before its TYPE: {:?}",
+            line
+        );
+        samples.push(Sample {
+            name: name.to_string(),
+            labels,
+            value,
+        });
+    }
+    (types, samples)
+}
+
+#[tokio::test]
+async fn test_render_prometheus_is_valid_and_counts_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/ok"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/missing"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let metrics = Arc::new(
+        MetricsPlugin::new()
+            .with_prefix("api")
+            .with_label("service", "billing"),
+    );
+    let mut plugins = PluginManager::new();
+    plugins.register_plugin(metrics.clone()).unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf())).with_plugins(plugins);
+
+    for _ in 0..2 {
+        client.get(&format!("{}/ok", server.uri())).await.unwrap();
+    }
+    let _ = client.get(&format!("{}/missing", server.uri())).await;
+
+    let text = metrics.render_prometheus();
+    let (types, samples) = parse_exposition(&text);
+    assert_eq!(types["api_requests_total"], "counter");
+    assert_eq!(types["api_request_duration_seconds"], "histogram");
+
+    let requests = |status: &str| {
+        samples
+            .iter()
+            .find(|s| s.name == "api_requests_total" && s.labels["status"] == status)
+            .unwrap_or_else(|| panic!("no series for status {} in\n{}", status, text))
+    };
+    assert_eq!(requests("200").value, 2.0);
+    assert_eq!(requests("404").value, 1.0);
+    assert_eq!(requests("200").labels["host"], "127.0.0.1");
+    assert_eq!(requests("200").labels["service"], "billing");
+
+    let count = samples
+        .iter()
+        .find(|s| s.name == "api_request_duration_seconds_count")
+        .unwrap();
+    assert_eq!(count.value, 3.0);
+    assert_eq!(metrics.request_count(), 3);
+}
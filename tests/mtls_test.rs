@@ -0,0 +1,176 @@
+#![cfg(feature = "rustls")]
+
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use kick::config::TlsConfig;
+use kick::prelude::*;
+use rcgen::{BasicConstraints, CertificateParams, ExtendedKeyUsagePurpose, IsCa, KeyPair};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::server::WebPkiClientVerifier;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+
+struct Pki {
+    dir: TempDir,
+    ca_der: CertificateDer<'static>,
+    server_der: CertificateDer<'static>,
+    server_key: PrivateKeyDer<'static>,
+}
+
+impl Pki {
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.path().join(name)
+    }
+}
+
+/// Generate a private CA with a localhost server cert and a client cert, written as PEM files
+fn generate_pki() -> Pki {
+    let dir = TempDir::new().unwrap();
+
+    let ca_key = KeyPair::generate().unwrap();
+    let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+
+    let server_key = KeyPair::generate().unwrap();
+    let mut server_params = CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+    server_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+    let server_cert = server_params
+        .signed_by(&server_key, &ca_cert, &ca_key)
+        .unwrap();
+
+    let client_key = KeyPair::generate().unwrap();
+    let mut client_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+    client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+    let client_cert = client_params
+        .signed_by(&client_key, &ca_cert, &ca_key)
+        .unwrap();
+
+    std::fs::write(dir.path().join("ca.pem"), ca_cert.pem()).unwrap();
+    std::fs::write(dir.path().join("client.pem"), client_cert.pem()).unwrap();
+    std::fs::write(dir.path().join("client.key"), client_key.serialize_pem()).unwrap();
+
+    Pki {
+        dir,
+        ca_der: ca_cert.der().clone(),
+        server_der: server_cert.der().clone(),
+        server_key: PrivatePkcs8KeyDer::from(server_key.serialize_der()).into(),
+    }
+}
+
+/// Serve "mtls-ok" over TLS, requiring a client certificate issued by the test CA
+async fn start_mtls_server(pki: &Pki) -> u16 {
+    let provider = std::sync::Arc::new(rustls::crypto::ring::default_provider());
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add(pki.ca_der.clone()).unwrap();
+    let verifier = WebPkiClientVerifier::builder_with_provider(roots.into(), provider.clone())
+        .build()
+        .unwrap();
+    let server_config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(vec![pki.server_der.clone()], pki.server_key.clone_key())
+        .unwrap();
+    let acceptor = TlsAcceptor::from(std::sync::Arc::new(server_config));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(_) => return,
+            };
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                // Handshake failures (e.g. missing client cert) are expected in some tests
+                if let Ok(tls) = acceptor.accept(stream).await {
+                    let service = service_fn(|_req: Request<hyper::body::Incoming>| async {
+                        Ok::<_, hyper::Error>(Response::new(Full::new(Bytes::from("mtls-ok"))))
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(tls), service)
+                        .await;
+                }
+            });
+        }
+    });
+
+    port
+}
+
+fn config_with_tls(tls: TlsConfig) -> Config {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.tls = tls;
+    config
+}
+
+#[tokio::test]
+async fn test_mtls_request_with_client_certificate() {
+    let pki = generate_pki();
+    let port = start_mtls_server(&pki).await;
+
+    let config = config_with_tls(TlsConfig {
+        ca_bundle: Some(pki.path("ca.pem")),
+        client_cert: Some(pki.path("client.pem")),
+        client_key: Some(pki.path("client.key")),
+    });
+    let client = ApiClientBuilder::new()
+        .with_config(config)
+        .build()
+        .await
+        .expect("Failed to create client with mTLS config");
+
+    let response = client
+        .get(&format!("https://localhost:{}/", port))
+        .await
+        .expect("mTLS request failed");
+    assert_eq!(response, "mtls-ok");
+}
+
+#[tokio::test]
+async fn test_mtls_request_rejected_without_client_certificate() {
+    let pki = generate_pki();
+    let port = start_mtls_server(&pki).await;
+
+    let config = config_with_tls(TlsConfig {
+        ca_bundle: Some(pki.path("ca.pem")),
+        ..TlsConfig::default()
+    });
+    let client = ApiClientBuilder::new()
+        .with_config(config)
+        .build()
+        .await
+        .expect("Failed to create client with CA bundle");
+
+    let result = client.get(&format!("https://localhost:{}/", port)).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_missing_client_key_file_is_config_error() {
+    let pki = generate_pki();
+    let config = config_with_tls(TlsConfig {
+        ca_bundle: Some(pki.path("ca.pem")),
+        client_cert: Some(pki.path("client.pem")),
+        client_key: Some(pki.path("missing.key")),
+    });
+
+    let result = ApiClientBuilder::new().with_config(config).build().await;
+    assert!(matches!(
+        result,
+        Err(ApiError::Build(kick::BuildError::MissingCertFile {
+            what: "client key",
+            ..
+        }))
+    ));
+}
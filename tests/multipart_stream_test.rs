@@ -0,0 +1,142 @@
+use bytes::Bytes;
+use futures::channel::mpsc;
+use futures::{FutureExt, StreamExt};
+use kick::prelude::*;
+use kick::streaming::{MultipartStream, Part};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+type Chunk = std::result::Result<Bytes, std::io::Error>;
+
+const MIXED_REPLACE: &[u8] = b"--frame\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+first\r\n\
+--frame\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+second part\r\n\
+--frame--\r\n";
+
+fn bodies(parts: &[Part]) -> Vec<&[u8]> {
+    parts.iter().map(|part| &part.body[..]).collect()
+}
+
+#[tokio::test]
+async fn test_parts_are_yielded_as_they_complete() {
+    let (tx, rx) = mpsc::unbounded::<Chunk>();
+    let mut parts = MultipartStream::new(rx, "frame");
+    let send = |chunk: &'static [u8]| tx.unbounded_send(Ok(Bytes::from_static(chunk))).unwrap();
+
+    send(b"--frame\r\nContent-Type: image/jpeg\r\n\r\nfra");
+    assert!(parts.next().now_or_never().is_none(), "part is still open");
+
+    // The closing delimiter arrives split across two chunks
+    send(b"me one\r\n--fr");
+    assert!(
+        parts.next().now_or_never().is_none(),
+        "boundary is incomplete"
+    );
+    send(b"ame\r\nContent-Type: image/jpeg\r\n\r\nframe two");
+
+    let first = parts.next().now_or_never().flatten().unwrap().unwrap();
+    assert_eq!(first.content_type(), Some("image/jpeg"));
+    assert_eq!(&first.body[..], b"frame one");
+    assert!(
+        parts.next().now_or_never().is_none(),
+        "second part is still open"
+    );
+
+    send(b"\r\n--frame\r\n");
+    let second = parts.next().now_or_never().flatten().unwrap().unwrap();
+    assert_eq!(&second.body[..], b"frame two");
+
+    send(b"Content-Type: image/jpeg\r\n\r\nframe three\r\n--frame--\r\n");
+    drop(tx);
+    let third = parts.next().await.unwrap().unwrap();
+    assert_eq!(&third.body[..], b"frame three");
+    assert!(parts.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_parts_survive_every_chunk_split() {
+    for split in 1..MIXED_REPLACE.len() {
+        let chunks: Vec<Chunk> = MIXED_REPLACE
+            .chunks(split)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+        let parts: Vec<Part> = MultipartStream::new(futures::stream::iter(chunks), "frame")
+            .map(|part| part.unwrap())
+            .collect()
+            .await;
+        assert_eq!(
+            bodies(&parts),
+            vec![&b"first"[..], &b"second part"[..]],
+            "chunk size {}",
+            split
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_preamble_and_epilogue_are_ignored() {
+    let body = b"preamble text\r\n--b\r\n\r\nonly\r\n--b--\r\nepilogue --b\r\n";
+    let stream = futures::stream::iter(vec![Ok::<_, std::io::Error>(Bytes::from_static(body))]);
+    let parts: Vec<Part> = MultipartStream::new(stream, "b")
+        .map(|part| part.unwrap())
+        .collect()
+        .await;
+    assert_eq!(bodies(&parts), vec![&b"only"[..]]);
+    assert!(parts[0].headers.is_empty());
+}
+
+#[tokio::test]
+async fn test_stream_cut_mid_part_is_an_error() {
+    let body = b"--frame\r\n\r\ncomplete\r\n--frame\r\n\r\ntrunc";
+    let stream = futures::stream::iter(vec![Ok::<_, std::io::Error>(Bytes::from_static(body))]);
+    let results: Vec<_> = MultipartStream::new(stream, "frame").collect().await;
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(&results[0].as_ref().unwrap().body[..], b"complete");
+    assert!(matches!(results[1], Err(ApiError::Stream(_))));
+}
+
+#[tokio::test]
+async fn test_get_multipart_reads_boundary_from_content_type() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/push"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "multipart/x-mixed-replace; boundary=frame")
+                .set_body_bytes(MIXED_REPLACE),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/plain"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("not multipart"))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let parts: Vec<Part> = client
+        .get_multipart(&format!("{}/push", server.uri()))
+        .await
+        .unwrap()
+        .map(|part| part.unwrap())
+        .collect()
+        .await;
+    assert_eq!(bodies(&parts), vec![&b"first"[..], &b"second part"[..]]);
+    assert_eq!(parts[1].content_type(), Some("text/plain"));
+
+    let err = client
+        .get_multipart(&format!("{}/plain", server.uri()))
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, ApiError::Stream(_)));
+}
@@ -0,0 +1,87 @@
+use bytes::Bytes;
+use futures::StreamExt;
+use kick::prelude::*;
+use kick::streaming::{boundary_from_content_type, MultipartStream};
+use kick::MultipartForm;
+use tempfile::TempDir;
+use wiremock::matchers::{header_regex, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_post_multipart_streams_fields_and_files() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/upload"))
+        .and(header_regex(
+            "content-type",
+            "^multipart/form-data; boundary=kick-",
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_string("stored"))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    // Content that looks like multipart framing, including the boundary prefix
+    let mut content = b"--kick-\r\n--kick-00000000000000000000000000000000--\r\n".to_vec();
+    content.extend((0..50_000u32).map(|i| (i % 251) as u8));
+    let file_path = temp_dir.path().join("report.bin");
+    std::fs::write(&file_path, &content).unwrap();
+
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let form = MultipartForm::new()
+        .text("title", "Q3 \"final\" report")
+        .file("attachment", &file_path, "application/octet-stream");
+    let body = client
+        .post_multipart(&format!("{}/upload", server.uri()), form)
+        .await
+        .unwrap();
+    assert_eq!(body, "stored");
+
+    let request = &server.received_requests().await.unwrap()[0];
+    let content_type = request.headers["content-type"].to_str().unwrap();
+    let boundary = boundary_from_content_type(content_type).unwrap();
+    assert!(!content
+        .windows(boundary.len())
+        .any(|window| window == boundary.as_bytes()));
+    assert_eq!(
+        request.headers["content-length"].to_str().unwrap(),
+        request.body.len().to_string()
+    );
+
+    let chunks = vec![Ok::<_, std::io::Error>(Bytes::from(request.body.clone()))];
+    let parts: Vec<_> = MultipartStream::new(futures::stream::iter(chunks), &boundary)
+        .map(|part| part.unwrap())
+        .collect()
+        .await;
+    assert_eq!(parts.len(), 2);
+    assert_eq!(
+        parts[0].headers["content-disposition"],
+        "form-data; name=\"title\""
+    );
+    assert_eq!(parts[0].body, "Q3 \"final\" report");
+    assert_eq!(
+        parts[1].headers["content-disposition"],
+        "form-data; name=\"attachment\"; filename=\"report.bin\""
+    );
+    assert_eq!(parts[1].content_type(), Some("application/octet-stream"));
+    assert_eq!(parts[1].body, content);
+}
+
+#[tokio::test]
+async fn test_post_multipart_missing_file_fails_before_sending() {
+    let server = MockServer::start().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let form = MultipartForm::new().file(
+        "attachment",
+        temp_dir.path().join("absent.bin"),
+        "text/plain",
+    );
+    let result = client
+        .post_multipart(&format!("{}/upload", server.uri()), form)
+        .await;
+
+    assert!(result.is_err());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
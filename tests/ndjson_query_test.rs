@@ -0,0 +1,66 @@
+mod common;
+
+use common::{run_kick, KickOutput};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const LOG: &str = concat!(
+    "{\"level\":\"info\",\"msg\":\"started\",\"ctx\":{\"pid\":7}}\n",
+    "{\"level\":\"error\",\"msg\":\"disk full\",\"ctx\":{\"pid\":7}}\r\n",
+    "not json at all\n",
+    "\n",
+    "{\"msg\":\"no level here\"}\n",
+    "{\"level\":\"warn\",\"msg\":\"slow\",\"ctx\":{\"pid\":9}}",
+);
+
+async fn log_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/logs"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(LOG, "application/x-ndjson"))
+        .mount(&server)
+        .await;
+    server
+}
+
+/// Run `kick` with a fresh, empty config home
+async fn kick(args: &[&str]) -> KickOutput {
+    run_kick(&TempDir::new().unwrap(), args).await
+}
+
+#[tokio::test]
+async fn test_ndjson_query_prints_value_per_line() {
+    let server = log_server().await;
+    let url = format!("{}/logs", server.uri());
+
+    let KickOutput {
+        status,
+        stdout,
+        stderr,
+    } = kick(&["get", "--ndjson", "--query", "/level", &url]).await;
+
+    assert_eq!(status, 0, "{}", stderr);
+    assert_eq!(stdout, "info\nerror\nwarn\n");
+    // Bad lines are reported and skipped, not fatal
+    assert!(stderr.contains("line 3: invalid JSON"), "{}", stderr);
+    assert!(stderr.contains("line 5: no value at /level"), "{}", stderr);
+
+    let output = kick(&["get", "--ndjson", "-q", "/ctx/pid", &url]).await;
+    assert_eq!(output.stdout, "7\n7\n9\n");
+}
+
+#[tokio::test]
+async fn test_ndjson_without_query_prints_records() {
+    let server = log_server().await;
+    let url = format!("{}/logs", server.uri());
+
+    let output = kick(&["get", "--ndjson", &url]).await;
+    let records: Vec<serde_json::Value> = output
+        .stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[3]["msg"], "slow");
+}
@@ -0,0 +1,74 @@
+use futures::stream;
+use kick::prelude::*;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn test_client(temp_dir: &TempDir) -> ApiClient {
+    ApiClient::new(Config::new(temp_dir.path().to_path_buf()))
+}
+
+#[tokio::test]
+async fn test_post_ndjson_stream_sends_one_record_per_line() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/bulk"))
+        .and(header("content-type", "application/x-ndjson"))
+        .and(header("transfer-encoding", "chunked"))
+        .respond_with(ResponseTemplate::new(202).set_body_string("accepted"))
+        .mount(&server)
+        .await;
+
+    let records: Vec<_> = (0..50)
+        .map(|id| json!({"id": id, "name": format!("record {}", id), "tags": ["a", "b\nc"]}))
+        .collect();
+    let temp_dir = TempDir::new().unwrap();
+    let body = test_client(&temp_dir)
+        .post_ndjson_stream(
+            &format!("{}/bulk", server.uri()),
+            stream::iter(records.clone().into_iter().map(Ok)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(body, "accepted");
+
+    let requests = server.received_requests().await.unwrap();
+    let text = String::from_utf8(requests[0].body.clone()).unwrap();
+    assert!(text.ends_with('\n'));
+    let received: Vec<serde_json::Value> = text
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(received, records);
+}
+
+#[tokio::test]
+async fn test_post_ndjson_stream_error_aborts_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let items = vec![
+        Ok(json!({"id": 1})),
+        Err(ApiError::other("source database went away")),
+        Ok(json!({"id": 2})),
+    ];
+    let temp_dir = TempDir::new().unwrap();
+    let error = test_client(&temp_dir)
+        .post_ndjson_stream(&server.uri(), stream::iter(items))
+        .await
+        .unwrap_err();
+
+    let mut chain = error.to_string();
+    let mut source = std::error::Error::source(&error);
+    while let Some(cause) = source {
+        chain.push_str(&format!(": {}", cause));
+        source = cause.source();
+    }
+    assert!(chain.contains("source database went away"), "{}", chain);
+    // The server never saw a complete request
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
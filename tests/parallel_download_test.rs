@@ -0,0 +1,107 @@
+use kick::prelude::*;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+fn content() -> Vec<u8> {
+    (0..100_003u32).map(|i| (i % 251) as u8).collect()
+}
+
+/// Serves `content` whole or as any single `bytes=start-end` range
+struct RangeResponder {
+    content: Vec<u8>,
+}
+
+impl Respond for RangeResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let range = request
+            .headers
+            .get("range")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("bytes="))
+            .and_then(|value| value.split_once('-'))
+            .and_then(|(start, end)| {
+                Some((start.parse::<usize>().ok()?, end.parse::<usize>().ok()?))
+            });
+        match range {
+            Some((start, end)) => ResponseTemplate::new(206)
+                .insert_header(
+                    "content-range",
+                    format!("bytes {}-{}/{}", start, end, self.content.len()).as_str(),
+                )
+                .set_body_bytes(self.content[start..=end].to_vec()),
+            None => ResponseTemplate::new(200).set_body_bytes(self.content.clone()),
+        }
+    }
+}
+
+async fn server(accept_ranges: bool) -> MockServer {
+    let server = MockServer::start().await;
+    let mut head = ResponseTemplate::new(200).set_body_bytes(content());
+    if accept_ranges {
+        head = head.insert_header("accept-ranges", "bytes");
+    }
+    Mock::given(method("HEAD"))
+        .and(path("/large.bin"))
+        .respond_with(head)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/large.bin"))
+        .respond_with(RangeResponder { content: content() })
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn range_headers(server: &MockServer) -> Vec<String> {
+    let mut ranges: Vec<String> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|request| request.method.as_str() == "GET")
+        .filter_map(|request| request.headers.get("range"))
+        .map(|value| value.to_str().unwrap().to_string())
+        .collect();
+    ranges.sort();
+    ranges
+}
+
+#[tokio::test]
+async fn test_parallel_download_assembles_ranges() {
+    let server = server(true).await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let saved = client
+        .download_file_parallel(&format!("{}/large.bin", server.uri()), "large.bin", 4)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&saved).unwrap(), content());
+    assert_eq!(
+        range_headers(&server).await,
+        [
+            "bytes=0-25000",
+            "bytes=25001-50001",
+            "bytes=50002-75002",
+            "bytes=75003-100002",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_parallel_download_falls_back_without_accept_ranges() {
+    let server = server(false).await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let saved = client
+        .download_file_parallel(&format!("{}/large.bin", server.uri()), "large.bin", 4)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&saved).unwrap(), content());
+    assert!(range_headers(&server).await.is_empty());
+}
@@ -0,0 +1,82 @@
+use futures::channel::mpsc;
+use futures::StreamExt;
+use hyper::body::Bytes;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response};
+use hyper_util::rt::TokioIo;
+use kick::prelude::*;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Server that streams each request body straight back as the response body
+async fn echo_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/echo", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let service = service_fn(|request: Request<hyper::body::Incoming>| async {
+                    Response::builder()
+                        .status(201)
+                        .header("x-echo", "yes")
+                        .body(request.into_body())
+                });
+                let _ = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await;
+            });
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn test_pipe_streams_both_directions() {
+    let url = echo_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let (tx, rx) = mpsc::unbounded::<Result<Bytes>>();
+    tx.unbounded_send(Ok(Bytes::from("first chunk;"))).unwrap();
+
+    let (status, headers, mut body) = client.pipe(Method::POST, &url, rx).await.unwrap();
+    assert_eq!(status, 201);
+    assert_eq!(headers["X-Echo"], "yes");
+
+    // The first chunk comes back while the upload is still open
+    assert_eq!(body.next().await.unwrap().unwrap(), "first chunk;");
+    tx.unbounded_send(Ok(Bytes::from("second chunk"))).unwrap();
+    drop(tx);
+    let mut rest = Vec::new();
+    while let Some(chunk) = body.next().await {
+        rest.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(rest, b"second chunk");
+}
+
+#[tokio::test]
+async fn test_pipe_returns_error_statuses_as_data() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(502).set_body_string("upstream down"))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let upload = futures::stream::iter([Ok(Bytes::from("payload"))]);
+
+    let (status, _, body) = client
+        .pipe(Method::PUT, &server.uri(), upload)
+        .await
+        .unwrap();
+    let body: Vec<Bytes> = body.map(|chunk| chunk.unwrap()).collect().await;
+
+    assert_eq!(status, 502);
+    assert_eq!(body.concat(), b"upstream down");
+    assert_eq!(
+        server.received_requests().await.unwrap()[0].body,
+        b"payload"
+    );
+}
@@ -5,11 +5,17 @@ async fn test_plugin_loading_from_config() {
     // Create config with enabled plugins
     let mut config = Config::default();
     config.plugins.enabled_plugins = vec!["logging".to_string(), "rate_limiter".to_string()];
-    
+
     // Add plugin settings
     let mut rate_limiter_settings = serde_json::Map::new();
-    rate_limiter_settings.insert("requests_per_minute".to_string(), serde_json::Value::Number(serde_json::Number::from(30)));
-    config.plugins.plugin_settings.insert("rate_limiter".to_string(), serde_json::Value::Object(rate_limiter_settings));
+    rate_limiter_settings.insert(
+        "requests_per_minute".to_string(),
+        serde_json::Value::Number(serde_json::Number::from(30)),
+    );
+    config.plugins.plugin_settings.insert(
+        "rate_limiter".to_string(),
+        serde_json::Value::Object(rate_limiter_settings),
+    );
 
     // Create client with config-based plugins
     let client = ApiClientBuilder::new()
@@ -19,9 +25,11 @@ async fn test_plugin_loading_from_config() {
         .expect("Failed to create client with plugins");
 
     // Test a simple request to verify plugins are loaded (they shouldn't break anything)
-    let response = client.get("https://httpbin.org/get").await
+    let response = client
+        .get("https://httpbin.org/get")
+        .await
         .expect("GET request should succeed with plugins loaded");
-    
+
     // Just verify we got a valid response
     assert!(response.len() > 0);
     assert!(response.contains("httpbin.org"));
@@ -41,9 +49,11 @@ async fn test_empty_plugin_config() {
         .expect("Failed to create client with empty plugins");
 
     // Test a simple request
-    let response = client.get("https://httpbin.org/get").await
+    let response = client
+        .get("https://httpbin.org/get")
+        .await
         .expect("GET request should succeed with no plugins");
-    
+
     assert!(response.len() > 0);
 }
 
@@ -61,8 +71,10 @@ async fn test_unknown_plugin_graceful_fallback() {
         .expect("Client creation should succeed even with unknown plugin");
 
     // Test should still work
-    let response = client.get("https://httpbin.org/get").await
+    let response = client
+        .get("https://httpbin.org/get")
+        .await
         .expect("GET request should succeed even after plugin loading failure");
-    
+
     assert!(response.len() > 0);
-}
\ No newline at end of file
+}
@@ -0,0 +1,198 @@
+use kick::plugin::PluginContext;
+use kick::prelude::*;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Rejects requests outside `allowed_prefix`, which must come from its settings
+#[derive(Default)]
+struct PrefixGuard {
+    allowed_prefix: Option<String>,
+}
+
+#[async_trait]
+impl Plugin for PrefixGuard {
+    fn name(&self) -> &str {
+        "prefix_guard"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Only allows requests under a configured URL prefix"
+    }
+
+    async fn initialize(&mut self, config: &serde_json::Value) -> Result<()> {
+        let prefix = config
+            .get("allowed_prefix")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ApiError::config("prefix_guard needs allowed_prefix"))?;
+        self.allowed_prefix = Some(prefix.to_string());
+        Ok(())
+    }
+
+    async fn handle_pre_request(&self, url: &str, _context: &PluginContext) -> Result<()> {
+        match &self.allowed_prefix {
+            Some(prefix) if url.starts_with(prefix.as_str()) => Ok(()),
+            _ => Err(ApiError::validation(format!(
+                "{} is outside the allowed prefix",
+                url
+            ))),
+        }
+    }
+}
+
+/// Counts completed requests and writes the total to `summary_path` when finalized
+#[derive(Default)]
+struct RequestSummary {
+    summary_path: PathBuf,
+    completed: AtomicU32,
+}
+
+#[async_trait]
+impl Plugin for RequestSummary {
+    fn name(&self) -> &str {
+        "request_summary"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Writes a request count on shutdown"
+    }
+
+    async fn initialize(&mut self, config: &serde_json::Value) -> Result<()> {
+        let path = config
+            .get("summary_path")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        self.summary_path = PathBuf::from(path);
+        Ok(())
+    }
+
+    async fn handle_post_request(
+        &self,
+        _url: &str,
+        _status: u16,
+        _context: &PluginContext,
+    ) -> Result<()> {
+        self.completed.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn finalize(&self) -> Result<()> {
+        let summary = format!("requests={}\n", self.completed.load(Ordering::SeqCst));
+        tokio::fs::write(&self.summary_path, summary).await?;
+        Ok(())
+    }
+}
+
+/// Fails to finalize, to show the others still get their turn
+struct BrokenFinalizer;
+
+#[async_trait]
+impl Plugin for BrokenFinalizer {
+    fn name(&self) -> &str {
+        "broken_finalizer"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Always fails to finalize"
+    }
+
+    async fn finalize(&self) -> Result<()> {
+        Err(ApiError::plugin("could not flush"))
+    }
+}
+
+#[tokio::test]
+async fn test_register_initializes_plugin_from_settings() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.plugins.plugin_settings.insert(
+        "prefix_guard".to_string(),
+        json!({"allowed_prefix": format!("{}/public/", server.uri())}),
+    );
+
+    let mut plugins = PluginManager::from_config(&config.plugins).await.unwrap();
+    plugins.register(PrefixGuard::default()).await.unwrap();
+    let client = ApiClient::new(config).with_plugins(plugins);
+
+    let body = client
+        .get(&format!("{}/public/a", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(body, "ok");
+    assert!(client
+        .get(&format!("{}/private/a", server.uri()))
+        .await
+        .is_err());
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_register_fails_when_initialize_fails() {
+    let mut plugins = PluginManager::new();
+    let error = plugins.register(PrefixGuard::default()).await.unwrap_err();
+    assert!(error.to_string().contains("prefix_guard"), "{}", error);
+    assert!(plugins.plugins.is_empty());
+
+    plugins
+        .register_with(
+            PrefixGuard::default(),
+            &json!({"allowed_prefix": "https://"}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(plugins.plugins.len(), 1);
+}
+
+#[tokio::test]
+async fn test_shutdown_finalizes_plugins() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let summary_path = temp_dir.path().join("summary.txt");
+    let mut plugins = PluginManager::new();
+    plugins.register(BrokenFinalizer).await.unwrap();
+    plugins
+        .register_with(
+            RequestSummary::default(),
+            &json!({"summary_path": summary_path}),
+        )
+        .await
+        .unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf())).with_plugins(plugins);
+
+    client.get(&server.uri()).await.unwrap();
+    client.get(&server.uri()).await.unwrap();
+    assert!(!summary_path.exists());
+
+    let error = client.shutdown().await.unwrap_err();
+    assert!(error.to_string().contains("could not flush"), "{}", error);
+    assert_eq!(
+        std::fs::read_to_string(&summary_path).unwrap(),
+        "requests=2\n"
+    );
+}
@@ -0,0 +1,73 @@
+use bytes::Bytes;
+use kick::prelude::*;
+use tempfile::TempDir;
+use tokio::sync::mpsc;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn test_client(temp_dir: &TempDir) -> ApiClient {
+    ApiClient::new(Config::new(temp_dir.path().to_path_buf()))
+}
+
+#[tokio::test]
+async fn test_post_channel_sends_chunks_in_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/ingest"))
+        .and(header("transfer-encoding", "chunked"))
+        .and(header("content-type", "text/csv"))
+        .respond_with(ResponseTemplate::new(202).set_body_string("queued"))
+        .mount(&server)
+        .await;
+
+    let (tx, rx) = mpsc::channel(2);
+    let producer = tokio::spawn(async move {
+        for line in ["id,name\n", "1,alpha\n", "2,beta\n", "3,gamma\n"] {
+            tx.send(Bytes::from(line)).await.unwrap();
+        }
+        // Dropping the sender finishes the body
+    });
+
+    let temp_dir = TempDir::new().unwrap();
+    let body = test_client(&temp_dir)
+        .post_channel(&format!("{}/ingest", server.uri()), rx, "text/csv")
+        .await
+        .unwrap();
+    producer.await.unwrap();
+    assert_eq!(body, "queued");
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body, b"id,name\n1,alpha\n2,beta\n3,gamma\n");
+}
+
+#[tokio::test]
+async fn test_post_channel_error_sentinel_aborts_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let (tx, rx) = mpsc::channel::<std::result::Result<Bytes, std::io::Error>>(4);
+    tx.send(Ok(Bytes::from_static(b"partial"))).await.unwrap();
+    tx.send(Err(std::io::Error::other("producer crashed")))
+        .await
+        .unwrap();
+    drop(tx);
+
+    let temp_dir = TempDir::new().unwrap();
+    let error = test_client(&temp_dir)
+        .post_channel(&server.uri(), rx, "application/octet-stream")
+        .await
+        .unwrap_err();
+
+    let mut chain = error.to_string();
+    let mut source = std::error::Error::source(&error);
+    while let Some(cause) = source {
+        chain.push_str(&format!(": {}", cause));
+        source = cause.source();
+    }
+    assert!(chain.contains("producer crashed"), "{}", chain);
+    // The server never saw a complete request
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
@@ -0,0 +1,147 @@
+use kick::plugin::{PluginContext, PluginHook};
+use kick::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+use wiremock::matchers::{body_string, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn test_client(temp_dir: &TempDir) -> ApiClient {
+    ApiClient::new(Config::new(temp_dir.path().to_path_buf()))
+}
+
+/// Counts pre-request and post-request hook calls
+#[derive(Default)]
+struct HookCounter {
+    pre: AtomicU32,
+    post: AtomicU32,
+}
+
+#[async_trait]
+impl Plugin for HookCounter {
+    fn name(&self) -> &str {
+        "hook_counter"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Counts request hook calls"
+    }
+
+    fn handles_hook(&self, hook: &PluginHook) -> bool {
+        matches!(hook, PluginHook::PreRequest | PluginHook::PostRequest)
+    }
+
+    async fn handle_pre_request(&self, _url: &str, _context: &PluginContext) -> Result<()> {
+        self.pre.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn handle_post_request(
+        &self,
+        _url: &str,
+        _status: u16,
+        _context: &PluginContext,
+    ) -> Result<()> {
+        self.post.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_post_form_encodes_fields() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/token"))
+        .and(header("content-type", "application/x-www-form-urlencoded"))
+        .and(body_string(
+            "grant_type=client_credentials&scope=read+write&note=a%26b%3Dc&empty=&city=Z%C3%BCrich",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let body = test_client(&temp_dir)
+        .post_form(
+            &format!("{}/token", server.uri()),
+            &[
+                ("grant_type", "client_credentials"),
+                ("scope", "read write"),
+                ("note", "a&b=c"),
+                ("empty", ""),
+                ("city", "Zürich"),
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(body, "ok");
+}
+
+#[tokio::test]
+async fn test_post_form_without_fields_sends_empty_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/empty"))
+        .and(body_string(""))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    test_client(&temp_dir)
+        .post_form(&format!("{}/empty", server.uri()), &[])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_post_form_runs_request_hooks() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/token"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let counter = Arc::new(HookCounter::default());
+    let mut plugins = PluginManager::new();
+    plugins.register_plugin(counter.clone()).unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_plugin_manager(plugins)
+        .build()
+        .await
+        .unwrap();
+
+    client
+        .post_form(
+            &format!("{}/token", server.uri()),
+            &[("grant_type", "client_credentials")],
+        )
+        .await
+        .unwrap();
+    assert_eq!(counter.pre.load(Ordering::SeqCst), 1);
+    assert_eq!(counter.post.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+#[ignore = "requires network access to httpbin.org"]
+async fn test_post_form_httpbin_echo() {
+    let temp_dir = TempDir::new().unwrap();
+    let response = test_client(&temp_dir)
+        .post_form(
+            "https://httpbin.org/post",
+            &[("name", "kick & co"), ("empty", "")],
+        )
+        .await
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(json["form"]["name"], "kick & co");
+    assert_eq!(json["form"]["empty"], "");
+}
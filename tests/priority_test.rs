@@ -0,0 +1,56 @@
+use kick::prelude::*;
+use kick::ClientEvent;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_high_priority_request_overtakes_queued_low_priority() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(300)))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = Arc::new(
+        ApiClientBuilder::new()
+            .with_config(Config::new(temp_dir.path().to_path_buf()))
+            .with_max_concurrent_requests(1)
+            .build()
+            .await
+            .unwrap(),
+    );
+    let mut rx = client.subscribe();
+
+    // Occupy the only slot, then queue lows ahead of a single high
+    let mut queued = vec![("/busy".to_string(), Priority::Normal)];
+    queued.extend((1..=5).map(|i| (format!("/low/{}", i), Priority::Low)));
+    queued.push(("/high".to_string(), Priority::High));
+
+    let mut tasks = Vec::new();
+    for (path, priority) in queued {
+        let client = client.clone();
+        let url = format!("{}{}", server.uri(), path);
+        tasks.push(tokio::spawn(async move {
+            client.get_with_priority(&url, priority).await
+        }));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+
+    let mut dispatched = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        if let ClientEvent::RequestStarted { url, .. } = event {
+            dispatched.push(url.trim_start_matches(&server.uri()).to_string());
+        }
+    }
+    assert_eq!(
+        dispatched,
+        ["/busy", "/high", "/low/1", "/low/2", "/low/3", "/low/4", "/low/5"]
+    );
+}
@@ -0,0 +1,136 @@
+use kick::config::ClientConfig;
+use kick::prelude::*;
+use kick::BuildError;
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn client_with(
+    temp_dir: &TempDir,
+    edit: impl FnOnce(&mut ClientConfig),
+) -> Result<ApiClient> {
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    edit(&mut config.client);
+    ApiClientBuilder::new().with_config(config).build().await
+}
+
+#[tokio::test]
+async fn test_http_request_goes_through_proxy_in_absolute_form() {
+    let proxy = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("via proxy"))
+        .mount(&proxy)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let proxy_url = proxy.uri().replace("http://", "http://alice:s3cret@");
+    let client = client_with(&temp_dir, |client| client.http_proxy = Some(proxy_url))
+        .await
+        .unwrap();
+
+    let body = client.get("http://api.example.test/items").await.unwrap();
+    assert_eq!(body, "via proxy");
+
+    let requests = proxy.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].url.host_str(), Some("api.example.test"));
+    // "alice:s3cret" in base64
+    assert_eq!(
+        requests[0].headers["proxy-authorization"],
+        "Basic YWxpY2U6czNjcmV0"
+    );
+}
+
+#[tokio::test]
+async fn test_no_proxy_hosts_connect_directly() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/direct"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("direct"))
+        .mount(&server)
+        .await;
+
+    // Nothing listens on the proxy port, so only a direct connection succeeds
+    let unused = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let proxy_url = format!("http://{}", unused.local_addr().unwrap());
+    drop(unused);
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = client_with(&temp_dir, |client| {
+        client.http_proxy = Some(proxy_url);
+        client.no_proxy = vec!["localhost".to_string(), "127.0.0.0/8".to_string()];
+    })
+    .await
+    .unwrap();
+
+    let body = client
+        .get(&format!("{}/direct", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(body, "direct");
+    assert!(client.get("http://api.example.test/direct").await.is_err());
+}
+
+#[tokio::test]
+async fn test_https_request_opens_connect_tunnel() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let proxy_url = format!("http://bob:pw@{}", listener.local_addr().unwrap());
+    let proxy = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = socket.read(&mut buf).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            head.extend_from_slice(&buf[..n]);
+        }
+        socket
+            .write_all(b"HTTP/1.1 403 Forbidden\r\ncontent-length: 0\r\n\r\n")
+            .await
+            .unwrap();
+        String::from_utf8(head).unwrap()
+    });
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = client_with(&temp_dir, |client| {
+        client.https_proxy = Some(proxy_url);
+        client.max_retries = 0;
+    })
+    .await
+    .unwrap();
+
+    assert!(client
+        .get("https://secure.example.test/data")
+        .await
+        .is_err());
+    let head = proxy.await.unwrap();
+    assert!(
+        head.starts_with("CONNECT secure.example.test:443 HTTP/1.1\r\n"),
+        "{}",
+        head
+    );
+    assert!(
+        head.contains("Proxy-Authorization: Basic Ym9iOnB3\r\n"),
+        "{}",
+        head
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_proxy_url_is_build_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let result = client_with(&temp_dir, |client| {
+        client.https_proxy = Some("socks5://proxy.internal:1080".to_string())
+    })
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(ApiError::Build(BuildError::InvalidProxy { .. }))
+    ));
+}
@@ -0,0 +1,111 @@
+use kick::prelude::*;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tempfile::TempDir;
+use tokio::io::{AsyncRead, ReadBuf};
+use wiremock::matchers::{header, header_exists, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn payload() -> Vec<u8> {
+    (0..100_000u32).map(|i| (i % 251) as u8).collect()
+}
+
+fn test_client(temp_dir: &TempDir) -> ApiClient {
+    ApiClient::new(Config::new(temp_dir.path().to_path_buf()))
+}
+
+#[tokio::test]
+async fn test_put_reader_with_known_length() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/blob"))
+        .and(header("content-length", "100000"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("stored"))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data = payload();
+    let body = test_client(&temp_dir)
+        .put_reader(
+            &format!("{}/blob", server.uri()),
+            std::io::Cursor::new(data.clone()),
+            "application/octet-stream",
+            Some(data.len() as u64),
+        )
+        .await
+        .unwrap();
+    assert_eq!(body, "stored");
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body, data);
+}
+
+#[tokio::test]
+async fn test_put_reader_without_length_is_chunked() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/pipe"))
+        .and(header("transfer-encoding", "chunked"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data = payload();
+    test_client(&temp_dir)
+        .put_reader(
+            &format!("{}/pipe", server.uri()),
+            std::io::Cursor::new(data.clone()),
+            "application/octet-stream",
+            None,
+        )
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body, data);
+}
+
+/// Yields some bytes, then fails
+struct FailingReader {
+    sent: bool,
+}
+
+impl AsyncRead for FailingReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        if self.sent {
+            return Poll::Ready(Err(std::io::Error::other("disk on fire")));
+        }
+        self.sent = true;
+        buf.put_slice(b"partial");
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn test_put_reader_error_aborts_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(header_exists("content-type"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let result = test_client(&temp_dir)
+        .put_reader(
+            &server.uri(),
+            FailingReader { sent: false },
+            "text/plain",
+            None,
+        )
+        .await;
+    assert!(result.is_err());
+    // The server never saw a complete request
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
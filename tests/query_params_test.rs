@@ -0,0 +1,34 @@
+use kick::prelude::*;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_get_with_query_sends_encoded_pairs() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search"))
+        .and(query_param("page", "2"))
+        .and(query_param("q", "a b&c"))
+        .and(query_param("sort", "asc"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("found"))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let body = client
+        .get_with_query(
+            &format!("{}/search?sort=asc", server.uri()),
+            &[("page", "2"), ("q", "a b&c"), ("tag", "a"), ("tag", "b")],
+        )
+        .await
+        .unwrap();
+    assert_eq!(body, "found");
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(
+        requests[0].url.query(),
+        Some("sort=asc&page=2&q=a%20b%26c&tag=a&tag=b")
+    );
+}
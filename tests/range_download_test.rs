@@ -0,0 +1,117 @@
+mod common;
+
+use common::run_kick;
+use kick::prelude::*;
+use kick::{ByteRange, RequestOptions};
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Server holding a 4 KiB resource that honours `Range: bytes=0-1023`
+async fn range_server() -> MockServer {
+    let server = MockServer::start().await;
+    let content: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+    Mock::given(method("GET"))
+        .and(path("/blob.bin"))
+        .and(header("range", "bytes=0-1023"))
+        .respond_with(
+            ResponseTemplate::new(206)
+                .insert_header("content-range", "bytes 0-1023/4096")
+                .set_body_bytes(content[..1024].to_vec()),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/blob.bin"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(content))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_range_download_writes_partial_content() {
+    let server = range_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let url = format!("{}/blob.bin", server.uri());
+    let range: ByteRange = "0-1023".parse().unwrap();
+    let options = RequestOptions::new().range(range);
+    let saved = client
+        .download_file_with(&url, "head.bin", false, &options)
+        .await
+        .unwrap();
+
+    let bytes = std::fs::read(&saved).unwrap();
+    assert_eq!(bytes.len() as u64, range.byte_count().unwrap());
+    // Binary content survives intact
+    assert_eq!(bytes[250..252], [250, 0]);
+}
+
+#[tokio::test]
+async fn test_cli_range_download() {
+    let server = range_server().await;
+    let work_dir = TempDir::new().unwrap();
+
+    let url = format!("{}/blob.bin", server.uri());
+    let output = run_kick(
+        &work_dir,
+        &[
+            "download", "--local", "--range", "0-1023", "-o", "head.bin", &url,
+        ],
+    )
+    .await;
+    assert_eq!(output.status, 0, "{}", output.stderr);
+
+    let saved = work_dir.path().join(".downloads/head.bin");
+    assert_eq!(std::fs::metadata(saved).unwrap().len(), 1024);
+}
+
+#[tokio::test]
+async fn test_cli_invalid_range_fails_before_sending() {
+    let server = range_server().await;
+    let work_dir = TempDir::new().unwrap();
+
+    let url = format!("{}/blob.bin", server.uri());
+    let output = run_kick(
+        &work_dir,
+        &[
+            "download", "--local", "--range", "1023-0", "-o", "head.bin", &url,
+        ],
+    )
+    .await;
+    assert_eq!(output.status, 2);
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_failed_range_download_keeps_existing_file() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/blob.bin"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![7u8; 4096]))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let downloads = temp_dir.path().join("downloads");
+    std::fs::create_dir_all(&downloads).unwrap();
+    std::fs::write(downloads.join("head.bin"), "original").unwrap();
+
+    // The server ignores the range, so the full body overruns max_size
+    let options = RequestOptions::new()
+        .range("0-1023".parse().unwrap())
+        .max_size(1024);
+    let url = format!("{}/blob.bin", server.uri());
+    assert!(client
+        .download_file_with(&url, "head.bin", false, &options)
+        .await
+        .is_err());
+
+    assert_eq!(
+        std::fs::read_to_string(downloads.join("head.bin")).unwrap(),
+        "original"
+    );
+    assert_eq!(std::fs::read_dir(&downloads).unwrap().count(), 1);
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::run_kick;
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_raw_xml_body_is_sent_unchanged() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/orders"))
+        .and(header("content-type", "application/xml"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("created"))
+        .mount(&server)
+        .await;
+    let home = TempDir::new().unwrap();
+
+    let xml = "<?xml version=\"1.0\"?>\n<order id=\"7\">  <item qty=\"2\">widget</item></order>";
+    let url = format!("{}/orders", server.uri());
+    let output = run_kick(
+        &home,
+        &[
+            "post",
+            "--raw",
+            "--content-type",
+            "application/xml",
+            "-d",
+            xml,
+            &url,
+        ],
+    )
+    .await;
+    assert_eq!(output.status, 0, "{}", output.stderr);
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body, xml.as_bytes());
+}
+
+#[tokio::test]
+async fn test_raw_body_from_file_keeps_json_bytes() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(header("content-type", "application/json"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let home = TempDir::new().unwrap();
+
+    // Key order and spacing would not survive a parse and re-serialize
+    let json = "{\"z\": 1, \"a\": [1,2]}\n";
+    std::fs::write(home.path().join("body.json"), json).unwrap();
+    let output = run_kick(&home, &["post", "--raw", "-d", "@body.json", &server.uri()]).await;
+    assert_eq!(output.status, 0, "{}", output.stderr);
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body, json.as_bytes());
+}
+
+#[tokio::test]
+async fn test_raw_missing_file_fails_before_sending() {
+    let server = MockServer::start().await;
+    let home = TempDir::new().unwrap();
+
+    let output = run_kick(
+        &home,
+        &["post", "--raw", "-d", "@missing.xml", &server.uri()],
+    )
+    .await;
+    assert_eq!(output.status, 2);
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
@@ -0,0 +1,108 @@
+use kick::plugin::{PluginContext, PluginHook};
+use kick::prelude::*;
+use serde_json::json;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Counts calls to the error hook
+#[derive(Default)]
+struct ErrorCounter {
+    errors: AtomicU32,
+}
+
+#[async_trait]
+impl Plugin for ErrorCounter {
+    fn name(&self) -> &str {
+        "error_counter"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Counts error hook calls"
+    }
+
+    fn handles_hook(&self, hook: &PluginHook) -> bool {
+        matches!(hook, PluginHook::OnError)
+    }
+
+    async fn handle_error(&self, _error: &ApiError, _context: &PluginContext) -> Result<()> {
+        self.errors.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+async fn client_with_counter(temp_dir: &TempDir) -> (ApiClient, Arc<ErrorCounter>) {
+    let counter = Arc::new(ErrorCounter::default());
+    let mut plugins = PluginManager::new();
+    plugins.register_plugin(counter.clone()).unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_plugin_manager(plugins)
+        .build()
+        .await
+        .unwrap();
+    (client, counter)
+}
+
+#[tokio::test]
+async fn test_server_error_is_returned_as_data() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/broken"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("database unavailable"))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let (client, counter) = client_with_counter(&temp_dir).await;
+
+    let url = format!("{}/broken", server.uri());
+    let result = client.get_raw(&url).await.unwrap();
+    assert_eq!(result, (500, "database unavailable".to_string()));
+    assert_eq!(counter.errors.load(Ordering::SeqCst), 0);
+
+    // The regular methods still treat the status as an error
+    assert!(matches!(
+        client.get(&url).await,
+        Err(ApiError::HttpStatus { .. })
+    ));
+    assert_eq!(counter.errors.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_json_raw_returns_client_error_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/widgets"))
+        .respond_with(ResponseTemplate::new(422).set_body_string(r#"{"error":"name required"}"#))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let (client, _counter) = client_with_counter(&temp_dir).await;
+
+    let (status, body) = client
+        .post_json_raw(&format!("{}/widgets", server.uri()), &json!({}))
+        .await
+        .unwrap();
+    assert_eq!(status, 422);
+    assert!(body.contains("name required"));
+}
+
+#[tokio::test]
+async fn test_transport_failure_is_still_an_error() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let temp_dir = TempDir::new().unwrap();
+    let (client, _counter) = client_with_counter(&temp_dir).await;
+
+    let result = client.get_raw(&format!("http://127.0.0.1:{}/", port)).await;
+    assert!(result.is_err());
+}
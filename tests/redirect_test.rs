@@ -0,0 +1,242 @@
+use kick::prelude::*;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(temp_dir: &TempDir, max_redirects: usize) -> ApiClient {
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_redirects = max_redirects;
+    config.client.max_retries = 0;
+    config
+        .client
+        .default_headers
+        .insert("Authorization".to_string(), "Bearer secret".to_string());
+    ApiClient::new(config)
+}
+
+async fn redirect(server: &MockServer, verb: &str, from: &str, status: u16, location: &str) {
+    Mock::given(method(verb))
+        .and(path(from))
+        .respond_with(ResponseTemplate::new(status).insert_header("Location", location))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_get_follows_relative_redirect() {
+    let server = MockServer::start().await;
+    redirect(&server, "GET", "/old", 301, "/new").await;
+    Mock::given(method("GET"))
+        .and(path("/new"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("moved here"))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+
+    let body = client(&temp_dir, 10)
+        .get(&format!("{}/old", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(body, "moved here");
+}
+
+#[tokio::test]
+async fn test_download_follows_redirect_to_other_origin_without_credentials() {
+    let api = MockServer::start().await;
+    let cdn = MockServer::start().await;
+    redirect(
+        &api,
+        "GET",
+        "/files/report",
+        302,
+        &format!("{}/blob/123", cdn.uri()),
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/blob/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"report data".to_vec()))
+        .mount(&cdn)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+
+    let saved = client(&temp_dir, 10)
+        .download_file(&format!("{}/files/report", api.uri()), "report.bin")
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(saved).unwrap(), b"report data");
+    assert_eq!(
+        api.received_requests().await.unwrap()[0].headers["authorization"],
+        "Bearer secret"
+    );
+    let cdn_requests = cdn.received_requests().await.unwrap();
+    assert!(!cdn_requests[0].headers.contains_key("authorization"));
+}
+
+#[tokio::test]
+async fn test_redirect_to_other_origin_drops_host_override() {
+    let api = MockServer::start().await;
+    let cdn = MockServer::start().await;
+    redirect(&api, "GET", "/old", 301, "/files/report").await;
+    redirect(
+        &api,
+        "GET",
+        "/files/report",
+        302,
+        &format!("{}/blob/123", cdn.uri()),
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/blob/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("report data"))
+        .mount(&cdn)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_host_header("api.internal".to_string())
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+
+    let body = client.get(&format!("{}/old", api.uri())).await.unwrap();
+
+    assert_eq!(body, "report data");
+    // Same-origin hops keep the override
+    for request in api.received_requests().await.unwrap() {
+        assert_eq!(request.headers["host"], "api.internal");
+    }
+    let cdn_requests = cdn.received_requests().await.unwrap();
+    let cdn_authority = cdn.uri().trim_start_matches("http://").to_string();
+    assert_eq!(cdn_requests[0].headers["host"], cdn_authority.as_str());
+}
+
+#[tokio::test]
+async fn test_see_other_turns_post_into_get_without_body() {
+    let server = MockServer::start().await;
+    redirect(&server, "POST", "/jobs", 303, "/jobs/7").await;
+    Mock::given(method("GET"))
+        .and(path("/jobs/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("queued"))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+
+    let body = client(&temp_dir, 10)
+        .post_json(&format!("{}/jobs", server.uri()), &json!({"task": "build"}))
+        .await
+        .unwrap();
+
+    assert_eq!(body, "queued");
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[1].method.as_str(), "GET");
+    assert!(requests[1].body.is_empty());
+    assert!(!requests[1].headers.contains_key("content-type"));
+}
+
+#[tokio::test]
+async fn test_temporary_and_permanent_redirects_repeat_method_and_body() {
+    let server = MockServer::start().await;
+    redirect(&server, "POST", "/v1/items", 307, "/v2/items").await;
+    redirect(&server, "PUT", "/v1/items/1", 308, "/v2/items/1").await;
+    Mock::given(method("POST"))
+        .and(path("/v2/items"))
+        .and(body_json(json!({"name": "widget"})))
+        .respond_with(ResponseTemplate::new(201).set_body_string("created"))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/v2/items/1"))
+        .and(body_json(json!({"name": "gadget"})))
+        .respond_with(ResponseTemplate::new(200).set_body_string("updated"))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, 10);
+
+    let created = client
+        .post_json(
+            &format!("{}/v1/items", server.uri()),
+            &json!({"name": "widget"}),
+        )
+        .await
+        .unwrap();
+    let updated = client
+        .put_json(
+            &format!("{}/v1/items/1", server.uri()),
+            &json!({"name": "gadget"}),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(created, "created");
+    assert_eq!(updated, "updated");
+}
+
+#[tokio::test]
+async fn test_redirect_limit_is_an_error() {
+    let server = MockServer::start().await;
+    redirect(&server, "GET", "/loop", 302, "/loop").await;
+    let temp_dir = TempDir::new().unwrap();
+    let url = format!("{}/loop", server.uri());
+
+    let error = client(&temp_dir, 2).get(&url).await.unwrap_err();
+
+    assert!(matches!(error, ApiError::Other(_)), "{:?}", error);
+    assert!(
+        error.to_string().contains("max_redirects is 2"),
+        "{}",
+        error
+    );
+    // The original request plus two redirects
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_zero_max_redirects_disables_following() {
+    let server = MockServer::start().await;
+    redirect(&server, "GET", "/old", 302, "/new").await;
+    let temp_dir = TempDir::new().unwrap();
+
+    let error = client(&temp_dir, 0)
+        .get(&format!("{}/old", server.uri()))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ApiError::HttpStatus { status, .. } if status.as_u16() == 302),
+        "{:?}",
+        error
+    );
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_redirect_of_streamed_or_large_body_is_not_replayable() {
+    let server = MockServer::start().await;
+    redirect(&server, "POST", "/upload", 307, "/v2/upload").await;
+    Mock::given(method("POST"))
+        .and(path("/v2/upload"))
+        .respond_with(ResponseTemplate::new(201))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, 10);
+    let url = format!("{}/upload", server.uri());
+
+    let streamed = futures::stream::iter([Ok(json!({"id": 1}))]);
+    let large = "x".repeat(128 * 1024);
+    for error in [
+        client.post_ndjson_stream(&url, streamed).await.unwrap_err(),
+        client
+            .request_bytes(hyper::Method::POST, &url, large, "text/plain")
+            .await
+            .unwrap_err(),
+    ] {
+        assert!(error.to_string().contains("not replayable"), "{}", error);
+    }
+    // Neither body reached the redirect target
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests.iter().all(|r| r.url.path() == "/upload"));
+}
@@ -0,0 +1,38 @@
+use kick::prelude::*;
+use kick::RequestOptions;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_remove_headers_omits_defaults_for_one_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config
+        .client
+        .default_headers
+        .insert("X-Tenant".to_string(), "acme".to_string());
+    let client = ApiClient::new(config);
+    let url = format!("{}/data", server.uri());
+
+    let options = RequestOptions::new()
+        .remove_header("x-tenant")
+        .remove_header("Accept-Encoding");
+    client.get_with(&url, &options).await.unwrap();
+    client.get(&url).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("x-tenant"));
+    assert!(!requests[0].headers.contains_key("accept-encoding"));
+    assert!(requests[0].headers.contains_key("user-agent"));
+    // Later requests still carry the defaults
+    assert_eq!(requests[1].headers["x-tenant"], "acme");
+    assert!(requests[1].headers.contains_key("accept-encoding"));
+}
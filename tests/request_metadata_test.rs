@@ -0,0 +1,154 @@
+use kick::plugin::{PluginContext, PluginHook};
+use kick::prelude::*;
+use kick::RequestOptions;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Records the `tenant_id` each hook sees
+#[derive(Default)]
+struct TenantRecorder {
+    seen: Mutex<Vec<(PluginHook, Option<serde_json::Value>)>>,
+}
+
+impl TenantRecorder {
+    fn record(&self, context: &PluginContext) {
+        self.seen.lock().unwrap().push((
+            context.hook.clone(),
+            context.metadata.get("tenant_id").cloned(),
+        ));
+    }
+}
+
+#[async_trait]
+impl Plugin for TenantRecorder {
+    fn name(&self) -> &str {
+        "tenant_recorder"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Records request metadata seen by hooks"
+    }
+
+    fn handles_hook(&self, _hook: &PluginHook) -> bool {
+        true
+    }
+
+    async fn handle_pre_request(&self, _url: &str, context: &PluginContext) -> Result<()> {
+        self.record(context);
+        Ok(())
+    }
+
+    async fn handle_post_request(
+        &self,
+        _url: &str,
+        _status: u16,
+        context: &PluginContext,
+    ) -> Result<()> {
+        self.record(context);
+        Ok(())
+    }
+
+    async fn handle_pre_response(&self, _status: u16, context: &PluginContext) -> Result<()> {
+        self.record(context);
+        Ok(())
+    }
+
+    async fn handle_post_response(&self, _body: &str, context: &PluginContext) -> Result<()> {
+        self.record(context);
+        Ok(())
+    }
+
+    async fn handle_error(&self, _error: &ApiError, context: &PluginContext) -> Result<()> {
+        self.record(context);
+        Ok(())
+    }
+}
+
+async fn client_with_recorder(recorder: Arc<TenantRecorder>) -> (ApiClient, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let mut plugins = PluginManager::new();
+    plugins.register_plugin(recorder).unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf())).with_plugins(plugins);
+    (client, temp_dir)
+}
+
+#[tokio::test]
+async fn test_plugins_see_request_metadata() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .mount(&server)
+        .await;
+    let recorder = Arc::new(TenantRecorder::default());
+    let (client, _dir) = client_with_recorder(recorder.clone()).await;
+
+    let options = RequestOptions::new().metadata("tenant_id", "acme");
+    client
+        .get_with(&format!("{}/orders", server.uri()), &options)
+        .await
+        .unwrap();
+
+    let seen = recorder.seen.lock().unwrap().clone();
+    let hooks: Vec<PluginHook> = seen.iter().map(|(hook, _)| hook.clone()).collect();
+    assert_eq!(
+        hooks,
+        [
+            PluginHook::PreRequest,
+            PluginHook::PostRequest,
+            PluginHook::PreResponse,
+            PluginHook::PostResponse
+        ]
+    );
+    assert!(seen
+        .iter()
+        .all(|(_, tenant)| tenant == &Some(serde_json::json!("acme"))));
+
+    // Metadata is for plugins only and never becomes a header
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests[0].headers.get("tenant_id").is_none());
+}
+
+#[tokio::test]
+async fn test_error_hook_sees_request_metadata() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    let recorder = Arc::new(TenantRecorder::default());
+    let (client, _dir) = client_with_recorder(recorder.clone()).await;
+
+    let options = RequestOptions::new().metadata("tenant_id", 42);
+    assert!(client.get_with(&server.uri(), &options).await.is_err());
+
+    let seen = recorder.seen.lock().unwrap().clone();
+    let (hook, tenant) = seen.last().unwrap();
+    assert_eq!(hook, &PluginHook::OnError);
+    assert_eq!(tenant, &Some(serde_json::json!(42)));
+}
+
+#[tokio::test]
+async fn test_requests_without_metadata_leave_context_empty() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let recorder = Arc::new(TenantRecorder::default());
+    let (client, _dir) = client_with_recorder(recorder.clone()).await;
+
+    client.get(&server.uri()).await.unwrap();
+    assert!(recorder
+        .seen
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|(_, tenant)| tenant.is_none()));
+}
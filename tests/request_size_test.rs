@@ -0,0 +1,50 @@
+use kick::prelude::*;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client_with_limit(max_request_size: u64) -> ApiClient {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_request_size = max_request_size;
+    ApiClient::new(config)
+}
+
+#[tokio::test]
+async fn test_oversized_body_rejected_before_send() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let client = client_with_limit(64);
+    let payload = json!({"data": "x".repeat(100)});
+    let result = client
+        .post_json(&format!("{}/upload", server.uri()), &payload)
+        .await;
+
+    match result {
+        Err(ApiError::Other(msg)) => assert!(msg.contains("max_request_size"), "{}", msg),
+        other => panic!("Expected size limit error, got {:?}", other),
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_body_within_limit_is_sent() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("stored"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client_with_limit(1024);
+    let response = client
+        .put_json(&format!("{}/item", server.uri()), &json!({"ok": true}))
+        .await
+        .unwrap();
+    assert_eq!(response, "stored");
+}
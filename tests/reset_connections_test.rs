@@ -0,0 +1,57 @@
+use kick::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn test_client() -> ApiClient {
+    let temp_dir = TempDir::new().unwrap();
+    ApiClient::new(Config::new(temp_dir.path().to_path_buf()))
+}
+
+#[tokio::test]
+async fn test_request_succeeds_after_reset_connections() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/ping"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("pong"))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = test_client();
+    let url = format!("{}/ping", server.uri());
+
+    assert_eq!(client.get(&url).await.unwrap(), "pong");
+    client.reset_connections();
+    assert_eq!(client.get(&url).await.unwrap(), "pong");
+}
+
+#[tokio::test]
+async fn test_reset_connections_does_not_disrupt_in_flight_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/slow"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("done")
+                .set_delay(Duration::from_millis(200)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = Arc::new(test_client());
+    let url = format!("{}/slow", server.uri());
+
+    let in_flight = {
+        let client = client.clone();
+        let url = url.clone();
+        tokio::spawn(async move { client.get(&url).await })
+    };
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    client.reset_connections();
+
+    assert_eq!(in_flight.await.unwrap().unwrap(), "done");
+}
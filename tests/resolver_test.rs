@@ -0,0 +1,141 @@
+use kick::prelude::*;
+use kick::{Resolver, StaticResolver};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mock_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/discovered"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("via resolver"))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn client_with(resolver: Arc<dyn Resolver>, temp_dir: &TempDir) -> ApiClient {
+    ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_resolver(resolver)
+        .build()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_static_resolver_maps_name_to_chosen_ip() {
+    let server = mock_server().await;
+    let port = server.address().port();
+
+    let resolver = StaticResolver::new().with_host("orders.service.test", server.address().ip());
+    let temp_dir = TempDir::new().unwrap();
+    let client = client_with(Arc::new(resolver), &temp_dir).await;
+
+    let body = client
+        .get(&format!("http://orders.service.test:{}/discovered", port))
+        .await
+        .unwrap();
+    assert_eq!(body, "via resolver");
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(
+        requests[0].headers["host"],
+        format!("orders.service.test:{}", port)
+    );
+}
+
+/// Counts lookups and answers with loopback
+struct CountingResolver {
+    lookups: AtomicUsize,
+}
+
+#[async_trait]
+impl Resolver for CountingResolver {
+    async fn resolve(&self, _host: &str) -> Result<Vec<IpAddr>> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        Ok(vec!["127.0.0.1".parse().unwrap()])
+    }
+}
+
+#[tokio::test]
+async fn test_custom_resolver_is_consulted() {
+    let server = mock_server().await;
+    let resolver = Arc::new(CountingResolver {
+        lookups: AtomicUsize::new(0),
+    });
+    let temp_dir = TempDir::new().unwrap();
+    let client = client_with(resolver.clone(), &temp_dir).await;
+
+    client
+        .get(&format!(
+            "http://anything.test:{}/discovered",
+            server.address().port()
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resolver.lookups.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_unresolvable_host_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let client = client_with(Arc::new(StaticResolver::new()), &temp_dir).await;
+    assert!(client.get("http://unknown.test/").await.is_err());
+}
+
+#[tokio::test]
+async fn test_with_resolve_pins_host_and_port_without_lookup() {
+    let server = mock_server().await;
+    let port = server.address().port();
+    let resolver = Arc::new(CountingResolver {
+        lookups: AtomicUsize::new(0),
+    });
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_resolver(resolver.clone())
+        .with_resolve("example.com", port, server.address().ip())
+        .build()
+        .await
+        .unwrap();
+
+    let body = client
+        .get(&format!("http://example.com:{}/discovered", port))
+        .await
+        .unwrap();
+    assert_eq!(body, "via resolver");
+    assert_eq!(resolver.lookups.load(Ordering::SeqCst), 0);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].headers["host"], format!("example.com:{}", port));
+
+    // Only the pinned host goes around the resolver
+    client
+        .get(&format!("http://other.test:{}/discovered", port))
+        .await
+        .unwrap();
+    assert_eq!(resolver.lookups.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_with_resolve_does_not_pin_other_ports() {
+    let server = mock_server().await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_resolver(Arc::new(StaticResolver::new()))
+        .with_resolve(
+            "example.com",
+            server.address().port(),
+            server.address().ip(),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    assert!(client.get("http://example.com:1/discovered").await.is_err());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
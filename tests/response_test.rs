@@ -0,0 +1,84 @@
+use hyper::header::HeaderValue;
+use hyper::StatusCode;
+use kick::prelude::*;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_get_response_keeps_status_and_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .append_header(
+                    "link",
+                    "<https://api.example.com/items?page=2>; rel=\"next\"",
+                )
+                .append_header(
+                    "link",
+                    "<https://api.example.com/items?page=9>; rel=\"last\"",
+                )
+                .insert_header("x-ratelimit-remaining", "41")
+                .insert_header("x-label", HeaderValue::from_bytes(b"caf\xe9").unwrap())
+                .set_body_string("[1, 2, 3]"),
+        )
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    let url = format!("{}/items", server.uri());
+
+    let response = client.get_response(&url).await.unwrap();
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body, "[1, 2, 3]");
+    assert_eq!(response.headers["X-Ratelimit-Remaining"], "41");
+    assert_eq!(
+        response.headers["Link"],
+        "<https://api.example.com/items?page=2>; rel=\"next\", <https://api.example.com/items?page=9>; rel=\"last\""
+    );
+    assert_eq!(response.headers["X-Label"], "caf\u{fffd}");
+    // Lookups through `header` ignore casing
+    for name in [
+        "x-ratelimit-remaining",
+        "X-RateLimit-Remaining",
+        "X-RATELIMIT-REMAINING",
+    ] {
+        assert_eq!(response.header(name), Some("41"), "{}", name);
+    }
+    assert_eq!(response.header("x-missing"), None);
+
+    // The string methods return the same body
+    assert_eq!(client.get(&url).await.unwrap(), response.body);
+}
+
+#[tokio::test]
+async fn test_post_json_response_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items"))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .insert_header("location", "/items/7")
+                .set_body_string(r#"{"id":7}"#),
+        )
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let response = client
+        .post_json_response(
+            &format!("{}/items", server.uri()),
+            &json!({"name": "widget"}),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, StatusCode::CREATED);
+    assert_eq!(response.headers["Location"], "/items/7");
+    assert_eq!(response.body, r#"{"id":7}"#);
+}
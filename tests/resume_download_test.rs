@@ -0,0 +1,144 @@
+use kick::prelude::*;
+use kick::ClientEvent;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+fn content() -> Vec<u8> {
+    (0..50_000u32).map(|i| (i % 251) as u8).collect()
+}
+
+/// Serves `content()`, cutting the first response off after 80% of the body.
+///
+/// Returns the base URL and the `Range` header of every request received.
+async fn flaky_server(honour_ranges: bool) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let ranges = Arc::new(Mutex::new(Vec::new()));
+    let seen = ranges.clone();
+
+    tokio::spawn(async move {
+        let content = content();
+        let mut served = 0;
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut head = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                head.extend_from_slice(&buf[..n]);
+            }
+            let head = String::from_utf8_lossy(&head).to_lowercase();
+            let range = head
+                .lines()
+                .find_map(|line| line.strip_prefix("range: bytes="))
+                .map(|range| range.trim().to_string());
+            seen.lock().unwrap().push(range.clone());
+
+            let start = match range.filter(|_| honour_ranges) {
+                Some(range) => range.trim_end_matches('-').parse::<usize>().unwrap(),
+                None => 0,
+            };
+            let status = if start > 0 {
+                format!(
+                    "HTTP/1.1 206 Partial Content\r\ncontent-range: bytes {}-{}/{}\r\n",
+                    start,
+                    content.len() - 1,
+                    content.len()
+                )
+            } else {
+                "HTTP/1.1 200 OK\r\n".to_string()
+            };
+            let response_head = format!(
+                "{}accept-ranges: bytes\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                status,
+                content.len() - start
+            );
+            socket.write_all(response_head.as_bytes()).await.unwrap();
+
+            let end = if served == 0 {
+                content.len() * 8 / 10
+            } else {
+                content.len()
+            };
+            served += 1;
+            socket
+                .write_all(&content[start..end.max(start)])
+                .await
+                .unwrap();
+            // Dropping the socket closes the connection, short of Content-Length on the first reply
+        }
+    });
+
+    (url, ranges)
+}
+
+fn test_client(temp_dir: &TempDir) -> ApiClient {
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.retry_delay = 10;
+    ApiClient::new(config)
+}
+
+#[tokio::test]
+async fn test_interrupted_download_resumes_with_range() {
+    let (url, ranges) = flaky_server(true).await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = test_client(&temp_dir);
+    let mut events = client.subscribe();
+
+    let saved = tokio::time::timeout(
+        Duration::from_secs(10),
+        client.download_file(&format!("{}/big.bin", url), "big.bin"),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(std::fs::read(saved).unwrap(), content());
+    let ranges = ranges.lock().unwrap().clone();
+    assert_eq!(ranges, vec![None, Some("40000-".to_string())]);
+
+    let mut retried = false;
+    while let Ok(event) = events.try_recv() {
+        retried |= matches!(event, ClientEvent::RetryAttempted { attempt: 1, .. });
+    }
+    assert!(retried);
+}
+
+#[tokio::test]
+async fn test_interrupted_download_restarts_without_range_support() {
+    let (url, ranges) = flaky_server(false).await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = test_client(&temp_dir);
+
+    let saved = tokio::time::timeout(
+        Duration::from_secs(10),
+        client.download_file(&format!("{}/big.bin", url), "big.bin"),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    // The server sent the whole file again, which replaced the partial one
+    assert_eq!(std::fs::read(saved).unwrap(), content());
+    assert_eq!(ranges.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_interrupted_download_fails_without_retries() {
+    let (url, ranges) = flaky_server(true).await;
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_retries = 0;
+    let client = ApiClient::new(config);
+
+    let result = client
+        .download_file(&format!("{}/big.bin", url), "big.bin")
+        .await;
+    assert!(result.is_err());
+    assert_eq!(ranges.lock().unwrap().len(), 1);
+}
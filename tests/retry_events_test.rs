@@ -0,0 +1,104 @@
+use hyper::Method;
+use kick::plugin::{PluginContext, PluginHook};
+use kick::prelude::*;
+use kick::ClientEvent;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Records the metadata the retry hook receives
+#[derive(Default)]
+struct RetryRecorder {
+    retries: Mutex<Vec<(u32, serde_json::Value, serde_json::Value)>>,
+}
+
+#[async_trait]
+impl Plugin for RetryRecorder {
+    fn name(&self) -> &str {
+        "retry_recorder"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    fn description(&self) -> &str {
+        "Records retry hook calls"
+    }
+
+    fn handles_hook(&self, hook: &PluginHook) -> bool {
+        matches!(hook, PluginHook::OnRetry)
+    }
+
+    async fn handle_retry(&self, attempt: u32, context: &PluginContext) -> Result<()> {
+        self.retries.lock().unwrap().push((
+            attempt,
+            context.metadata["delay_ms"].clone(),
+            context.metadata["reason"].clone(),
+        ));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_retry_events_carry_delay_and_reason() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/flaky"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/flaky"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_retries = 3;
+    config.client.retry_delay = 20;
+    let recorder = Arc::new(RetryRecorder::default());
+    let mut plugins = PluginManager::new();
+    plugins.register_plugin(recorder.clone()).unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(config)
+        .with_plugin_manager(plugins)
+        .build()
+        .await
+        .unwrap();
+    let mut events = client.subscribe();
+
+    let body = client
+        .execute_request_with_retry(&format!("{}/flaky", server.uri()), Method::GET, None)
+        .await
+        .unwrap();
+    assert_eq!(body, "ok");
+
+    let mut retries = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let ClientEvent::RetryAttempted {
+            attempt,
+            delay,
+            reason,
+        } = event
+        {
+            retries.push((attempt, delay, reason));
+        }
+    }
+    assert_eq!(retries.len(), 2);
+    for (i, (attempt, delay, reason)) in retries.iter().enumerate() {
+        assert_eq!(*attempt, i as u32 + 1);
+        assert_eq!(*delay, Duration::from_millis(20));
+        assert!(reason.contains("503"), "{}", reason);
+    }
+
+    let hooks = recorder.retries.lock().unwrap();
+    assert_eq!(hooks.len(), 2);
+    assert_eq!(hooks[0].0, 1);
+    assert_eq!(hooks[0].1, 20);
+    assert_eq!(hooks[0].2, retries[0].2.as_str());
+}
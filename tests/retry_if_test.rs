@@ -0,0 +1,80 @@
+use hyper::Method;
+use kick::prelude::*;
+use kick::ClientEvent;
+use std::sync::Arc;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const RETRYABLE: &str = r#"{"status":"error","retryable":true}"#;
+
+/// Answers `/jobs` with a retryable body `failures` times, then succeeds
+async fn server(failures: u64) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/jobs"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(RETRYABLE))
+        .up_to_n_times(failures)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/jobs"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"status":"ok"}"#))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn client(temp_dir: &TempDir, max_retries: usize) -> ApiClient {
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_retries = max_retries;
+    config.client.retry_delay = 1;
+    ApiClientBuilder::new()
+        .with_config(config)
+        .with_retry_if(Arc::new(|body: &str| {
+            serde_json::from_str::<serde_json::Value>(body)
+                .is_ok_and(|json| json["retryable"] == true)
+        }))
+        .build()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_retryable_body_is_retried_until_success() {
+    let server = server(2).await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, 3).await;
+    let mut events = client.subscribe();
+
+    let body = client
+        .execute_request_with_retry(&format!("{}/jobs", server.uri()), Method::GET, None)
+        .await
+        .unwrap();
+
+    assert_eq!(body, r#"{"status":"ok"}"#);
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    let mut attempts = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let ClientEvent::RetryAttempted { attempt, .. } = event {
+            attempts.push(attempt);
+        }
+    }
+    assert_eq!(attempts, vec![1, 2]);
+}
+
+#[tokio::test]
+async fn test_last_retryable_body_is_returned_when_retries_run_out() {
+    let server = server(5).await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir, 1).await;
+
+    let body = client
+        .execute_request_with_retry(&format!("{}/jobs", server.uri()), Method::GET, None)
+        .await
+        .unwrap();
+
+    assert_eq!(body, RETRYABLE);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
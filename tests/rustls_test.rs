@@ -0,0 +1,112 @@
+#![cfg(feature = "rustls")]
+
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use kick::config::TlsConfig;
+use kick::prelude::*;
+use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+use rustls::pki_types::PrivatePkcs8KeyDer;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+
+/// Serve the request path back over TLS with a localhost certificate from a
+/// private CA; returns the port and the CA bundle path
+async fn start_tls_server(dir: &TempDir) -> (u16, PathBuf) {
+    let ca_key = KeyPair::generate().unwrap();
+    let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+    let server_key = KeyPair::generate().unwrap();
+    let server_cert = CertificateParams::new(vec!["localhost".to_string()])
+        .unwrap()
+        .signed_by(&server_key, &ca_cert, &ca_key)
+        .unwrap();
+    let ca_path = dir.path().join("ca.pem");
+    std::fs::write(&ca_path, ca_cert.pem()).unwrap();
+
+    let server_config = rustls::ServerConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .unwrap()
+    .with_no_client_auth()
+    .with_single_cert(
+        vec![server_cert.der().clone()],
+        PrivatePkcs8KeyDer::from(server_key.serialize_der()).into(),
+    )
+    .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(server_config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                // Handshake failures (e.g. an untrusting client) are expected in some tests
+                if let Ok(tls) = acceptor.accept(stream).await {
+                    let service = service_fn(|req: Request<hyper::body::Incoming>| async move {
+                        let body = format!("rustls-ok {}", req.uri().path());
+                        Ok::<_, hyper::Error>(Response::new(Full::new(Bytes::from(body))))
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(tls), service)
+                        .await;
+                }
+            });
+        }
+    });
+    (port, ca_path)
+}
+
+async fn client_trusting(ca_bundle: Option<PathBuf>, temp_dir: &TempDir) -> ApiClient {
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.max_retries = 0;
+    config.client.tls = TlsConfig {
+        ca_bundle,
+        ..TlsConfig::default()
+    };
+    ApiClientBuilder::new()
+        .with_config(config)
+        .build()
+        .await
+        .expect("Failed to create client")
+}
+
+#[test]
+fn test_rustls_backend_selected() {
+    assert_eq!(kick::client::tls_backend(), "rustls");
+}
+
+#[tokio::test]
+async fn test_https_request_with_rustls() {
+    let temp_dir = TempDir::new().unwrap();
+    let (port, ca_bundle) = start_tls_server(&temp_dir).await;
+    let client = client_trusting(Some(ca_bundle), &temp_dir).await;
+
+    let response = client
+        .get(&format!("https://localhost:{}/get", port))
+        .await
+        .expect("HTTPS GET over rustls failed");
+
+    assert_eq!(response, "rustls-ok /get");
+}
+
+#[tokio::test]
+async fn test_untrusted_certificate_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let (port, _) = start_tls_server(&temp_dir).await;
+    let client = client_trusting(None, &temp_dir).await;
+
+    assert!(client
+        .get(&format!("https://localhost:{}/get", port))
+        .await
+        .is_err());
+}
@@ -0,0 +1,132 @@
+use bytes::Bytes;
+use kick::prelude::*;
+use kick::SerializerRegistry;
+use serde_json::Value;
+use tempfile::TempDir;
+use wiremock::matchers::{body_string, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const KV: &str = "application/x-kv";
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Release {
+    name: String,
+    channel: String,
+}
+
+/// Flat objects as sorted `key=value` lines
+fn kv_registry() -> SerializerRegistry {
+    let mut registry = SerializerRegistry::new();
+    registry
+        .register(
+            KV,
+            |value| {
+                let object = value
+                    .as_object()
+                    .ok_or_else(|| ApiError::validation("x-kv bodies must be objects"))?;
+                let mut lines: Vec<String> = object
+                    .iter()
+                    .map(|(key, value)| format!("{}={}\n", key, value.as_str().unwrap_or_default()))
+                    .collect();
+                lines.sort();
+                Ok(Bytes::from(lines.concat()))
+            },
+            |body| {
+                let text = std::str::from_utf8(body).map_err(|e| ApiError::other(e.to_string()))?;
+                let object = text
+                    .lines()
+                    .filter_map(|line| line.split_once('='))
+                    .map(|(key, value)| (key.to_string(), Value::String(value.to_string())))
+                    .collect();
+                Ok(Value::Object(object))
+            },
+        )
+        .unwrap();
+    registry
+}
+
+#[test]
+fn test_custom_format_round_trips_through_registry() {
+    let registry = kv_registry();
+    let release = Release {
+        name: "kick".into(),
+        channel: "stable".into(),
+    };
+
+    let encoded = registry.encode(KV, &release).unwrap();
+    assert_eq!(&encoded[..], b"channel=stable\nname=kick\n");
+    let decoded: Release = registry
+        .decode("application/x-kv; charset=utf-8", &encoded)
+        .unwrap();
+    assert_eq!(decoded, release);
+}
+
+#[tokio::test]
+async fn test_post_typed_uses_registered_format() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/releases"))
+        .and(header("content-type", KV))
+        .and(body_string("channel=beta\nname=kick\n"))
+        .respond_with(ResponseTemplate::new(201).set_body_raw("channel=beta\nname=kick-1.0\n", KV))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_plugin_manager(PluginManager::new())
+        .with_serializers(kv_registry())
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(
+        client.capabilities().content_types,
+        ["application/json", KV]
+    );
+
+    let created: Release = client
+        .post_typed(
+            &format!("{}/releases", server.uri()),
+            KV,
+            &Release {
+                name: "kick".into(),
+                channel: "beta".into(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(created.name, "kick-1.0");
+}
+
+#[tokio::test]
+async fn test_get_typed_defaults_to_json_and_rejects_unknown_types() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/json"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_bytes(r#"{"name":"kick","channel":"stable"}"#),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/cbor"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(vec![0xa0], "application/cbor"))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let release: Release = client
+        .get_typed(&format!("{}/json", server.uri()))
+        .await
+        .unwrap();
+    assert_eq!(release.channel, "stable");
+
+    let err = client
+        .get_typed::<Release>(&format!("{}/cbor", server.uri()))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("application/cbor"));
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "storage-watch")]
+
+use futures::StreamExt;
+use kick::config::Config;
+use kick::storage::{StorageEvent, StorageManager};
+use std::time::Duration;
+use tempfile::TempDir;
+
+async fn next_event(watch: &mut kick::storage::StorageWatch) -> StorageEvent {
+    tokio::time::timeout(Duration::from_secs(5), watch.next())
+        .await
+        .expect("no storage event within 5s")
+        .expect("watch stream ended")
+}
+
+#[tokio::test]
+async fn test_creating_file_yields_created_event() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage = StorageManager::new(Config::new(temp_dir.path().to_path_buf()));
+    let mut watch = storage.watch().await.expect("Failed to watch storage");
+
+    // Several writes in quick succession are reported once
+    let path = storage.save_string("one", "notes.txt").await.unwrap();
+    storage.save_string("two", "notes.txt").await.unwrap();
+
+    let event = next_event(&mut watch).await;
+    assert_eq!(event, StorageEvent::Created(path.canonicalize().unwrap()));
+}
+
+#[tokio::test]
+async fn test_directories_are_not_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage = StorageManager::new(Config::new(temp_dir.path().to_path_buf()));
+    let mut watch = storage.watch().await.expect("Failed to watch storage");
+
+    std::fs::create_dir(temp_dir.path().join("subdir")).unwrap();
+    // New subdirectories are added to the recursive watch asynchronously
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let path = storage
+        .save_string("data", "subdir/file.txt")
+        .await
+        .unwrap();
+
+    let event = next_event(&mut watch).await;
+    assert_eq!(event, StorageEvent::Created(path.canonicalize().unwrap()));
+}
+
+#[tokio::test]
+async fn test_removing_file_yields_removed_event() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage = StorageManager::new(Config::new(temp_dir.path().to_path_buf()));
+    let path = storage.save_string("data", "old.txt").await.unwrap();
+    let path = path.canonicalize().unwrap();
+    let mut watch = storage.watch().await.expect("Failed to watch storage");
+
+    storage.delete_file("old.txt").await.unwrap();
+
+    let event = next_event(&mut watch).await;
+    assert_eq!(event, StorageEvent::Removed(path));
+}
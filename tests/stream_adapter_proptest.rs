@@ -0,0 +1,44 @@
+use kick::streaming::{BufferedStream, ChunkedStream};
+use kick::testing::feed_bytes_through;
+use proptest::prelude::*;
+
+fn arbitrary_chunks() -> impl Strategy<Value = Vec<Vec<u8>>> {
+    prop::collection::vec(prop::collection::vec(any::<u8>(), 0..64), 0..32)
+}
+
+proptest! {
+    #[test]
+    fn chunked_stream_emits_full_chunks_except_last(
+        chunks in arbitrary_chunks(),
+        chunk_size in 1usize..48,
+    ) {
+        let input: Vec<u8> = chunks.concat();
+        let output: Vec<_> = feed_bytes_through(|source| ChunkedStream::new(source, chunk_size), chunks)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        if let Some((last, full)) = output.split_last() {
+            for chunk in full {
+                prop_assert_eq!(chunk.len(), chunk_size);
+            }
+            prop_assert!(!last.is_empty() && last.len() <= chunk_size);
+        }
+        prop_assert_eq!(output.concat(), input);
+    }
+
+    #[test]
+    fn buffered_stream_preserves_bytes(
+        chunks in arbitrary_chunks(),
+        buffer_size in 1usize..128,
+    ) {
+        let input: Vec<u8> = chunks.concat();
+        let output: Vec<_> = feed_bytes_through(|source| BufferedStream::new(source, buffer_size), chunks)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        prop_assert!(output.iter().all(|chunk| !chunk.is_empty()));
+        prop_assert_eq!(output.concat(), input);
+    }
+}
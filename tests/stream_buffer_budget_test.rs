@@ -0,0 +1,57 @@
+use bytes::Bytes;
+use futures::channel::mpsc;
+use futures::{FutureExt, StreamExt};
+use kick::config::Config;
+use kick::streaming::StreamHandler;
+use tempfile::TempDir;
+
+type Chunk = Result<Bytes, std::io::Error>;
+
+const BUFFER_SIZE: usize = 1024;
+
+#[tokio::test]
+async fn test_buffered_streams_stay_within_budget() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.streaming.buffer_size = BUFFER_SIZE;
+    config.streaming.buffer_budget = Some(3 * BUFFER_SIZE);
+    let handler = StreamHandler::new(config);
+
+    let (senders, mut streams): (Vec<_>, Vec<_>) = (0..5)
+        .map(|_| {
+            let (tx, rx) = mpsc::unbounded::<Chunk>();
+            (tx, handler.create_buffered_stream(rx))
+        })
+        .unzip();
+
+    // Every stream starts filling a buffer; only three fit in the budget
+    for stream in &mut streams {
+        assert!(stream.next().now_or_never().is_none());
+        assert!(handler.stream_buffer_usage() <= 3 * BUFFER_SIZE);
+    }
+    assert_eq!(handler.stream_buffer_usage(), 3 * BUFFER_SIZE);
+
+    // A full buffer is yielded and its reservation handed to a waiting stream
+    senders[0]
+        .unbounded_send(Ok(Bytes::from(vec![1u8; BUFFER_SIZE])))
+        .unwrap();
+    let chunk = streams[0].next().now_or_never().flatten().unwrap().unwrap();
+    assert_eq!(chunk.len(), BUFFER_SIZE);
+    assert!(streams[3].next().now_or_never().is_none());
+    assert_eq!(handler.stream_buffer_usage(), 3 * BUFFER_SIZE);
+
+    // Drained concurrently, each stream waits its turn for a buffer
+    for (index, sender) in senders.into_iter().enumerate() {
+        sender
+            .unbounded_send(Ok(Bytes::from(vec![index as u8; 10])))
+            .unwrap();
+    }
+    let drained = futures::future::join_all(streams.iter_mut().map(|stream| async {
+        let rest: Vec<Bytes> = stream.map(|chunk| chunk.unwrap()).collect().await;
+        assert!(handler.stream_buffer_usage() <= 3 * BUFFER_SIZE);
+        rest.iter().map(Bytes::len).sum::<usize>()
+    }))
+    .await;
+    assert_eq!(drained, [10, 10, 10, 10, 10]);
+    assert_eq!(handler.stream_buffer_usage(), 0);
+}
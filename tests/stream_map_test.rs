@@ -0,0 +1,55 @@
+use bytes::Bytes;
+use futures::StreamExt;
+use kick::prelude::*;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn text_server(body: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/text"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_get_stream_map_uppercases_chunks() {
+    let text = "the quick brown fox jumps over the lazy dog\n".repeat(2000);
+    let server = text_server(&text).await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let stream = client
+        .get_stream_map(
+            &format!("{}/text", server.uri()),
+            |chunk: Bytes| async move { Ok(Bytes::from(chunk.to_ascii_uppercase())) },
+        )
+        .await
+        .unwrap();
+
+    let chunks: Vec<Bytes> = stream.map(|chunk| chunk.unwrap()).collect().await;
+    assert_eq!(chunks.concat(), text.to_uppercase().into_bytes());
+}
+
+#[tokio::test]
+async fn test_get_stream_map_propagates_transform_errors() {
+    let server = text_server("payload").await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+
+    let mut stream = client
+        .get_stream_map(
+            &format!("{}/text", server.uri()),
+            |_chunk: Bytes| async move { Err(ApiError::stream("cannot decrypt")) },
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(ApiError::Stream(_)))
+    ));
+}
@@ -0,0 +1,39 @@
+use kick::error::TimeoutPhase;
+use kick::prelude::*;
+use std::time::Duration;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_request_timeout_reports_phase_limit_and_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/slow"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(3)))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new(temp_dir.path().to_path_buf());
+    config.client.timeout = 1;
+    let client = ApiClient::new(config);
+
+    let url = format!("{}/slow", server.uri());
+    let error = client.get(&url).await.unwrap_err();
+    let ApiError::Timeout(detail) = &error else {
+        panic!("expected timeout, got {:?}", error);
+    };
+    assert_eq!(detail.phase, TimeoutPhase::Request);
+    assert_eq!(detail.limit, Duration::from_secs(1));
+    assert!(detail.elapsed >= detail.limit);
+
+    let message = error.to_string();
+    assert!(message.starts_with("request timeout after "), "{}", message);
+    assert!(message.contains("(limit 1s)"), "{}", message);
+    assert!(
+        message.ends_with(&format!("for GET {}", url)),
+        "{}",
+        message
+    );
+}
@@ -0,0 +1,137 @@
+use kick::prelude::*;
+use kick::{RequestOptions, TraceContextPlugin};
+use std::sync::Arc;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const PARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+fn is_lower_hex(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+async fn client_with_trace(trace: Arc<TraceContextPlugin>) -> (ApiClient, TempDir) {
+    let mut plugins = PluginManager::new();
+    plugins.register_plugin(trace).unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf())).with_plugins(plugins);
+    (client, temp_dir)
+}
+
+fn sent_header(request: &wiremock::Request, name: &str) -> Option<String> {
+    request
+        .headers
+        .get(name)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn test_new_trace_sends_well_formed_traceparent() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/traced"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let trace = Arc::new(TraceContextPlugin::new().with_retained_spans(16));
+    let (client, _temp_dir) = client_with_trace(trace.clone()).await;
+    let url = format!("{}/traced", server.uri());
+    client.get(&url).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let traceparent = sent_header(&requests[0], "traceparent").expect("traceparent sent");
+    let parts: Vec<&str> = traceparent.split('-').collect();
+    assert_eq!(parts.len(), 4, "{}", traceparent);
+    assert_eq!(parts[0], "00");
+    assert!(is_lower_hex(parts[1], 32), "{}", traceparent);
+    assert!(is_lower_hex(parts[2], 16), "{}", traceparent);
+    assert!(is_lower_hex(parts[3], 2), "{}", traceparent);
+    assert!(sent_header(&requests[0], "tracestate").is_none());
+
+    let spans = trace.finished_spans();
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].trace_id, parts[1]);
+    assert_eq!(spans[0].span_id, parts[2]);
+    assert_eq!(spans[0].parent_span_id, None);
+    assert_eq!(spans[0].url, url);
+    assert_eq!(spans[0].status, 200);
+}
+
+#[tokio::test]
+async fn test_trace_from_metadata_is_continued_and_status_recorded() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/missing"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let trace = Arc::new(TraceContextPlugin::new().with_retained_spans(16));
+    let (client, _temp_dir) = client_with_trace(trace.clone()).await;
+    let options = RequestOptions::new()
+        .metadata("traceparent", PARENT)
+        .metadata("tracestate", "vendor=abc");
+    assert!(client
+        .get_with(&format!("{}/missing", server.uri()), &options)
+        .await
+        .is_err());
+
+    let requests = server.received_requests().await.unwrap();
+    let traceparent = sent_header(&requests[0], "traceparent").unwrap();
+    assert!(
+        traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"),
+        "{}",
+        traceparent
+    );
+    assert!(traceparent.ends_with("-01"));
+    assert!(
+        !traceparent.contains("00f067aa0ba902b7"),
+        "a new span id is used"
+    );
+    assert_eq!(
+        sent_header(&requests[0], "tracestate").as_deref(),
+        Some("vendor=abc")
+    );
+
+    let spans = trace.take_finished_spans();
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].parent_span_id.as_deref(), Some("00f067aa0ba902b7"));
+    assert_eq!(spans[0].status, 404);
+    assert!(trace.finished_spans().is_empty());
+}
+
+#[tokio::test]
+async fn test_span_retention_is_opt_in_and_bounded() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let untracked = Arc::new(TraceContextPlugin::new());
+    let (client, _temp_dir) = client_with_trace(untracked.clone()).await;
+    client.get(&format!("{}/a", server.uri())).await.unwrap();
+    assert!(untracked.finished_spans().is_empty());
+    let requests = server.received_requests().await.unwrap();
+    assert!(sent_header(&requests[0], "traceparent").is_some());
+
+    let trace = Arc::new(TraceContextPlugin::new().with_retained_spans(2));
+    let (client, _temp_dir) = client_with_trace(trace.clone()).await;
+    for page in ["b", "c", "d"] {
+        client
+            .get(&format!("{}/{}", server.uri(), page))
+            .await
+            .unwrap();
+    }
+    let urls: Vec<String> = trace
+        .finished_spans()
+        .into_iter()
+        .map(|span| span.url)
+        .collect();
+    assert_eq!(
+        urls,
+        [format!("{}/c", server.uri()), format!("{}/d", server.uri())]
+    );
+}
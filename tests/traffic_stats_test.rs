@@ -0,0 +1,61 @@
+use futures::StreamExt;
+use kick::prelude::*;
+use kick::TrafficStats;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_traffic_stats_count_collected_and_streamed_bodies() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/echo"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/file"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![7u8; 10_000]))
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = ApiClient::new(Config::new(temp_dir.path().to_path_buf()));
+    assert_eq!(client.traffic_stats(), TrafficStats::default());
+
+    let payload = serde_json::json!({"name": "kick"});
+    client
+        .post_json(&format!("{}/echo", server.uri()), &payload)
+        .await
+        .unwrap();
+    let sent = serde_json::to_vec(&payload).unwrap().len() as u64;
+    assert_eq!(
+        client.traffic_stats(),
+        TrafficStats {
+            bytes_sent: sent,
+            bytes_received: 2,
+            requests: 1,
+        }
+    );
+
+    // Downloads stream straight to disk and still count
+    client
+        .download_file(&format!("{}/file", server.uri()), "file.bin")
+        .await
+        .unwrap();
+    let streamed: usize = client
+        .get_stream_map(&format!("{}/file", server.uri()), |chunk| async move {
+            Ok(chunk)
+        })
+        .await
+        .unwrap()
+        .map(|chunk| chunk.unwrap().len())
+        .fold(0, |total, len| async move { total + len })
+        .await;
+    assert_eq!(streamed, 10_000);
+
+    let stats = client.traffic_stats();
+    assert_eq!(stats.bytes_sent, sent);
+    assert_eq!(stats.bytes_received, 2 + 10_000 + 10_000);
+    assert_eq!(stats.requests, 3);
+}
@@ -0,0 +1,70 @@
+use kick::prelude::*;
+use kick::RequestOptions;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn client(temp_dir: &TempDir) -> ApiClient {
+    ApiClientBuilder::new()
+        .with_config(Config::new(temp_dir.path().to_path_buf()))
+        .with_plugin_manager(PluginManager::new())
+        .with_user_agent("kick-default/1.0".to_string())
+        .build()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_user_agent_overridden_per_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/user-agent"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir).await;
+    let url = format!("{}/user-agent", server.uri());
+
+    for user_agent in ["probe-a/1.0", "probe-b/2.0"] {
+        client
+            .get_with(&url, &RequestOptions::new().user_agent(user_agent))
+            .await
+            .unwrap();
+    }
+    client.get(&url).await.unwrap();
+
+    let sent: Vec<Vec<_>> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| {
+            request
+                .headers
+                .get_all("user-agent")
+                .iter()
+                .cloned()
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        sent,
+        [["probe-a/1.0"], ["probe-b/2.0"], ["kick-default/1.0"]]
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_user_agent_override_is_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let client = client(&temp_dir).await;
+
+    let result = client
+        .get_with(
+            "http://127.0.0.1:9/",
+            &RequestOptions::new().user_agent("evil\r\nX-Injected: 1"),
+        )
+        .await;
+    // Rejected by header validation before any connection is attempted
+    assert!(matches!(result, Err(ApiError::Other(_))), "{:?}", result);
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::run_kick;
+use tempfile::TempDir;
+
+async fn kick_version(cwd: &TempDir) -> String {
+    let output = run_kick(cwd, &["--version"]).await;
+    assert_eq!(output.status, 0);
+    output.stdout
+}
+
+#[tokio::test]
+async fn test_version_shows_embedded_license_outside_repo() {
+    let cwd = TempDir::new().unwrap();
+    let stdout = kick_version(&cwd).await;
+
+    assert!(stdout.contains("Apache License"), "{}", stdout);
+    assert!(
+        stdout.contains("TERMS AND CONDITIONS FOR USE"),
+        "{}",
+        stdout
+    );
+    let logo = include_str!("../logo.txt");
+    assert!(stdout.contains(logo.lines().next().unwrap()), "{}", stdout);
+}
+
+#[tokio::test]
+async fn test_files_in_working_directory_override_embedded_copies() {
+    let cwd = TempDir::new().unwrap();
+    std::fs::write(cwd.path().join("logo.txt"), "CUSTOM LOGO\n").unwrap();
+    std::fs::write(
+        cwd.path().join("LICENSE"),
+        "Proprietary build, all rights reserved\n",
+    )
+    .unwrap();
+    let stdout = kick_version(&cwd).await;
+
+    assert!(stdout.starts_with("CUSTOM LOGO\n"), "{}", stdout);
+    assert!(stdout.contains("Proprietary build"), "{}", stdout);
+    assert!(!stdout.contains("Apache License"), "{}", stdout);
+}